@@ -74,18 +74,11 @@ async fn main() {
     ];
 
     tokio::spawn(async move {
-        loop {
-            match logs.next().await {
-                Some(log) => {
-                    if StructuredFormatter.is_idle_signal(&log) {
-                        logs.notify_when_new_tasks().await;
-                    } else {
-                        println!("Log: {:#?}", log);
-                    }
-                }
-                None => {
-                    break;
-                }
+        while let Some(log) = logs.next().await {
+            if StructuredFormatter.is_idle_signal(&log) {
+                logs.notify_when_new_tasks().await;
+            } else {
+                println!("Log: {:#?}", log);
             }
         }
     });
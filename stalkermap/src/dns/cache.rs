@@ -0,0 +1,285 @@
+//! # DNS Response Cache
+//!
+//! A small in-memory cache for DNS responses, keyed by `(name, record_type)`.
+//!
+//! Repeated scans of the same hosts would otherwise re-query the name servers
+//! every time. [`DnsCache`] keeps each decoded [`DnsMessage`] until the smallest
+//! TTL of its records expires (see [`DnsMessage::min_ttl`]).
+//!
+//! ## Behaviour
+//!
+//! - Entries expire after `min_ttl()` seconds from insertion.
+//! - Messages without any answer/authority record are **not** cached.
+//! - Eviction is lazy: an expired entry is only removed when it is looked up.
+//! - The cache is safe to share between threads (`RwLock` based).
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use stalkermap::dns::cache::{DnsCache, cached_resolve_ipv4};
+//!
+//! let cache = DnsCache::new();
+//!
+//! // First call goes to the network
+//! let first = cached_resolve_ipv4("example.com", &cache)?;
+//! // Second call (within the TTL) is served from memory
+//! let second = cached_resolve_ipv4("example.com", &cache)?;
+//!
+//! assert_eq!(first, second);
+//! ```
+use crate::dns::resolver::{DnsMessage, ResolverErrors, resolve_ipv4};
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+/// Record type code used for `A` lookups (RFC 1035 §3.2.2).
+const TYPE_A: u16 = 1;
+
+/// Thread-safe cache of DNS responses keyed by `(name, record_type)`.
+///
+/// Each entry stores the decoded [`DnsMessage`] together with the [`Instant`]
+/// at which it expires.
+///
+/// The clock used to compute expiry is injectable via [`DnsCache::with_clock`],
+/// which is mostly useful for tests.
+pub struct DnsCache {
+    entries: RwLock<HashMap<(String, u16), (DnsMessage, Instant)>>,
+    clock: Box<dyn Fn() -> Instant + Send + Sync>,
+}
+
+impl Default for DnsCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DnsCache {
+    /// Creates an empty cache using [`Instant::now`] as its clock.
+    pub fn new() -> Self {
+        Self::with_clock(Instant::now)
+    }
+
+    /// Creates an empty cache using a custom clock.
+    pub fn with_clock<C>(clock: C) -> Self
+    where
+        C: Fn() -> Instant + Send + Sync + 'static,
+    {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            clock: Box::new(clock),
+        }
+    }
+
+    /// Returns the cached response for `(name, record_type)` if it has not expired.
+    ///
+    /// An expired entry is removed from the cache and `None` is returned.
+    pub fn get(&self, name: &str, record_type: u16) -> Option<DnsMessage> {
+        let key = (name.to_string(), record_type);
+        let now = (self.clock)();
+
+        {
+            let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+            match entries.get(&key) {
+                Some((msg, expires_at)) if now < *expires_at => return Some(msg.clone()),
+                Some(_) => {}
+                None => return None,
+            }
+        }
+
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        // Re-check under the write lock, another thread may have refreshed the entry.
+        if matches!(entries.get(&key), Some((_, expires_at)) if now >= *expires_at) {
+            entries.remove(&key);
+        }
+        None
+    }
+
+    /// Stores `msg` for `(name, record_type)`, expiring after `msg.min_ttl()` seconds.
+    ///
+    /// Messages without a TTL (no answer or authority records) are ignored.
+    pub fn insert(&self, name: &str, record_type: u16, msg: DnsMessage) {
+        let Some(ttl) = msg.min_ttl() else {
+            return;
+        };
+        let expires_at = (self.clock)() + Duration::from_secs(ttl as u64);
+
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        entries.insert((name.to_string(), record_type), (msg, expires_at));
+    }
+
+    /// Removes every entry from the cache.
+    pub fn clear(&self) {
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Returns the number of stored entries, including expired ones not yet evicted.
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Returns `true` if the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cached response or calls `resolve` and caches its result.
+    fn get_or_resolve<E>(
+        &self,
+        name: &str,
+        record_type: u16,
+        resolve: impl FnOnce() -> Result<DnsMessage, E>,
+    ) -> Result<DnsMessage, E> {
+        if let Some(msg) = self.get(name, record_type) {
+            return Ok(msg);
+        }
+
+        let msg = resolve()?;
+        self.insert(name, record_type, msg.clone());
+        Ok(msg)
+    }
+}
+
+/// Resolves an IPv4 (`A`) record, serving it from `cache` when possible.
+///
+/// On a cache miss the query is sent with [`resolve_ipv4`] and the response
+/// is stored in the cache.
+///
+/// # Errors
+/// Returns [`ResolverErrors`] if the query could not be sent or decoded.
+pub fn cached_resolve_ipv4(name: &str, cache: &DnsCache) -> Result<DnsMessage, ResolverErrors> {
+    cache.get_or_resolve(name, TYPE_A, || resolve_ipv4(name))
+}
+
+/// Resolves an IPv4 (`A`) record asynchronously, serving it from `cache` when possible.
+///
+/// On a cache miss the query is sent with
+/// [`resolve_ipv4_async`](crate::dns::resolver::resolve_ipv4_async) and the response
+/// is stored in the cache.
+///
+/// # Errors
+/// Returns [`ResolverErrorsAsync`](crate::dns::resolver::ResolverErrorsAsync) if the query
+/// could not be sent or decoded.
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
+#[cfg(feature = "tokio-dep")]
+pub async fn cached_resolve_ipv4_async(
+    name: &str,
+    cache: &DnsCache,
+) -> Result<DnsMessage, crate::dns::resolver::ResolverErrorsAsync> {
+    if let Some(msg) = cache.get(name, TYPE_A) {
+        return Ok(msg);
+    }
+
+    let msg = crate::dns::resolver::resolve_ipv4_async(name).await?;
+    cache.insert(name, TYPE_A, msg.clone());
+    Ok(msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::resolver::{AnswerSection, HeaderSection, QuestionSection, RecordData};
+    use std::net::Ipv4Addr;
+    use std::sync::{Arc, Mutex};
+
+    fn message_with_ttl(ttl: i32) -> DnsMessage {
+        DnsMessage {
+            header: HeaderSection {
+                id: 1,
+                flags: 0x8180,
+                qd_count: 1,
+                an_count: 1,
+                ns_count: 0,
+                ar_count: 0,
+            },
+            question: QuestionSection {
+                name: "example.com".to_string(),
+                record_type: TYPE_A,
+                class: 1,
+            },
+            answer: vec![AnswerSection {
+                owner_name: "example.com".to_string(),
+                record_type: TYPE_A,
+                class: 1,
+                ttl,
+                rd_length: 4,
+                r_data: RecordData::A(Ipv4Addr::new(93, 184, 216, 34)),
+            }],
+            authority: Vec::new(),
+            additional: Vec::new(),
+        }
+    }
+
+    fn fake_clock() -> (Arc<Mutex<Instant>>, DnsCache) {
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock = now.clone();
+        let cache = DnsCache::with_clock(move || *clock.lock().unwrap());
+        (now, cache)
+    }
+
+    #[test]
+    fn test_dns_cache_hit_within_ttl() {
+        let (now, cache) = fake_clock();
+        let mut queries = 0;
+
+        let first = cache
+            .get_or_resolve("example.com", TYPE_A, || {
+                queries += 1;
+                Ok::<_, ()>(message_with_ttl(60))
+            })
+            .unwrap();
+
+        *now.lock().unwrap() += Duration::from_secs(30);
+
+        let second = cache
+            .get_or_resolve("example.com", TYPE_A, || {
+                queries += 1;
+                Ok::<_, ()>(message_with_ttl(60))
+            })
+            .unwrap();
+
+        assert_eq!(queries, 1);
+        assert_eq!(first, second);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_dns_cache_expiry_forces_requery() {
+        let (now, cache) = fake_clock();
+        let mut queries = 0;
+
+        cache
+            .get_or_resolve("example.com", TYPE_A, || {
+                queries += 1;
+                Ok::<_, ()>(message_with_ttl(60))
+            })
+            .unwrap();
+
+        *now.lock().unwrap() += Duration::from_secs(61);
+        assert!(cache.get("example.com", TYPE_A).is_none());
+        assert!(cache.is_empty());
+
+        cache
+            .get_or_resolve("example.com", TYPE_A, || {
+                queries += 1;
+                Ok::<_, ()>(message_with_ttl(60))
+            })
+            .unwrap();
+
+        assert_eq!(queries, 2);
+    }
+
+    #[test]
+    fn test_dns_cache_skips_messages_without_ttl() {
+        let cache = DnsCache::new();
+        let mut msg = message_with_ttl(60);
+        msg.answer.clear();
+
+        cache.insert("example.com", TYPE_A, msg);
+        assert!(cache.is_empty());
+    }
+}
@@ -21,8 +21,10 @@
 //! 1. The `message` buffer is updated with the compressed domain name bytes.
 //! 2. The `pointer_map` keeps track of each suffix and its offset in the buffer,
 //!    allowing reuse of previous labels with pointers.
-//! 3. Each label is validated against DNS limits (63 bytes per label, 255 bytes
-//!    per full domain name). Errors are returned if limits are exceeded.
+//! 3. Each label is validated against DNS limits ([`MAX_LABEL_LEN`] bytes per label,
+//!    [`MAX_NAME_LEN`] bytes per full domain name). Errors are returned if limits are
+//!    exceeded. The same checks are available without a buffer through
+//!    [`MessageCompressor::validate_name`].
 //!
 //! # Usage
 //!
@@ -58,6 +60,14 @@
 //! - <https://datatracker.ietf.org/doc/html/rfc1035>
 use std::{collections::HashMap, error::Error, fmt::Display};
 
+/// Maximum length of a single label, in bytes (RFC 1035 §2.3.4).
+pub const MAX_LABEL_LEN: usize = 63;
+
+/// Maximum length of a full domain name, in bytes (RFC 1035 §2.3.4).
+///
+/// Names of this length or longer are rejected.
+pub const MAX_NAME_LEN: usize = 255;
+
 #[derive(PartialEq, Eq, Hash)]
 pub struct MessageCompressor {}
 
@@ -83,21 +93,21 @@ impl MessageCompressor {
     /// the start of the message (i.e., the first octet of the ID field in the
     /// domain header).  A zero offset specifies the first byte of the ID field,
     /// etc.
+    ///
+    /// The name is checked with [`MessageCompressor::validate_name`] first, so
+    /// nothing is written to `message` when it is invalid.
     pub fn compress(
         name: &str,
         message: &mut Vec<u8>,
         pointer_map: &mut HashMap<String, usize>,
     ) -> Result<(), CompressorErrors> {
+        Self::validate_name(name)?;
+
         let labels: Vec<&str> = name.split('.').collect();
         // Position is the current offset inside the buffer.
         // pointer_map -> (suffix, position)
         let mut position = message.len();
 
-        // If name is bigger than 255 bytes
-        if name.len() >= 0xFF {
-            return Err(CompressorErrors::InvalidName(name.to_string()));
-        }
-
         if name == "." || name.is_empty() {
             message.push(0);
             return Ok(());
@@ -118,11 +128,6 @@ impl MessageCompressor {
                 } else {
                     //offset to large to encode as a pointer; writing labels normally
                     let label = labels[i];
-                    let len = label.len();
-
-                    if len > 63 {
-                        return Err(CompressorErrors::LabelTooLong(label.to_string()));
-                    }
                     message.push(label.len() as u8);
                     message.extend_from_slice(label.as_bytes());
 
@@ -132,11 +137,6 @@ impl MessageCompressor {
                 pointer_map.insert(suffix.clone(), position);
 
                 let label = labels[i];
-                let len = label.len();
-
-                if len > 63 {
-                    return Err(CompressorErrors::LabelTooLong(label.to_string()));
-                }
                 message.push(label.len() as u8);
                 message.extend_from_slice(label.as_bytes());
 
@@ -147,9 +147,114 @@ impl MessageCompressor {
         message.push(0);
         Ok(())
     }
+
+    /// Checks `name` against the DNS length limits without encoding it.
+    ///
+    /// Useful to validate user input before building a message.
+    ///
+    /// # Errors
+    /// - [`CompressorErrors::InvalidName`] if the name is [`MAX_NAME_LEN`] bytes or longer.
+    /// - [`CompressorErrors::LabelTooLong`] if a label is longer than [`MAX_LABEL_LEN`] bytes.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use stalkermap::dns::compressor::agnostic::MessageCompressor;
+    ///
+    /// assert!(MessageCompressor::validate_name("www.example.com").is_ok());
+    /// assert!(MessageCompressor::validate_name(&"a".repeat(64)).is_err());
+    /// ```
+    pub fn validate_name(name: &str) -> Result<(), CompressorErrors> {
+        if name.len() >= MAX_NAME_LEN {
+            return Err(CompressorErrors::InvalidName(name.to_string()));
+        }
+
+        match name.split('.').find(|label| label.len() > MAX_LABEL_LEN) {
+            Some(label) => Err(CompressorErrors::LabelTooLong(label.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads a (possibly compressed) domain name starting at `offset` in `buf`.
+    ///
+    /// Pointers (RFC1035 §4.1.4) are followed anywhere in `buf`, so the whole
+    /// message must be passed, not only the section being decoded. On success
+    /// `offset` is moved past the name as it appears at the original position
+    /// (i.e. past the first pointer, if any).
+    ///
+    /// # Errors
+    /// Returns [`DecompressorErrors`] if the name runs past the end of `buf` or
+    /// contains more than 10 nested pointers.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use stalkermap::dns::compressor::agnostic::MessageCompressor;
+    ///
+    /// let buf = [3, b'w', b'w', b'w', 0, 0xC0, 0x00];
+    /// let mut offset = 5;
+    /// assert_eq!(MessageCompressor::decompress_name(&buf, &mut offset).unwrap(), "www");
+    /// assert_eq!(offset, 7);
+    /// ```
+    pub fn decompress_name(buf: &[u8], offset: &mut usize) -> Result<String, DecompressorErrors> {
+        let mut labels = Vec::new();
+        let mut jumped = false;
+        let mut current_offset = *offset;
+        let mut seen_pointers = 0;
+
+        loop {
+            if current_offset >= buf.len() {
+                return Err(DecompressorErrors::OutOfBounds);
+            }
+
+            let len = buf[current_offset] as usize;
+
+            if len & 0xC0 == 0xC0 {
+                if current_offset + 1 >= buf.len() {
+                    return Err(DecompressorErrors::IncompletePointer);
+                }
+
+                let pointer_offset = ((len & 0x3F) << 8) | buf[current_offset + 1] as usize;
+                if pointer_offset >= buf.len() {
+                    return Err(DecompressorErrors::PointerOffsetOOB);
+                }
+
+                seen_pointers += 1;
+                if seen_pointers > 10 {
+                    return Err(DecompressorErrors::TooManyNestedPointers);
+                }
+
+                if !jumped {
+                    *offset = current_offset + 2;
+                    jumped = true;
+                }
+
+                current_offset = pointer_offset;
+                continue;
+            }
+
+            if len == 0 {
+                current_offset += 1;
+                if !jumped {
+                    *offset = current_offset;
+                }
+                break;
+            }
+
+            current_offset += 1;
+            if current_offset + len > buf.len() {
+                return Err(DecompressorErrors::InvalidLabelSize);
+            }
+
+            let label = String::from_utf8_lossy(&buf[current_offset..current_offset + len]);
+            labels.push(label.into_owned());
+            current_offset += len;
+        }
+
+        Ok(labels.join("."))
+    }
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum CompressorErrors {
     LabelTooLong(String),
     InvalidName(String),
@@ -166,6 +271,38 @@ impl Display for CompressorErrors {
 
 impl Error for CompressorErrors {}
 
+/// Errors returned by [`MessageCompressor::decompress_name`].
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum DecompressorErrors {
+    /// A pointer is missing its second byte.
+    IncompletePointer,
+    /// The name runs past the end of the buffer.
+    OutOfBounds,
+    /// A pointer refers to an offset outside the buffer.
+    PointerOffsetOOB,
+    /// More than 10 pointers were followed (probably a loop).
+    TooManyNestedPointers,
+    /// A label length exceeds the remaining buffer.
+    InvalidLabelSize,
+}
+
+impl Display for DecompressorErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecompressorErrors::OutOfBounds => write!(f, "Out of bounds while decompressing name"),
+            DecompressorErrors::IncompletePointer => write!(f, "Incomplete pointer in name"),
+            DecompressorErrors::PointerOffsetOOB => write!(f, "Pointer offset out of bounds"),
+            DecompressorErrors::TooManyNestedPointers => {
+                write!(f, "Too many nested pointers (possible loop)")
+            }
+            DecompressorErrors::InvalidLabelSize => write!(f, "Label length exceeds buffer size"),
+        }
+    }
+}
+
+impl Error for DecompressorErrors {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,6 +348,24 @@ mod tests {
         let name = format!("{}.com", long_label);
         let result = MessageCompressor::compress(&name, &mut message, &mut pointer_map);
         assert!(matches!(result, Err(CompressorErrors::LabelTooLong(_))));
+        assert!(message.is_empty());
+    }
+
+    #[test]
+    fn test_compressor_validate_name() {
+        let long_label = "a".repeat(MAX_LABEL_LEN + 1);
+        assert!(matches!(
+            MessageCompressor::validate_name(&format!("www.{}.com", long_label)),
+            Err(CompressorErrors::LabelTooLong(label)) if label == long_label
+        ));
+        assert!(matches!(
+            MessageCompressor::validate_name(&"a.".repeat(MAX_NAME_LEN)),
+            Err(CompressorErrors::InvalidName(_))
+        ));
+
+        assert!(MessageCompressor::validate_name("www.example.com").is_ok());
+        assert!(MessageCompressor::validate_name(&"a".repeat(MAX_LABEL_LEN)).is_ok());
+        assert!(MessageCompressor::validate_name(".").is_ok());
     }
 
     #[test]
@@ -254,4 +409,37 @@ mod tests {
         let pointer_bytes = &message[pointer_pos..pointer_pos + 2];
         assert_eq!(pointer_bytes[0] >> 6, 0b11); // top 2 bits is 11
     }
+
+    #[test]
+    fn test_compressor_decompress_name_round_trip() {
+        let mut message = Vec::new();
+        let mut pointer_map = HashMap::new();
+        MessageCompressor::compress("www.example.com", &mut message, &mut pointer_map).unwrap();
+        MessageCompressor::compress("mail.example.com", &mut message, &mut pointer_map).unwrap();
+
+        let mut offset = 0;
+        let first = MessageCompressor::decompress_name(&message, &mut offset).unwrap();
+        assert_eq!(first, "www.example.com");
+
+        let second = MessageCompressor::decompress_name(&message, &mut offset).unwrap();
+        assert_eq!(second, "mail.example.com");
+        assert_eq!(offset, message.len());
+    }
+
+    #[test]
+    fn test_compressor_decompress_name_errors() {
+        // Truncated label
+        let mut offset = 0;
+        assert_eq!(
+            MessageCompressor::decompress_name(&[5, b'a', b'b'], &mut offset),
+            Err(DecompressorErrors::InvalidLabelSize)
+        );
+
+        // Pointer to itself
+        let mut offset = 0;
+        assert_eq!(
+            MessageCompressor::decompress_name(&[0xC0, 0x00], &mut offset),
+            Err(DecompressorErrors::TooManyNestedPointers)
+        );
+    }
 }
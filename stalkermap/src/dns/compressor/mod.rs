@@ -18,12 +18,17 @@ compile_error!("Features `agnostic` and (`std`/`tokio`) cannot be enabled at the
 cfg_if::cfg_if! {
     if #[cfg(any(feature = "std", feature = "tokio-dep"))] {
         mod standard;
+        pub use self::standard::CompressorErrors;
         pub(crate) use self::standard::{DecompressorErrors, MessageCompressor};
     } else if #[cfg(feature = "agnostic")] {
         pub mod agnostic;
-        pub use self::agnostic::{CompressorErrors, MessageCompressor};
+        pub use self::agnostic::{
+            CompressorErrors, DecompressorErrors, MAX_LABEL_LEN, MAX_NAME_LEN, MessageCompressor,
+        };
     } else if #[cfg(doc)] {
         pub mod agnostic;
-        pub use self::agnostic::{CompressorErrors, MessageCompressor};
+        pub use self::agnostic::{
+            CompressorErrors, DecompressorErrors, MAX_LABEL_LEN, MAX_NAME_LEN, MessageCompressor,
+        };
     }
 }
@@ -186,6 +186,7 @@ impl MessageCompressor {
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum DecompressorErrors {
     IncompletePointer,
     OutOfBounds,
@@ -209,9 +210,13 @@ impl Display for DecompressorErrors {
 }
 impl Error for DecompressorErrors {}
 
+/// Errors returned when a domain name can't be encoded.
 #[derive(Debug)]
-pub(crate) enum CompressorErrors {
+#[non_exhaustive]
+pub enum CompressorErrors {
+    /// A label is longer than 63 bytes.
     LabelTooLong(String),
+    /// The whole name is longer than 255 bytes.
     InvalidName(String),
 }
 
@@ -27,6 +27,7 @@
 //! - `resolver` — High-level API for sending queries and resolving DNS records.
 //! - `compressor` — Handles name compression and message encoding/decoding.
 //! - `transporter` — Utilities for managing DNS server lists and transport details.
+//! - `cache` — In-memory response cache honouring record TTLs (`std` / `tokio-dep`).
 //!
 //! ## Quick Example
 //!
@@ -73,6 +74,9 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(any(feature = "std", feature = "tokio-dep"))]
+pub mod cache;
+
 //#[cfg(any(feature = "std", feature = "tokio-dep", doc))]
 //mod compressor;
 
@@ -12,12 +12,23 @@
 //! - Parsing and inspecting DNS responses manually.
 //!
 //! ## What this mode provides
-//! - A [`DnsMessage`] struct to hold the full DNS message (header, question, answer, authority, additional).
-//! - [`HeaderSection`] with support for encoding/decoding [`DnsHeaderFlags`].
+//! - A [`DnsMessage`] struct to hold the full DNS message (header, questions, answer, authority, additional).
+//!   Multiple questions are supported; `qd_count` is always derived from them when encoding.
+//! - [`HeaderSection`] with support for encoding/decoding [`DnsHeaderFlags`], plus a validating
+//!   [`DnsHeaderFlagsBuilder`].
 //! - [`QuestionSection`] and resource record structs ([`AnswerSection`], [`AuthoritySection`], [`AdditionalSection`]).
 //! - An [`OpCodeOptions`] enum for the DNS opcodes (Standard, Inverse, ServerStatus).
 //! - A [`RecordType`] enum with common record types (A, MX, TXT, etc.).
+//! - A [`RecordData`] enum holding typed RDATA for resource records.
 //! - Integration with [`MessageCompressor`] to apply RFC 1035-compliant name compression.
+//! - Full message encoding (questions **and** resource records) via [`DnsMessage::encode_full`],
+//!   for building responses in tests or stub servers.
+//! - Message decoding via [`DnsMessage::decode_query`], to inspect responses received over
+//!   your own transport.
+//! - A [`ResponseBuilder`] (see [`DnsMessage::response_builder`]) answering a decoded query,
+//!   e.g. for a local stub DNS server.
+//! - Human-readable rendering: `Display` for [`QuestionSection`] and [`RecordData`], and a
+//!   `dig`-style [`DnsMessage::summary`].
 //!
 //! ## Example
 //! ```rust,no_run
@@ -34,25 +45,44 @@
 //!
 //! [RFC 1035]: https://datatracker.ietf.org/doc/html/rfc1035
 
-use crate::dns::compressor::MessageCompressor;
+use crate::dns::compressor::{CompressorErrors, DecompressorErrors, MessageCompressor};
 use rand::Rng;
 use rand::SeedableRng;
 use rand::rngs::SmallRng;
-use std::collections::HashMap;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Write},
+    net::Ipv4Addr,
+};
+
+thread_local! {
+    /// Per-thread generator, seeded once from the thread RNG and reused by every query.
+    static ID_RNG: RefCell<SmallRng> = RefCell::new(SmallRng::from_rng(&mut rand::rng()));
+}
 
 /// Generates a random 16-bit ID for a DNS query.
+///
+/// IDs come from a per-thread [`SmallRng`], so concurrent callers never contend
+/// on a shared generator. Use [`set_id_seed`] to make the sequence reproducible.
 pub fn generate_id() -> u16 {
-    let mut thread_rng = rand::rng();
-    let mut rng = SmallRng::from_rng(&mut thread_rng);
+    ID_RNG.with(|rng| rng.borrow_mut().random::<u16>())
+}
 
-    rng.random::<u16>()
+/// Re-seeds the query ID generator of the current thread.
+///
+/// After this call [`generate_id`] returns a reproducible sequence on this thread,
+/// which is mostly useful for tests. Other threads are not affected.
+pub fn set_id_seed(seed: u64) {
+    ID_RNG.with(|rng| *rng.borrow_mut() = SmallRng::seed_from_u64(seed));
 }
 
 /// Represents a full DNS message, including the header and all four sections.
 ///
 /// A DNS message is composed of:
 /// - [`HeaderSection`] – metadata, flags, and counters.
-/// - [`QuestionSection`] – the query being asked.
+/// - [`QuestionSection`] – the queries being asked (usually one).
 /// - [`AnswerSection`] – responses containing resource records.
 /// - [`AuthoritySection`] – information about authoritative name servers.
 /// - [`AdditionalSection`] – extra information to help resolve queries.
@@ -70,8 +100,8 @@ pub fn generate_id() -> u16 {
 #[derive(Debug, Clone, PartialEq)]
 pub struct DnsMessage {
     pub header: HeaderSection,
-    // The question for the name server
-    pub question: QuestionSection,
+    // The questions for the name server
+    pub questions: Vec<QuestionSection>,
     // RRs answering the question
     pub answer: Vec<AnswerSection>,
     // RRs pointing toward an authority
@@ -81,7 +111,9 @@ pub struct DnsMessage {
 }
 
 impl DnsMessage {
-    /// Creates a new standard query message.
+    /// Creates a new standard query message with a single question.
+    ///
+    /// More questions can be pushed onto [`DnsMessage::questions`] before encoding.
     ///
     /// # Arguments
     /// * `target` - The domain name to query.
@@ -91,6 +123,32 @@ impl DnsMessage {
         target: &str,
         record_type: RecordType,
         query_type: OpCodeOptions,
+    ) -> DnsMessage {
+        Self::new_query_class(target, record_type, query_type, QueryClass::In)
+    }
+
+    /// Creates a new query message with a single question in the given `class`.
+    ///
+    /// Same as [`DnsMessage::new_query`] (which always uses [`QueryClass::In`]), e.g.
+    /// for `CHAOS` queries such as `version.bind`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::dns::resolver::agnostic::{DnsMessage, OpCodeOptions, QueryClass, RecordType};
+    ///
+    /// let msg = DnsMessage::new_query_class(
+    ///     "version.bind",
+    ///     RecordType::Txt,
+    ///     OpCodeOptions::StandardQuery,
+    ///     QueryClass::Ch,
+    /// );
+    /// assert_eq!(msg.questions[0].class, 3);
+    /// ```
+    pub fn new_query_class(
+        target: &str,
+        record_type: RecordType,
+        query_type: OpCodeOptions,
+        class: QueryClass,
     ) -> DnsMessage {
         DnsMessage {
             header: HeaderSection {
@@ -111,31 +169,667 @@ impl DnsMessage {
                 ns_count: 0,
                 ar_count: 0,
             },
-            question: QuestionSection {
+            questions: vec![QuestionSection {
                 name: target.to_string(),
                 record_type: record_type as u16,
-                class: 1,
-            },
+                class: class as u16,
+            }],
             answer: Vec::new(),
             authority: Vec::new(),
             additional: Vec::new(),
         }
     }
 
+    /// Compares two messages field by field, ignoring `header.id`.
+    ///
+    /// Query IDs are random (see [`generate_id`]), so the derived `PartialEq` never
+    /// matches two independently built queries; this makes snapshot-style
+    /// comparisons possible.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::dns::resolver::agnostic::{DnsMessage, OpCodeOptions, RecordType};
+    ///
+    /// let a = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+    /// let b = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+    /// assert!(a.eq_ignoring_id(&b));
+    /// ```
+    pub fn eq_ignoring_id(&self, other: &Self) -> bool {
+        let header = HeaderSection {
+            id: self.header.id,
+            ..other.header
+        };
+
+        self.header == header
+            && self.questions == other.questions
+            && self.answer == other.answer
+            && self.authority == other.authority
+            && self.additional == other.additional
+    }
+
+    /// Renders the message in a `dig`-like layout: header, flags and section counts,
+    /// then one line per question and resource record.
+    ///
+    /// Counts are taken from the sections themselves, like when encoding.
+    ///
+    /// # Example
+    /// ```text
+    /// ;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 4660
+    /// ;; flags: qr rd ra; QUERY: 1, ANSWER: 1, AUTHORITY: 0, ADDITIONAL: 0
+    ///
+    /// ;; QUESTION SECTION:
+    /// ;example.com IN A
+    ///
+    /// ;; ANSWER SECTION:
+    /// example.com 300 IN A 93.184.216.34
+    /// ```
+    pub fn summary(&self) -> String {
+        let flags = DnsHeaderFlags::from_u16(self.header.flags);
+        let opcode = match flags.opcode {
+            0 => "QUERY".to_string(),
+            1 => "IQUERY".to_string(),
+            2 => "STATUS".to_string(),
+            n => n.to_string(),
+        };
+        let status = match flags.rcode {
+            0 => "NOERROR".to_string(),
+            1 => "FORMERR".to_string(),
+            2 => "SERVFAIL".to_string(),
+            3 => "NXDOMAIN".to_string(),
+            4 => "NOTIMP".to_string(),
+            5 => "REFUSED".to_string(),
+            n => n.to_string(),
+        };
+        let set_flags: Vec<&str> = [
+            (flags.qr, "qr"),
+            (flags.aa, "aa"),
+            (flags.tc, "tc"),
+            (flags.rd, "rd"),
+            (flags.ra, "ra"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect();
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            ";; ->>HEADER<<- opcode: {}, status: {}, id: {}",
+            opcode, status, self.header.id
+        );
+        let _ = write!(
+            out,
+            ";; flags: {}; QUERY: {}, ANSWER: {}, AUTHORITY: {}, ADDITIONAL: {}",
+            set_flags.join(" "),
+            self.questions.len(),
+            self.answer.len(),
+            self.authority.len(),
+            self.additional.len()
+        );
+
+        if !self.questions.is_empty() {
+            out.push_str("\n\n;; QUESTION SECTION:");
+            for question in &self.questions {
+                let _ = write!(out, "\n;{}", question);
+            }
+        }
+
+        let sections: [(&str, Vec<String>); 3] = [
+            (
+                "ANSWER",
+                self.answer
+                    .iter()
+                    .map(|rr| {
+                        rr_line(
+                            &rr.owner_name,
+                            rr.ttl as i64,
+                            rr.class,
+                            rr.record_type,
+                            &rr.r_data,
+                        )
+                    })
+                    .collect(),
+            ),
+            (
+                "AUTHORITY",
+                self.authority
+                    .iter()
+                    .map(|rr| {
+                        rr_line(
+                            &rr.owner_name,
+                            rr.ttl as i64,
+                            rr.class,
+                            rr.record_type,
+                            &rr.r_data,
+                        )
+                    })
+                    .collect(),
+            ),
+            (
+                "ADDITIONAL",
+                self.additional
+                    .iter()
+                    .map(|rr| {
+                        rr_line(
+                            &rr.owner_name,
+                            rr.ttl as i64,
+                            rr.class,
+                            rr.record_type,
+                            &rr.r_data,
+                        )
+                    })
+                    .collect(),
+            ),
+        ];
+        for (title, lines) in sections.iter().filter(|(_, lines)| !lines.is_empty()) {
+            let _ = write!(out, "\n\n;; {} SECTION:", title);
+            for line in lines {
+                let _ = write!(out, "\n{}", line);
+            }
+        }
+
+        out
+    }
+
     /// Encodes the DNS query into bytes for sending over the network.
     ///
+    /// Every entry of [`DnsMessage::questions`] is encoded, and `qd_count` is written
+    /// from their number.
+    ///
     /// This uses RFC1035-compliant compression on domain names via `MessageCompressor`.
+    ///
+    /// # Panics
+    /// Panics if a question name is not a valid DNS name (a label longer than
+    /// [`MAX_LABEL_LEN`](crate::dns::compressor::MAX_LABEL_LEN) bytes or a name longer
+    /// than [`MAX_NAME_LEN`](crate::dns::compressor::MAX_NAME_LEN) bytes). Use
+    /// [`DnsMessage::try_encode_query`] for names that haven't been validated.
     pub fn encode_query(&self) -> Vec<u8> {
+        self.try_encode_query()
+            .expect("question names must be valid DNS names")
+    }
+
+    /// Same as [`DnsMessage::encode_query`], but returns an error instead of panicking
+    /// when a question name can't be encoded.
+    ///
+    /// # Errors
+    /// Returns [`CompressorErrors`] if a label or a whole name is too long.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::dns::compressor::CompressorErrors;
+    /// use stalkermap::dns::resolver::agnostic::{DnsMessage, OpCodeOptions, RecordType};
+    ///
+    /// let name = format!("{}.com", "a".repeat(70));
+    /// let msg = DnsMessage::new_query(&name, RecordType::A, OpCodeOptions::StandardQuery);
+    /// assert!(matches!(msg.try_encode_query(), Err(CompressorErrors::LabelTooLong(_))));
+    /// ```
+    pub fn try_encode_query(&self) -> Result<Vec<u8>, CompressorErrors> {
         let mut message: Vec<u8> = Vec::new();
         let mut pointer_map: HashMap<String, usize> = HashMap::new();
 
-        message.extend_from_slice(&self.header.to_bytes());
+        let header = HeaderSection {
+            qd_count: self.questions.len() as u16,
+            ..self.header
+        };
+        message.extend_from_slice(&header.to_bytes());
+
+        for question in &self.questions {
+            question.encode(&mut message, &mut pointer_map)?;
+        }
+        Ok(message)
+    }
+
+    /// Encodes the whole DNS message (header, question and all resource records).
+    ///
+    /// Unlike [`DnsMessage::encode_query`], this also serializes the answer, authority
+    /// and additional sections, which makes it suitable for building responses
+    /// (e.g. for tests or stub DNS servers).
+    ///
+    /// - Names are compressed across the whole message via `MessageCompressor`.
+    /// - `qd_count`, `an_count`, `ns_count` and `ar_count` are written from the section lengths,
+    ///   so they always match the encoded records.
+    /// - `r_data` is parsed for the record's `record_type` (see [`RecordData::parse`]) and
+    ///   `rd_length` is recomputed from the encoded RDATA (see [`RecordData::encode`]).
+    ///
+    /// # Errors
+    /// Returns [`EncodeErrors`] if a name cannot be compressed, an `r_data` can't be parsed
+    /// or an RDATA field is too long.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::dns::resolver::agnostic::{
+    ///     AnswerSection, DnsMessage, OpCodeOptions, RecordData, RecordType,
+    /// };
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let mut msg = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+    /// msg.answer.push(AnswerSection::new(
+    ///     "example.com",
+    ///     300,
+    ///     RecordData::a(Ipv4Addr::new(93, 184, 216, 34)),
+    /// ));
+    ///
+    /// let bytes = msg.encode_full().unwrap();
+    /// assert!(bytes.len() > 12);
+    /// ```
+    pub fn encode_full(&self) -> Result<Vec<u8>, EncodeErrors> {
+        let mut message: Vec<u8> = Vec::new();
+        let mut pointer_map: HashMap<String, usize> = HashMap::new();
+
+        let header = HeaderSection {
+            qd_count: self.questions.len() as u16,
+            an_count: self.answer.len() as u16,
+            ns_count: self.authority.len() as u16,
+            ar_count: self.additional.len() as u16,
+            ..self.header
+        };
+        message.extend_from_slice(&header.to_bytes());
+
+        for question in &self.questions {
+            question.encode(&mut message, &mut pointer_map)?;
+        }
+
+        for rr in &self.answer {
+            rr.encode(&mut message, &mut pointer_map)?;
+        }
+        for rr in &self.authority {
+            rr.encode(&mut message, &mut pointer_map)?;
+        }
+        for rr in &self.additional {
+            rr.encode(&mut message, &mut pointer_map)?;
+        }
+
+        Ok(message)
+    }
+
+    /// Starts building a response to `query`.
+    ///
+    /// The response keeps the query's `id`, questions, opcode and `RD` bit, and has
+    /// the `QR` bit set. Records are then added with the [`ResponseBuilder`] methods.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::dns::resolver::agnostic::{DnsMessage, OpCodeOptions, RecordType};
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let query = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+    /// let bytes = DnsMessage::response_builder(&query)
+    ///     .add_a("example.com", Ipv4Addr::new(93, 184, 216, 34), 300)
+    ///     .encode()
+    ///     .unwrap();
+    /// assert_eq!(&bytes[0..2], &query.header.id.to_be_bytes());
+    /// ```
+    pub fn response_builder(query: &DnsMessage) -> ResponseBuilder {
+        let query_flags = DnsHeaderFlags::from_u16(query.header.flags);
+        let flags = DnsHeaderFlags {
+            qr: true,
+            aa: false,
+            tc: false,
+            ra: false,
+            z: 0,
+            rcode: 0,
+            ..query_flags
+        };
+
+        ResponseBuilder {
+            message: DnsMessage {
+                header: HeaderSection {
+                    flags: flags.to_u16(),
+                    an_count: 0,
+                    ns_count: 0,
+                    ar_count: 0,
+                    ..query.header
+                },
+                questions: query.questions.clone(),
+                answer: Vec::new(),
+                authority: Vec::new(),
+                additional: Vec::new(),
+            },
+        }
+    }
+
+    /// Decodes a full DNS message (typically a response) from its wire format.
+    ///
+    /// - Every section is decoded, including multiple questions.
+    /// - Compressed names are followed anywhere in `buf`.
+    /// - RDATA is rendered into `r_data` in presentation form (see [`RecordData`]'s
+    ///   `Display`); unsupported types (and `A` records with a wrong length) use the
+    ///   generic `\# <len> <hex>` form.
+    /// - Decoded records can be encoded again with [`DnsMessage::encode_full`].
+    ///
+    /// # Errors
+    /// Returns [`DecodeErrors`] if the buffer is truncated or a name is malformed.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::dns::resolver::agnostic::{
+    ///     AnswerSection, DnsMessage, OpCodeOptions, RecordData, RecordType,
+    /// };
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let mut msg = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+    /// msg.answer.push(AnswerSection::new(
+    ///     "example.com",
+    ///     300,
+    ///     RecordData::a(Ipv4Addr::new(93, 184, 216, 34)),
+    /// ));
+    ///
+    /// let decoded = DnsMessage::decode_query(&msg.encode_full().unwrap()).unwrap();
+    /// assert_eq!(decoded.answer[0].r_data, "93.184.216.34");
+    /// ```
+    pub fn decode_query(buf: &[u8]) -> Result<DnsMessage, DecodeErrors> {
+        let header_bytes = read_bytes(buf, 0, 12)?;
+        let word = |i: usize| u16::from_be_bytes([header_bytes[i], header_bytes[i + 1]]);
+        let header = HeaderSection {
+            id: word(0),
+            flags: word(2),
+            qd_count: word(4),
+            an_count: word(6),
+            ns_count: word(8),
+            ar_count: word(10),
+        };
+        let mut offset = 12;
+
+        let mut questions = Vec::new();
+        for _ in 0..header.qd_count {
+            let name = MessageCompressor::decompress_name(buf, &mut offset)?;
+            let fixed = read_bytes(buf, offset, 4)?;
+            questions.push(QuestionSection {
+                name,
+                record_type: u16::from_be_bytes([fixed[0], fixed[1]]),
+                class: u16::from_be_bytes([fixed[2], fixed[3]]),
+            });
+            offset += 4;
+        }
+
+        let mut answer = Vec::new();
+        for _ in 0..header.an_count {
+            let rr = decode_rr(buf, &mut offset)?;
+            answer.push(AnswerSection {
+                owner_name: rr.owner_name,
+                record_type: rr.record_type,
+                class: rr.class,
+                ttl: i32::from_be_bytes(rr.ttl),
+                rd_length: rr.rd_length,
+                r_data: rr.r_data,
+            });
+        }
+
+        let mut authority = Vec::new();
+        for _ in 0..header.ns_count {
+            let rr = decode_rr(buf, &mut offset)?;
+            authority.push(AuthoritySection {
+                owner_name: rr.owner_name,
+                record_type: rr.record_type,
+                class: rr.class,
+                ttl: u32::from_be_bytes(rr.ttl),
+                rd_length: rr.rd_length,
+                r_data: rr.r_data,
+            });
+        }
+
+        let mut additional = Vec::new();
+        for _ in 0..header.ar_count {
+            let rr = decode_rr(buf, &mut offset)?;
+            additional.push(AdditionalSection {
+                owner_name: rr.owner_name,
+                record_type: rr.record_type,
+                class: rr.class,
+                ttl: u32::from_be_bytes(rr.ttl),
+                rd_length: rr.rd_length,
+                r_data: rr.r_data,
+            });
+        }
+
+        Ok(DnsMessage {
+            header,
+            questions,
+            answer,
+            authority,
+            additional,
+        })
+    }
+}
+
+/// Returns `len` bytes of `buf` starting at `offset`.
+fn read_bytes(buf: &[u8], offset: usize, len: usize) -> Result<&[u8], DecodeErrors> {
+    buf.get(offset..offset + len)
+        .ok_or(DecodeErrors::InvalidBufferSize)
+}
+
+/// Fields shared by every resource record, as read by [`decode_rr`].
+struct DecodedRr {
+    owner_name: String,
+    record_type: u16,
+    class: u16,
+    ttl: [u8; 4],
+    rd_length: u16,
+    r_data: String,
+}
+
+/// Decodes the resource record (RFC 1035 §4.1.3) at `offset` and moves `offset` past it.
+fn decode_rr(buf: &[u8], offset: &mut usize) -> Result<DecodedRr, DecodeErrors> {
+    let owner_name = MessageCompressor::decompress_name(buf, offset)?;
+    let fixed = read_bytes(buf, *offset, 10)?;
+    let record_type = u16::from_be_bytes([fixed[0], fixed[1]]);
+    let class = u16::from_be_bytes([fixed[2], fixed[3]]);
+    let ttl = [fixed[4], fixed[5], fixed[6], fixed[7]];
+    let rd_length = u16::from_be_bytes([fixed[8], fixed[9]]);
+
+    let start = *offset + 10;
+    let end = start + rd_length as usize;
+    read_bytes(buf, start, rd_length as usize)?;
+    let r_data = decode_rdata(record_type, buf, start, end)?.to_string();
+
+    // Skip the whole RDATA, even if the decoded value used fewer bytes.
+    *offset = end;
+
+    Ok(DecodedRr {
+        owner_name,
+        record_type,
+        class,
+        ttl,
+        rd_length,
+        r_data,
+    })
+}
+
+const TYPE_A: u16 = RecordType::A as u16;
+const TYPE_NS: u16 = RecordType::Ns as u16;
+const TYPE_CNAME: u16 = RecordType::Cname as u16;
+const TYPE_SOA: u16 = RecordType::Soa as u16;
+const TYPE_WKS: u16 = RecordType::Wks as u16;
+const TYPE_PTR: u16 = RecordType::Ptr as u16;
+const TYPE_HINFO: u16 = RecordType::Hinfo as u16;
+const TYPE_MINFO: u16 = RecordType::Minfo as u16;
+const TYPE_MX: u16 = RecordType::Mx as u16;
+const TYPE_TXT: u16 = RecordType::Txt as u16;
+
+/// Decodes the RDATA stored in `buf[start..end]`.
+///
+/// The whole message is needed because names inside RDATA may be compressed.
+fn decode_rdata(
+    record_type: u16,
+    buf: &[u8],
+    start: usize,
+    end: usize,
+) -> Result<RecordData, DecodeErrors> {
+    let data = &buf[start..end];
+    let mut offset = start;
+
+    let r_data = match record_type {
+        TYPE_A => match <[u8; 4]>::try_from(data) {
+            Ok(octets) => RecordData::A(Ipv4Addr::from(octets)),
+            Err(_) => RecordData::Unknown(record_type, data.to_vec()),
+        },
+        TYPE_NS => RecordData::Ns(MessageCompressor::decompress_name(buf, &mut offset)?),
+        TYPE_CNAME => RecordData::Cname(MessageCompressor::decompress_name(buf, &mut offset)?),
+        TYPE_PTR => RecordData::Ptr(MessageCompressor::decompress_name(buf, &mut offset)?),
+        TYPE_SOA => {
+            let mname = MessageCompressor::decompress_name(buf, &mut offset)?;
+            let rname = MessageCompressor::decompress_name(buf, &mut offset)?;
+            if offset + 20 > end {
+                return Err(DecodeErrors::InvalidBufferSize);
+            }
+            let field = |i: usize| {
+                u32::from_be_bytes([
+                    buf[offset + i],
+                    buf[offset + i + 1],
+                    buf[offset + i + 2],
+                    buf[offset + i + 3],
+                ])
+            };
+            RecordData::Soa {
+                mname,
+                rname,
+                serial: field(0),
+                refresh: field(4),
+                retry: field(8),
+                expire: field(12),
+                minimum: field(16),
+            }
+        }
+        TYPE_WKS => {
+            if data.len() < 5 {
+                return Err(DecodeErrors::InvalidBufferSize);
+            }
+            RecordData::Wks {
+                adress: Ipv4Addr::new(data[0], data[1], data[2], data[3]),
+                protocol: data[4],
+                bitmap: data[5..].to_vec(),
+            }
+        }
+        TYPE_HINFO => {
+            let mut texts = decode_character_strings(data)?.into_iter();
+            match (texts.next(), texts.next()) {
+                (Some(cpu), Some(os)) => RecordData::Hinfo { cpu, os },
+                _ => return Err(DecodeErrors::InvalidBufferSize),
+            }
+        }
+        TYPE_MINFO => RecordData::Minfo {
+            rmailbx: MessageCompressor::decompress_name(buf, &mut offset)?,
+            emailbx: MessageCompressor::decompress_name(buf, &mut offset)?,
+        },
+        TYPE_MX => {
+            if data.len() < 3 {
+                return Err(DecodeErrors::InvalidBufferSize);
+            }
+            let preference = u16::from_be_bytes([data[0], data[1]]);
+            offset += 2;
+            RecordData::Mx {
+                preference,
+                exchange: MessageCompressor::decompress_name(buf, &mut offset)?,
+            }
+        }
+        TYPE_TXT => RecordData::Txt(decode_character_strings(data)?),
+        _ => RecordData::Unknown(record_type, data.to_vec()),
+    };
+
+    Ok(r_data)
+}
+
+/// Splits `data` into `<character-string>`s (a length byte followed by that many bytes).
+fn decode_character_strings(data: &[u8]) -> Result<Vec<String>, DecodeErrors> {
+    let mut texts = Vec::new();
+    let mut rest = data;
+
+    while let Some((&len, tail)) = rest.split_first() {
+        let text = tail
+            .get(..len as usize)
+            .ok_or(DecodeErrors::InvalidBufferSize)?;
+        texts.push(String::from_utf8_lossy(text).into_owned());
+        rest = &tail[len as usize..];
+    }
+
+    Ok(texts)
+}
 
-        MessageCompressor::compress(&self.question.name, &mut message, &mut pointer_map).unwrap();
-        message.extend_from_slice(&self.question.record_type.to_be_bytes());
-        message.extend_from_slice(&self.question.class.to_be_bytes());
+/// Encodes a resource record (RFC 1035 §4.1.3) at the end of `message`.
+///
+/// `r_data` is parsed with [`RecordData::parse`], and `RDLENGTH` is computed from
+/// the bytes actually written for it.
+fn encode_rr(
+    owner_name: &str,
+    record_type: u16,
+    class: u16,
+    ttl: [u8; 4],
+    r_data: &str,
+    message: &mut Vec<u8>,
+    pointer_map: &mut HashMap<String, usize>,
+) -> Result<(), EncodeErrors> {
+    let r_data = RecordData::parse(record_type, r_data)
+        .ok_or_else(|| EncodeErrors::InvalidRData(r_data.to_string()))?;
+
+    MessageCompressor::compress(owner_name, message, pointer_map)?;
+    message.extend_from_slice(&record_type.to_be_bytes());
+    message.extend_from_slice(&class.to_be_bytes());
+    message.extend_from_slice(&ttl);
+
+    // Placeholder for RDLENGTH, patched once RDATA is written.
+    let rd_length_pos = message.len();
+    message.extend_from_slice(&[0, 0]);
+
+    r_data.encode(message, pointer_map)?;
+
+    let rd_length = message.len() - rd_length_pos - 2;
+    if rd_length > u16::MAX as usize {
+        return Err(EncodeErrors::RDataTooLong(rd_length));
+    }
+    message[rd_length_pos..rd_length_pos + 2].copy_from_slice(&(rd_length as u16).to_be_bytes());
+    Ok(())
+}
+
+/// Builder for a response to a query, created by [`DnsMessage::response_builder`].
+///
+/// Answer records are appended in call order. [`ResponseBuilder::encode`] writes
+/// every section with name compression (see [`DnsMessage::encode_full`]).
+#[derive(Debug, Clone)]
+pub struct ResponseBuilder {
+    message: DnsMessage,
+}
+
+impl ResponseBuilder {
+    /// Adds an `A` answer record for `name`.
+    pub fn add_a(self, name: &str, address: Ipv4Addr, ttl: i32) -> Self {
+        self.add_answer(AnswerSection::new(name, ttl, RecordData::a(address)))
+    }
+
+    /// Adds a `TXT` answer record for `name`, holding a single character-string.
+    pub fn add_txt(self, name: &str, text: &str, ttl: i32) -> Self {
+        self.add_answer(AnswerSection::new(name, ttl, RecordData::txt(text)))
+    }
+
+    /// Adds any answer record.
+    pub fn add_answer(mut self, answer: AnswerSection) -> Self {
+        self.message.answer.push(answer);
+        self
+    }
+
+    /// Sets the response code (e.g. `3` for NXDOMAIN).
+    ///
+    /// Only the low 4 bits are kept.
+    pub fn rcode(mut self, rcode: u8) -> Self {
+        let mut flags = DnsHeaderFlags::from_u16(self.message.header.flags);
+        flags.rcode = rcode & 0x0F;
+        self.message.header.flags = flags.to_u16();
+        self
+    }
+
+    /// Returns the response as a [`DnsMessage`], with the section counts filled in.
+    pub fn build(self) -> DnsMessage {
+        let mut message = self.message;
+        message.header.qd_count = message.questions.len() as u16;
+        message.header.an_count = message.answer.len() as u16;
         message
     }
+
+    /// Encodes the response into its wire format.
+    ///
+    /// # Errors
+    /// Returns [`EncodeErrors`] if a name cannot be compressed or an RDATA field is too long.
+    pub fn encode(self) -> Result<Vec<u8>, EncodeErrors> {
+        self.build().encode_full()
+    }
 }
 
 /// Represents the header section of a DNS message (RFC 1035 §4.1.1).
@@ -268,6 +962,107 @@ impl DnsHeaderFlags {
             rcode: (value & 0b1111) as u8,
         }
     }
+
+    /// Returns a [`DnsHeaderFlagsBuilder`] with every flag cleared.
+    ///
+    /// Unlike [`to_u16`](Self::to_u16), which silently truncates out-of-range
+    /// values, the builder rejects them.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::dns::resolver::agnostic::{DnsHeaderFlags, OpCodeOptions};
+    ///
+    /// let flags = DnsHeaderFlags::builder()
+    ///     .opcode(OpCodeOptions::StandardQuery as u8)
+    ///     .rd(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(flags, 0x0100);
+    /// ```
+    pub fn builder() -> DnsHeaderFlagsBuilder {
+        DnsHeaderFlagsBuilder {
+            flags: DnsHeaderFlags::from_u16(0),
+        }
+    }
+}
+
+/// Builder for the raw header flags, created with [`DnsHeaderFlags::builder`].
+///
+/// [`build`](Self::build) validates the multi-bit fields before encoding:
+/// - `opcode`: 4 bits (`0..=15`)
+/// - `z`: 3 bits (`0..=7`)
+/// - `rcode`: 4 bits (`0..=15`)
+#[derive(Debug, Clone, Copy)]
+pub struct DnsHeaderFlagsBuilder {
+    flags: DnsHeaderFlags,
+}
+
+impl DnsHeaderFlagsBuilder {
+    /// Sets the Query/Response flag.
+    pub fn qr(mut self, qr: bool) -> Self {
+        self.flags.qr = qr;
+        self
+    }
+
+    /// Sets the operation code, see [`OpCodeOptions`].
+    pub fn opcode(mut self, opcode: u8) -> Self {
+        self.flags.opcode = opcode;
+        self
+    }
+
+    /// Sets the Authoritative Answer flag.
+    pub fn aa(mut self, aa: bool) -> Self {
+        self.flags.aa = aa;
+        self
+    }
+
+    /// Sets the Truncation flag.
+    pub fn tc(mut self, tc: bool) -> Self {
+        self.flags.tc = tc;
+        self
+    }
+
+    /// Sets the Recursion Desired flag.
+    pub fn rd(mut self, rd: bool) -> Self {
+        self.flags.rd = rd;
+        self
+    }
+
+    /// Sets the Recursion Available flag.
+    pub fn ra(mut self, ra: bool) -> Self {
+        self.flags.ra = ra;
+        self
+    }
+
+    /// Sets the reserved bits.
+    pub fn z(mut self, z: u8) -> Self {
+        self.flags.z = z;
+        self
+    }
+
+    /// Sets the response code.
+    pub fn rcode(mut self, rcode: u8) -> Self {
+        self.flags.rcode = rcode;
+        self
+    }
+
+    /// Validates the flags and encodes them into a 16-bit integer.
+    ///
+    /// # Errors
+    /// Returns [`FlagError`] if `opcode`, `z` or `rcode` doesn't fit in its field.
+    pub fn build(self) -> Result<u16, FlagError> {
+        if self.flags.opcode > 0b1111 {
+            return Err(FlagError::OpCodeOutOfRange(self.flags.opcode));
+        }
+        if self.flags.z > 0b111 {
+            return Err(FlagError::ZOutOfRange(self.flags.z));
+        }
+        if self.flags.rcode > 0b1111 {
+            return Err(FlagError::RCodeOutOfRange(self.flags.rcode));
+        }
+        Ok(self.flags.to_u16())
+    }
 }
 
 /// Represents the question section of a DNS message.
@@ -286,6 +1081,56 @@ pub struct QuestionSection {
     pub class: u16,
 }
 
+impl QuestionSection {
+    /// Creates a question for `name` of the given `record_type` in the `IN` class.
+    pub fn new(name: &str, record_type: RecordType) -> Self {
+        Self {
+            name: name.to_string(),
+            record_type: record_type as u16,
+            class: 1,
+        }
+    }
+
+    /// Appends the wire form of this question (compressed name, type, class) to `message`.
+    pub fn encode(
+        &self,
+        message: &mut Vec<u8>,
+        pointer_map: &mut HashMap<String, usize>,
+    ) -> Result<(), CompressorErrors> {
+        MessageCompressor::compress(&self.name, message, pointer_map)?;
+        message.extend_from_slice(&self.record_type.to_be_bytes());
+        message.extend_from_slice(&self.class.to_be_bytes());
+        Ok(())
+    }
+}
+
+/// Formats the question as `name CLASS TYPE`, e.g. `example.com IN A`.
+///
+/// Unknown classes and types are rendered as `CLASS<n>` / `TYPE<n>` (RFC 3597).
+impl Display for QuestionSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.name,
+            ClassCode(self.class),
+            TypeCode(self.record_type)
+        )
+    }
+}
+
+/// Renders one resource record as `owner TTL CLASS TYPE RDATA`.
+fn rr_line(owner_name: &str, ttl: i64, class: u16, record_type: u16, r_data: &str) -> String {
+    format!(
+        "{} {} {} {} {}",
+        owner_name,
+        ttl,
+        ClassCode(class),
+        TypeCode(record_type),
+        r_data
+    )
+}
+
 /// Represents a single resource record (RR) in the DNS message.
 ///
 /// All RRs share the same format:
@@ -312,10 +1157,54 @@ pub struct AnswerSection {
     pub ttl: i32,
     /// Length of the RDATA field.
     pub rd_length: u16,
-    /// The actual resource data (e.g., IP address for A record).
+    /// The actual resource data (e.g., IP address for A record), in presentation form.
     pub r_data: String,
 }
 
+impl AnswerSection {
+    /// Creates an `IN` class answer record, deriving `record_type` and `rd_length` from `r_data`.
+    ///
+    /// `r_data` is stored in its presentation form (see [`RecordData`]'s `Display`).
+    pub fn new(owner_name: &str, ttl: i32, r_data: RecordData) -> Self {
+        Self {
+            owner_name: owner_name.to_string(),
+            record_type: r_data.record_type(),
+            class: 1,
+            ttl,
+            rd_length: r_data.encoded_len() as u16,
+            r_data: r_data.to_string(),
+        }
+    }
+
+    /// Parses `r_data` back into [`RecordData`] according to `record_type`.
+    ///
+    /// Returns `None` if `r_data` is not a valid presentation of that type
+    /// (see [`RecordData::parse`]).
+    pub fn record_data(&self) -> Option<RecordData> {
+        RecordData::parse(self.record_type, &self.r_data)
+    }
+
+    /// Encodes this record at the end of `message`, compressing names with `pointer_map`.
+    ///
+    /// # Errors
+    /// Returns [`EncodeErrors::InvalidRData`] if `r_data` can't be parsed for `record_type`.
+    pub fn encode(
+        &self,
+        message: &mut Vec<u8>,
+        pointer_map: &mut HashMap<String, usize>,
+    ) -> Result<(), EncodeErrors> {
+        encode_rr(
+            &self.owner_name,
+            self.record_type,
+            self.class,
+            self.ttl.to_be_bytes(),
+            &self.r_data,
+            message,
+            pointer_map,
+        )
+    }
+}
+
 /// Represents a single resource record (RR) in the DNS message.
 ///
 /// All RRs share the same format:
@@ -342,10 +1231,54 @@ pub struct AuthoritySection {
     pub ttl: u32,
     /// Length of the RDATA field.
     pub rd_length: u16,
-    /// The actual resource data (e.g., authoritative name server).
+    /// The actual resource data (e.g., authoritative name server), in presentation form.
     pub r_data: String,
 }
 
+impl AuthoritySection {
+    /// Creates an `IN` class authority record, deriving `record_type` and `rd_length` from `r_data`.
+    ///
+    /// `r_data` is stored in its presentation form (see [`RecordData`]'s `Display`).
+    pub fn new(owner_name: &str, ttl: u32, r_data: RecordData) -> Self {
+        Self {
+            owner_name: owner_name.to_string(),
+            record_type: r_data.record_type(),
+            class: 1,
+            ttl,
+            rd_length: r_data.encoded_len() as u16,
+            r_data: r_data.to_string(),
+        }
+    }
+
+    /// Parses `r_data` back into [`RecordData`] according to `record_type`.
+    ///
+    /// Returns `None` if `r_data` is not a valid presentation of that type
+    /// (see [`RecordData::parse`]).
+    pub fn record_data(&self) -> Option<RecordData> {
+        RecordData::parse(self.record_type, &self.r_data)
+    }
+
+    /// Encodes this record at the end of `message`, compressing names with `pointer_map`.
+    ///
+    /// # Errors
+    /// Returns [`EncodeErrors::InvalidRData`] if `r_data` can't be parsed for `record_type`.
+    pub fn encode(
+        &self,
+        message: &mut Vec<u8>,
+        pointer_map: &mut HashMap<String, usize>,
+    ) -> Result<(), EncodeErrors> {
+        encode_rr(
+            &self.owner_name,
+            self.record_type,
+            self.class,
+            self.ttl.to_be_bytes(),
+            &self.r_data,
+            message,
+            pointer_map,
+        )
+    }
+}
+
 /// Represents a single resource record (RR) in the DNS message.
 ///
 /// All RRs share the same format:
@@ -372,10 +1305,54 @@ pub struct AdditionalSection {
     pub ttl: u32,
     /// Length of the RDATA field.
     pub rd_length: u16,
-    /// The actual resource data (e.g., additional IP information).
+    /// The actual resource data (e.g., additional IP information), in presentation form.
     pub r_data: String,
 }
 
+impl AdditionalSection {
+    /// Creates an `IN` class additional record, deriving `record_type` and `rd_length` from `r_data`.
+    ///
+    /// `r_data` is stored in its presentation form (see [`RecordData`]'s `Display`).
+    pub fn new(owner_name: &str, ttl: u32, r_data: RecordData) -> Self {
+        Self {
+            owner_name: owner_name.to_string(),
+            record_type: r_data.record_type(),
+            class: 1,
+            ttl,
+            rd_length: r_data.encoded_len() as u16,
+            r_data: r_data.to_string(),
+        }
+    }
+
+    /// Parses `r_data` back into [`RecordData`] according to `record_type`.
+    ///
+    /// Returns `None` if `r_data` is not a valid presentation of that type
+    /// (see [`RecordData::parse`]).
+    pub fn record_data(&self) -> Option<RecordData> {
+        RecordData::parse(self.record_type, &self.r_data)
+    }
+
+    /// Encodes this record at the end of `message`, compressing names with `pointer_map`.
+    ///
+    /// # Errors
+    /// Returns [`EncodeErrors::InvalidRData`] if `r_data` can't be parsed for `record_type`.
+    pub fn encode(
+        &self,
+        message: &mut Vec<u8>,
+        pointer_map: &mut HashMap<String, usize>,
+    ) -> Result<(), EncodeErrors> {
+        encode_rr(
+            &self.owner_name,
+            self.record_type,
+            self.class,
+            self.ttl.to_be_bytes(),
+            &self.r_data,
+            message,
+            pointer_map,
+        )
+    }
+}
+
 /// DNS record types (subset of QTYPEs from RFC 1035).
 ///
 /// - `A` – IPv4 address
@@ -432,10 +1409,567 @@ impl RecordType {
     }
 }
 
+/// DNS classes that can be queried (RFC 1035 §3.2.4).
+///
+/// - `In` – the Internet, used by almost every query.
+/// - `Ch` – CHAOS, used by servers to report their software and identity
+///   (e.g. `version.bind`, `hostname.bind`).
+/// - `Hs` – Hesiod.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryClass {
+    In = 1,
+    Ch = 3,
+    Hs = 4,
+}
+
+#[allow(clippy::wrong_self_convention)]
+impl QueryClass {
+    /// Encode the class as a 2-byte big-endian value.
+    pub fn to_bytes(self) -> [u8; 2] {
+        (self as u16).to_be_bytes()
+    }
+}
+
+/// Returns the mnemonic of a record type code, e.g. `"A"` for `1`.
+///
+/// Covers the types of [`RecordType`] plus a few common ones (`AAAA`, `SRV`, ...).
+/// Returns `"UNKNOWN"` for any other code.
+pub fn record_type_name(code: u16) -> &'static str {
+    match code {
+        1 => "A",
+        2 => "NS",
+        5 => "CNAME",
+        6 => "SOA",
+        11 => "WKS",
+        12 => "PTR",
+        13 => "HINFO",
+        14 => "MINFO",
+        15 => "MX",
+        16 => "TXT",
+        28 => "AAAA",
+        33 => "SRV",
+        35 => "NAPTR",
+        65 => "HTTPS",
+        255 => "ANY",
+        257 => "CAA",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Returns the mnemonic of a class code (see [`QueryClass`]), or `None` if unknown.
+fn class_name(code: u16) -> Option<&'static str> {
+    match code {
+        1 => Some("IN"),
+        3 => Some("CH"),
+        4 => Some("HS"),
+        255 => Some("ANY"),
+        _ => None,
+    }
+}
+
+/// Displays a record type code by name, or as `TYPE<n>` if unknown.
+struct TypeCode(u16);
+
+impl Display for TypeCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match record_type_name(self.0) {
+            "UNKNOWN" => write!(f, "TYPE{}", self.0),
+            name => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Displays a class code by name, or as `CLASS<n>` if unknown.
+struct ClassCode(u16);
+
+impl Display for ClassCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match class_name(self.0) {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "CLASS{}", self.0),
+        }
+    }
+}
+
+/// Typed RDATA of a resource record (RFC 1035 §3.3).
+///
+/// Mirrors the representation used by the standard resolver so records can be
+/// built and inspected the same way in both modes.
+///
+/// Use the convenience constructors ([`RecordData::a`], [`RecordData::cname`],
+/// [`RecordData::mx`], ...) to build records from borrowed data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordData {
+    A(Ipv4Addr),
+    Ns(String),
+    Cname(String),
+    Soa {
+        mname: String,
+        rname: String,
+        serial: u32,
+        refresh: u32,
+        retry: u32,
+        expire: u32,
+        minimum: u32,
+    },
+    Wks {
+        adress: Ipv4Addr,
+        protocol: u8,
+        bitmap: Vec<u8>,
+    },
+    Ptr(String),
+    Hinfo {
+        cpu: String,
+        os: String,
+    },
+    Minfo {
+        rmailbx: String,
+        emailbx: String,
+    },
+    Mx {
+        preference: u16,
+        exchange: String,
+    },
+    Txt(Vec<String>),
+    Unknown(u16, Vec<u8>),
+}
+
+impl RecordData {
+    /// Builds an `A` record.
+    pub fn a(address: Ipv4Addr) -> Self {
+        RecordData::A(address)
+    }
+
+    /// Builds an `NS` record pointing to `name_server`.
+    pub fn ns(name_server: &str) -> Self {
+        RecordData::Ns(name_server.to_string())
+    }
+
+    /// Builds a `CNAME` record pointing to `canonical_name`.
+    pub fn cname(canonical_name: &str) -> Self {
+        RecordData::Cname(canonical_name.to_string())
+    }
+
+    /// Builds a `PTR` record pointing to `name`.
+    pub fn ptr(name: &str) -> Self {
+        RecordData::Ptr(name.to_string())
+    }
+
+    /// Builds an `MX` record.
+    pub fn mx(preference: u16, exchange: &str) -> Self {
+        RecordData::Mx {
+            preference,
+            exchange: exchange.to_string(),
+        }
+    }
+
+    /// Builds a `TXT` record holding a single character-string.
+    pub fn txt(text: &str) -> Self {
+        RecordData::Txt(vec![text.to_string()])
+    }
+
+    /// Returns the record type code matching this RDATA.
+    ///
+    /// `Unknown` records return the type code they were built with.
+    pub fn record_type(&self) -> u16 {
+        match self {
+            RecordData::A(_) => RecordType::A as u16,
+            RecordData::Ns(_) => RecordType::Ns as u16,
+            RecordData::Cname(_) => RecordType::Cname as u16,
+            RecordData::Soa { .. } => RecordType::Soa as u16,
+            RecordData::Wks { .. } => RecordType::Wks as u16,
+            RecordData::Ptr(_) => RecordType::Ptr as u16,
+            RecordData::Hinfo { .. } => RecordType::Hinfo as u16,
+            RecordData::Minfo { .. } => RecordType::Minfo as u16,
+            RecordData::Mx { .. } => RecordType::Mx as u16,
+            RecordData::Txt(_) => RecordType::Txt as u16,
+            RecordData::Unknown(t, _) => *t,
+        }
+    }
+
+    /// Returns the uncompressed length in bytes of the encoded RDATA.
+    ///
+    /// The actual length on the wire may be smaller when names are compressed.
+    pub fn encoded_len(&self) -> usize {
+        // Uncompressed wire length of a domain name: one length octet per label + root.
+        fn name_len(name: &str) -> usize {
+            let name = name.trim_end_matches('.');
+            if name.is_empty() { 1 } else { name.len() + 2 }
+        }
+
+        match self {
+            RecordData::A(_) => 4,
+            RecordData::Ns(n) | RecordData::Cname(n) | RecordData::Ptr(n) => name_len(n),
+            RecordData::Soa { mname, rname, .. } => name_len(mname) + name_len(rname) + 20,
+            RecordData::Wks { bitmap, .. } => 5 + bitmap.len(),
+            RecordData::Hinfo { cpu, os } => 2 + cpu.len() + os.len(),
+            RecordData::Minfo { rmailbx, emailbx } => name_len(rmailbx) + name_len(emailbx),
+            RecordData::Mx { exchange, .. } => 2 + name_len(exchange),
+            RecordData::Txt(texts) => texts.iter().map(|t| 1 + t.len()).sum(),
+            RecordData::Unknown(_, data) => data.len(),
+        }
+    }
+
+    /// Encodes the RDATA at the end of `message`.
+    ///
+    /// Domain names inside the RDATA (`NS`, `CNAME`, `SOA`, `PTR`, `MINFO`, `MX`) are
+    /// compressed with `pointer_map`, as allowed by RFC 1035 §4.1.4.
+    ///
+    /// # Errors
+    /// - [`EncodeErrors::Compressor`] if a name is invalid.
+    /// - [`EncodeErrors::CharacterStringTooLong`] if a `TXT`/`HINFO` string exceeds 255 bytes.
+    pub fn encode(
+        &self,
+        message: &mut Vec<u8>,
+        pointer_map: &mut HashMap<String, usize>,
+    ) -> Result<(), EncodeErrors> {
+        match self {
+            RecordData::A(ip) => message.extend_from_slice(&ip.octets()),
+            RecordData::Ns(name) | RecordData::Cname(name) | RecordData::Ptr(name) => {
+                MessageCompressor::compress(name, message, pointer_map)?
+            }
+            RecordData::Soa {
+                mname,
+                rname,
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum,
+            } => {
+                MessageCompressor::compress(mname, message, pointer_map)?;
+                MessageCompressor::compress(rname, message, pointer_map)?;
+                for v in [serial, refresh, retry, expire, minimum] {
+                    message.extend_from_slice(&v.to_be_bytes());
+                }
+            }
+            RecordData::Wks {
+                adress,
+                protocol,
+                bitmap,
+            } => {
+                message.extend_from_slice(&adress.octets());
+                message.push(*protocol);
+                message.extend_from_slice(bitmap);
+            }
+            RecordData::Hinfo { cpu, os } => {
+                encode_character_string(cpu, message)?;
+                encode_character_string(os, message)?;
+            }
+            RecordData::Minfo { rmailbx, emailbx } => {
+                MessageCompressor::compress(rmailbx, message, pointer_map)?;
+                MessageCompressor::compress(emailbx, message, pointer_map)?;
+            }
+            RecordData::Mx {
+                preference,
+                exchange,
+            } => {
+                message.extend_from_slice(&preference.to_be_bytes());
+                MessageCompressor::compress(exchange, message, pointer_map)?;
+            }
+            RecordData::Txt(texts) => {
+                for text in texts {
+                    encode_character_string(text, message)?;
+                }
+            }
+            RecordData::Unknown(_, data) => message.extend_from_slice(data),
+        }
+        Ok(())
+    }
+
+    /// Parses RDATA of type `record_type` from its presentation form (see `Display`).
+    ///
+    /// The generic `\# <len> <hex>` form (RFC 3597) is accepted for every type and
+    /// gives a [`RecordData::Unknown`]. Returns `None` if `text` is not a valid
+    /// presentation of `record_type`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::dns::resolver::agnostic::{RecordData, RecordType};
+    ///
+    /// let mx = RecordData::parse(RecordType::Mx as u16, "10 mail.example.com");
+    /// assert_eq!(mx, Some(RecordData::mx(10, "mail.example.com")));
+    /// ```
+    pub fn parse(record_type: u16, text: &str) -> Option<RecordData> {
+        let text = text.trim();
+        let fields: Vec<&str> = text.split_whitespace().collect();
+
+        if let ["\\#", len, hex @ ..] = fields.as_slice() {
+            let data = decode_hex(&hex.concat())?;
+            return match len.parse::<usize>() {
+                Ok(len) if len == data.len() => Some(RecordData::Unknown(record_type, data)),
+                _ => None,
+            };
+        }
+
+        let r_data = match (record_type, fields.as_slice()) {
+            (TYPE_A, _) => RecordData::A(text.parse().ok()?),
+            (TYPE_NS, _) => RecordData::Ns(text.to_string()),
+            (TYPE_CNAME, _) => RecordData::Cname(text.to_string()),
+            (TYPE_PTR, _) => RecordData::Ptr(text.to_string()),
+            (TYPE_SOA, [mname, rname, serial, refresh, retry, expire, minimum]) => {
+                RecordData::Soa {
+                    mname: mname.to_string(),
+                    rname: rname.to_string(),
+                    serial: serial.parse().ok()?,
+                    refresh: refresh.parse().ok()?,
+                    retry: retry.parse().ok()?,
+                    expire: expire.parse().ok()?,
+                    minimum: minimum.parse().ok()?,
+                }
+            }
+            (TYPE_WKS, [adress, protocol, bitmap @ ..]) if bitmap.len() <= 1 => RecordData::Wks {
+                adress: adress.parse().ok()?,
+                protocol: protocol.parse().ok()?,
+                bitmap: decode_hex(&bitmap.concat())?,
+            },
+            (TYPE_HINFO, _) => match parse_quoted_strings(text)?.as_slice() {
+                [cpu, os] => RecordData::Hinfo {
+                    cpu: cpu.clone(),
+                    os: os.clone(),
+                },
+                _ => return None,
+            },
+            (TYPE_MINFO, [rmailbx, emailbx]) => RecordData::Minfo {
+                rmailbx: rmailbx.to_string(),
+                emailbx: emailbx.to_string(),
+            },
+            (TYPE_MX, [preference, exchange]) => RecordData::Mx {
+                preference: preference.parse().ok()?,
+                exchange: exchange.to_string(),
+            },
+            (TYPE_TXT, _) => RecordData::Txt(parse_quoted_strings(text)?),
+            _ => return None,
+        };
+
+        Some(r_data)
+    }
+}
+
+/// Decodes a string of hex digit pairs (e.g. `"0a1b"`).
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Parses `<character-string>`s quoted as in `Display` (e.g. `"v=spf1" "-all"`).
+fn parse_quoted_strings(text: &str) -> Option<Vec<String>> {
+    let mut texts = Vec::new();
+    let mut chars = text.chars();
+
+    loop {
+        match chars.next() {
+            None => return Some(texts),
+            Some(c) if c.is_whitespace() => continue,
+            Some('"') => {}
+            Some(_) => return None,
+        }
+
+        let mut text = String::new();
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => text.push(match chars.next()? {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    '0' => '\0',
+                    'u' => {
+                        if chars.next()? != '{' {
+                            return None;
+                        }
+                        let code: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                        char::from_u32(u32::from_str_radix(&code, 16).ok()?)?
+                    }
+                    escaped => escaped,
+                }),
+                c => text.push(c),
+            }
+        }
+        texts.push(text);
+    }
+}
+
+/// Formats the RDATA in zone-file presentation, e.g. `10 mail.example.com` for an `MX`.
+///
+/// Character-strings are quoted, the `WKS` bitmap and unknown RDATA are written in hex,
+/// the latter in the generic `\# <len> <hex>` form (RFC 3597). [`RecordData::parse`]
+/// reads this form back.
+impl Display for RecordData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordData::A(ip) => write!(f, "{}", ip),
+            RecordData::Ns(name) | RecordData::Cname(name) | RecordData::Ptr(name) => {
+                write!(f, "{}", name)
+            }
+            RecordData::Soa {
+                mname,
+                rname,
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum,
+            } => write!(
+                f,
+                "{} {} {} {} {} {} {}",
+                mname, rname, serial, refresh, retry, expire, minimum
+            ),
+            RecordData::Wks {
+                adress,
+                protocol,
+                bitmap,
+            } => {
+                write!(f, "{} {}", adress, protocol)?;
+                if !bitmap.is_empty() {
+                    write!(f, " ")?;
+                    for byte in bitmap {
+                        write!(f, "{:02x}", byte)?;
+                    }
+                }
+                Ok(())
+            }
+            RecordData::Hinfo { cpu, os } => write!(f, "{:?} {:?}", cpu, os),
+            RecordData::Minfo { rmailbx, emailbx } => write!(f, "{} {}", rmailbx, emailbx),
+            RecordData::Mx {
+                preference,
+                exchange,
+            } => write!(f, "{} {}", preference, exchange),
+            RecordData::Txt(texts) => {
+                let quoted: Vec<String> = texts.iter().map(|t| format!("{:?}", t)).collect();
+                write!(f, "{}", quoted.join(" "))
+            }
+            RecordData::Unknown(_, data) => {
+                write!(f, "\\# {}", data.len())?;
+                if !data.is_empty() {
+                    write!(f, " ")?;
+                    for byte in data {
+                        write!(f, "{:02x}", byte)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Encodes a `<character-string>` (RFC 1035 §3.3): one length octet followed by the bytes.
+fn encode_character_string(text: &str, message: &mut Vec<u8>) -> Result<(), EncodeErrors> {
+    if text.len() > 0xFF {
+        return Err(EncodeErrors::CharacterStringTooLong(text.to_string()));
+    }
+    message.push(text.len() as u8);
+    message.extend_from_slice(text.as_bytes());
+    Ok(())
+}
+
+/// Errors that can occur while encoding a full DNS message.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EncodeErrors {
+    /// A domain name could not be compressed.
+    Compressor(CompressorErrors),
+    /// A `<character-string>` (TXT/HINFO) is longer than 255 bytes.
+    CharacterStringTooLong(String),
+    /// The encoded RDATA does not fit in the 16-bit `RDLENGTH` field.
+    RDataTooLong(usize),
+    /// A record's `r_data` is not a valid presentation of its `record_type`.
+    InvalidRData(String),
+}
+
+impl Display for EncodeErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodeErrors::Compressor(e) => write!(f, "{}", e),
+            EncodeErrors::CharacterStringTooLong(s) => {
+                write!(f, "Character string too long (>255): {}", s)
+            }
+            EncodeErrors::RDataTooLong(len) => write!(f, "RDATA too long (>65535): {}", len),
+            EncodeErrors::InvalidRData(r_data) => write!(f, "Invalid RDATA: {}", r_data),
+        }
+    }
+}
+
+impl Error for EncodeErrors {}
+
+/// Errors returned by [`DnsMessage::decode_query`].
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum DecodeErrors {
+    /// The buffer ends before a header, question, record or RDATA field is complete.
+    InvalidBufferSize,
+    /// A domain name could not be decompressed.
+    Decompressor(DecompressorErrors),
+}
+
+impl Display for DecodeErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeErrors::InvalidBufferSize => write!(f, "Buffer too short for a DNS message"),
+            DecodeErrors::Decompressor(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for DecodeErrors {}
+
+/// Errors returned by [`DnsHeaderFlagsBuilder::build`].
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum FlagError {
+    /// The opcode is greater than 15.
+    OpCodeOutOfRange(u8),
+    /// The reserved bits are greater than 7.
+    ZOutOfRange(u8),
+    /// The response code is greater than 15.
+    RCodeOutOfRange(u8),
+}
+
+impl Display for FlagError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlagError::OpCodeOutOfRange(v) => write!(f, "Opcode out of range (>15): {}", v),
+            FlagError::ZOutOfRange(v) => write!(f, "Reserved bits out of range (>7): {}", v),
+            FlagError::RCodeOutOfRange(v) => write!(f, "Rcode out of range (>15): {}", v),
+        }
+    }
+}
+
+impl Error for FlagError {}
+
+impl From<CompressorErrors> for EncodeErrors {
+    fn from(value: CompressorErrors) -> Self {
+        EncodeErrors::Compressor(value)
+    }
+}
+
+impl From<DecompressorErrors> for DecodeErrors {
+    fn from(value: DecompressorErrors) -> Self {
+        DecodeErrors::Decompressor(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_set_id_seed_is_reproducible() {
+        set_id_seed(7);
+        let first: Vec<u16> = (0..8).map(|_| generate_id()).collect();
+
+        set_id_seed(7);
+        let second: Vec<u16> = (0..8).map(|_| generate_id()).collect();
+
+        assert_eq!(first, second);
+        assert!(first.windows(2).any(|w| w[0] != w[1]));
+    }
+
     #[test]
     fn test_record_type_to_bytes() {
         let record_a = RecordType::A;
@@ -472,6 +2006,41 @@ mod tests {
         assert_eq!(decoded.rcode, flags.rcode);
     }
 
+    #[test]
+    fn test_dns_header_flags_builder_round_trip() {
+        let raw = DnsHeaderFlags::builder()
+            .qr(true)
+            .opcode(OpCodeOptions::InverseQuery as u8)
+            .aa(true)
+            .rd(true)
+            .ra(true)
+            .rcode(3)
+            .build()
+            .unwrap();
+
+        let decoded = DnsHeaderFlags::from_u16(raw);
+        assert!(decoded.qr && decoded.aa && decoded.rd && decoded.ra && !decoded.tc);
+        assert_eq!(decoded.opcode, OpCodeOptions::InverseQuery as u8);
+        assert_eq!(decoded.z, 0);
+        assert_eq!(decoded.rcode, 3);
+    }
+
+    #[test]
+    fn test_dns_header_flags_builder_rejects_out_of_range() {
+        assert_eq!(
+            DnsHeaderFlags::builder().opcode(16).build(),
+            Err(FlagError::OpCodeOutOfRange(16))
+        );
+        assert_eq!(
+            DnsHeaderFlags::builder().z(8).build(),
+            Err(FlagError::ZOutOfRange(8))
+        );
+        assert_eq!(
+            DnsHeaderFlags::builder().rcode(200).build(),
+            Err(FlagError::RCodeOutOfRange(200))
+        );
+    }
+
     #[test]
     fn test_dns_message_new_query() {
         let msg = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
@@ -481,15 +2050,34 @@ mod tests {
         assert_eq!(msg.header.ns_count, 0);
         assert_eq!(msg.header.ar_count, 0);
 
-        assert_eq!(msg.question.name, "example.com");
-        assert_eq!(msg.question.record_type, RecordType::A as u16);
-        assert_eq!(msg.question.class, 1);
+        assert_eq!(msg.questions.len(), 1);
+        assert_eq!(msg.questions[0].name, "example.com");
+        assert_eq!(msg.questions[0].record_type, RecordType::A as u16);
+        assert_eq!(msg.questions[0].class, 1);
 
         assert!(msg.answer.is_empty());
         assert!(msg.authority.is_empty());
         assert!(msg.additional.is_empty());
     }
 
+    #[test]
+    fn test_dns_message_try_encode_query_label_too_long() {
+        let label = "a".repeat(70);
+        let msg = DnsMessage::new_query(
+            &format!("{}.com", label),
+            RecordType::A,
+            OpCodeOptions::StandardQuery,
+        );
+
+        assert!(matches!(
+            msg.try_encode_query(),
+            Err(CompressorErrors::LabelTooLong(l)) if l == label
+        ));
+
+        let ok = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        assert_eq!(ok.try_encode_query().unwrap(), ok.encode_query());
+    }
+
     #[test]
     fn test_dns_message_encode_query() {
         let msg = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
@@ -513,6 +2101,22 @@ mod tests {
         assert!(!bytes[12..].is_empty());
     }
 
+    #[test]
+    fn test_dns_message_new_query_chaos_class() {
+        let msg = DnsMessage::new_query_class(
+            "version.bind",
+            RecordType::Txt,
+            OpCodeOptions::StandardQuery,
+            QueryClass::Ch,
+        );
+        let bytes = msg.encode_query();
+
+        // The question ends with its type and class.
+        assert_eq!(bytes[bytes.len() - 4..bytes.len() - 2], [0x00, 0x10]);
+        assert_eq!(bytes[bytes.len() - 2..], [0x00, 0x03]);
+        assert_eq!(QueryClass::Ch.to_bytes(), [0x00, 0x03]);
+    }
+
     #[test]
     fn test_dns_message_new_query_different_record_types() {
         let record_types = [
@@ -526,9 +2130,9 @@ mod tests {
         for &rec in record_types.iter() {
             let msg = DnsMessage::new_query("example.com", rec, OpCodeOptions::StandardQuery);
 
-            assert_eq!(msg.question.name, "example.com");
-            assert_eq!(msg.question.record_type, rec as u16);
-            assert_eq!(msg.question.class, 1);
+            assert_eq!(msg.questions[0].name, "example.com");
+            assert_eq!(msg.questions[0].record_type, rec as u16);
+            assert_eq!(msg.questions[0].class, 1);
 
             assert_eq!(msg.header.qd_count, 1);
             assert_eq!(msg.header.an_count, 0);
@@ -545,4 +2149,387 @@ mod tests {
             assert_eq!(encoded_type, rec as u16);
         }
     }
+
+    #[test]
+    fn test_dns_message_encode_full_round_trip() {
+        let mut msg =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        msg.answer.push(AnswerSection::new(
+            "example.com",
+            300,
+            RecordData::a(Ipv4Addr::new(93, 184, 216, 34)),
+        ));
+
+        let bytes = msg.encode_full().unwrap();
+
+        // Header: counts come from the sections
+        assert_eq!(u16::from_be_bytes([bytes[0], bytes[1]]), msg.header.id);
+        assert_eq!(u16::from_be_bytes([bytes[4], bytes[5]]), 1); // qd_count
+        assert_eq!(u16::from_be_bytes([bytes[6], bytes[7]]), 1); // an_count
+        assert_eq!(u16::from_be_bytes([bytes[8], bytes[9]]), 0); // ns_count
+        assert_eq!(u16::from_be_bytes([bytes[10], bytes[11]]), 0); // ar_count
+
+        // Question: example.com, A, IN
+        let question_end = 12 + 13 + 4;
+        assert_eq!(&bytes[12..question_end], &msg.encode_query()[12..]);
+
+        // Answer: owner name is a pointer to the question name (offset 12)
+        let rr = &bytes[question_end..];
+        assert_eq!(&rr[0..2], &[0xC0, 0x0C]);
+        assert_eq!(u16::from_be_bytes([rr[2], rr[3]]), RecordType::A as u16);
+        assert_eq!(u16::from_be_bytes([rr[4], rr[5]]), 1);
+        assert_eq!(i32::from_be_bytes([rr[6], rr[7], rr[8], rr[9]]), 300);
+        assert_eq!(u16::from_be_bytes([rr[10], rr[11]]), 4);
+        assert_eq!(
+            Ipv4Addr::new(rr[12], rr[13], rr[14], rr[15]),
+            Ipv4Addr::new(93, 184, 216, 34)
+        );
+        assert_eq!(rr.len(), 16);
+    }
+
+    #[test]
+    fn test_dns_message_response_builder_round_trip() {
+        let query =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+
+        let bytes = DnsMessage::response_builder(&query)
+            .add_a("example.com", Ipv4Addr::new(93, 184, 216, 34), 300)
+            .add_txt("example.com", "v=spf1 -all", 60)
+            .encode()
+            .unwrap();
+
+        let response = DnsMessage::decode_query(&bytes).unwrap();
+        let flags = DnsHeaderFlags::from_u16(response.header.flags);
+        assert_eq!(response.header.id, query.header.id);
+        assert!(flags.qr);
+        assert!(flags.rd);
+        assert_eq!(flags.rcode, 0);
+        assert_eq!(response.questions, query.questions);
+
+        assert_eq!(response.answer.len(), 2);
+        assert_eq!(response.answer[0].r_data, "93.184.216.34");
+        assert_eq!(response.answer[0].ttl, 300);
+        assert_eq!(response.answer[1].r_data, "\"v=spf1 -all\"");
+        assert_eq!(
+            response.answer[1].record_data(),
+            Some(RecordData::txt("v=spf1 -all"))
+        );
+
+        let nxdomain = DnsMessage::response_builder(&query).rcode(3).build();
+        assert_eq!(DnsHeaderFlags::from_u16(nxdomain.header.flags).rcode, 3);
+        assert!(nxdomain.answer.is_empty());
+    }
+
+    #[test]
+    fn test_record_data_encode_compresses_names() {
+        let mut msg =
+            DnsMessage::new_query("example.com", RecordType::Mx, OpCodeOptions::StandardQuery);
+        msg.answer.push(AnswerSection::new(
+            "example.com",
+            60,
+            RecordData::mx(10, "mail.example.com"),
+        ));
+
+        let bytes = msg.encode_full().unwrap();
+        let rr = &bytes[12 + 13 + 4..];
+
+        assert_eq!(u16::from_be_bytes([rr[2], rr[3]]), RecordType::Mx as u16);
+        // preference (2) + "mail" label (5) + pointer to "example.com" (2)
+        assert_eq!(u16::from_be_bytes([rr[10], rr[11]]), 9);
+        assert_eq!(u16::from_be_bytes([rr[12], rr[13]]), 10);
+        assert_eq!(&rr[14..19], b"\x04mail");
+        assert_eq!(&rr[19..21], &[0xC0, 0x0C]);
+    }
+
+    #[test]
+    fn test_dns_message_eq_ignoring_id() {
+        let a = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        let mut b =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        b.header.id = a.header.id.wrapping_add(1);
+
+        assert_ne!(a, b);
+        assert!(a.eq_ignoring_id(&b));
+
+        let mx = DnsMessage::new_query("example.com", RecordType::Mx, OpCodeOptions::StandardQuery);
+        assert!(!a.eq_ignoring_id(&mx));
+
+        b.header.flags ^= 1 << 8; // toggle RD
+        assert!(!a.eq_ignoring_id(&b));
+    }
+
+    #[test]
+    fn test_question_section_display() {
+        let msg = DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        assert_eq!(msg.questions[0].to_string(), "example.com IN A");
+
+        let chaos = QuestionSection {
+            class: QueryClass::Ch as u16,
+            ..QuestionSection::new("version.bind", RecordType::Txt)
+        };
+        assert_eq!(chaos.to_string(), "version.bind CH TXT");
+
+        let unknown = QuestionSection {
+            name: "example.com".to_string(),
+            record_type: 999,
+            class: 42,
+        };
+        assert_eq!(unknown.to_string(), "example.com CLASS42 TYPE999");
+        assert_eq!(record_type_name(28), "AAAA");
+        assert_eq!(record_type_name(999), "UNKNOWN");
+    }
+
+    #[test]
+    fn test_dns_message_summary() {
+        let mut msg =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        msg.header.id = 0x1234;
+        msg.header.flags = DnsHeaderFlags::builder()
+            .qr(true)
+            .rd(true)
+            .ra(true)
+            .build()
+            .unwrap();
+        msg.answer.push(AnswerSection::new(
+            "example.com",
+            300,
+            RecordData::a(Ipv4Addr::new(93, 184, 216, 34)),
+        ));
+
+        assert_eq!(
+            msg.summary(),
+            ";; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 4660\n\
+             ;; flags: qr rd ra; QUERY: 1, ANSWER: 1, AUTHORITY: 0, ADDITIONAL: 0\n\
+             \n\
+             ;; QUESTION SECTION:\n\
+             ;example.com IN A\n\
+             \n\
+             ;; ANSWER SECTION:\n\
+             example.com 300 IN A 93.184.216.34"
+        );
+    }
+
+    #[test]
+    fn test_record_data_parse_round_trip() {
+        let records = [
+            RecordData::a(Ipv4Addr::new(93, 184, 216, 34)),
+            RecordData::ns("ns1.example.com"),
+            RecordData::cname("www.example.com"),
+            RecordData::ptr("host.example.com"),
+            RecordData::Soa {
+                mname: "ns1.example.com".to_string(),
+                rname: "hostmaster.example.com".to_string(),
+                serial: 2024010101,
+                refresh: 7200,
+                retry: 3600,
+                expire: 1209600,
+                minimum: 300,
+            },
+            RecordData::Wks {
+                adress: Ipv4Addr::new(10, 0, 0, 1),
+                protocol: 6,
+                bitmap: vec![0x00, 0x40],
+            },
+            RecordData::Hinfo {
+                cpu: "x86 \"64\"".to_string(),
+                os: "Linux\n".to_string(),
+            },
+            RecordData::Minfo {
+                rmailbx: "admin.example.com".to_string(),
+                emailbx: "errors.example.com".to_string(),
+            },
+            RecordData::mx(10, "mail.example.com"),
+            RecordData::Txt(vec!["v=spf1".to_string(), "-all".to_string()]),
+            RecordData::Unknown(28, vec![0x20, 0x01, 0x0d, 0xb8]),
+        ];
+
+        for record in records {
+            let text = record.to_string();
+            assert_eq!(
+                RecordData::parse(record.record_type(), &text),
+                Some(record),
+                "{}",
+                text
+            );
+        }
+
+        assert_eq!(RecordData::parse(RecordType::A as u16, "example.com"), None);
+        assert_eq!(
+            RecordData::parse(RecordType::Mx as u16, "mail.example.com"),
+            None
+        );
+        assert_eq!(RecordData::parse(RecordType::Txt as u16, "unquoted"), None);
+        assert_eq!(
+            RecordData::parse(RecordType::A as u16, "\\# 2 0a0b"),
+            Some(RecordData::Unknown(RecordType::A as u16, vec![0x0a, 0x0b]))
+        );
+
+        let mut msg =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        msg.answer.push(AnswerSection {
+            r_data: "not an address".to_string(),
+            ..AnswerSection::new("example.com", 300, RecordData::a(Ipv4Addr::LOCALHOST))
+        });
+        assert!(matches!(
+            msg.encode_full(),
+            Err(EncodeErrors::InvalidRData(_))
+        ));
+    }
+
+    #[test]
+    fn test_record_data_txt_too_long() {
+        let mut message = Vec::new();
+        let mut pointer_map = HashMap::new();
+        let res = RecordData::txt(&"a".repeat(256)).encode(&mut message, &mut pointer_map);
+        assert!(matches!(res, Err(EncodeErrors::CharacterStringTooLong(_))));
+    }
+
+    #[test]
+    fn test_dns_message_encode_multiple_questions() {
+        let mut msg =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        msg.questions
+            .push(QuestionSection::new("mail.example.com", RecordType::Mx));
+
+        let bytes = msg.encode_query();
+
+        assert_eq!(u16::from_be_bytes([bytes[4], bytes[5]]), 2); // qd_count
+
+        // First question: example.com, A, IN
+        let first_end = 12 + 13 + 4;
+        assert_eq!(
+            u16::from_be_bytes([bytes[first_end - 4], bytes[first_end - 3]]),
+            RecordType::A as u16
+        );
+
+        // Second question: "mail" label followed by a pointer to example.com (offset 12)
+        let second = &bytes[first_end..];
+        assert_eq!(&second[0..5], &[4, b'm', b'a', b'i', b'l']);
+        assert_eq!(&second[5..7], &[0xC0, 0x0C]);
+        assert_eq!(
+            u16::from_be_bytes([second[7], second[8]]),
+            RecordType::Mx as u16
+        );
+        assert_eq!(u16::from_be_bytes([second[9], second[10]]), 1);
+        assert_eq!(second.len(), 11);
+
+        assert_eq!(msg.encode_full().unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_dns_message_decode_query_a_response() {
+        let query =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        let mut buf = query.encode_query();
+        buf[2..4].copy_from_slice(&0x8180u16.to_be_bytes()); // flags: standard response
+        buf[6..8].copy_from_slice(&1u16.to_be_bytes()); // an_count
+        buf.extend_from_slice(&[
+            0xC0, 0x0C, // pointer to example.com
+            0x00, 0x01, // type A
+            0x00, 0x01, // class IN
+            0x00, 0x00, 0x01, 0x2C, // ttl 300
+            0x00, 0x04, // rdlength
+            93, 184, 216, 34,
+        ]);
+
+        let msg = DnsMessage::decode_query(&buf).unwrap();
+
+        assert_eq!(msg.header.id, query.header.id);
+        assert_eq!(msg.header.flags, 0x8180);
+        assert_eq!(msg.questions, query.questions);
+        assert_eq!(msg.answer.len(), 1);
+        assert_eq!(msg.answer[0].owner_name, "example.com");
+        assert_eq!(msg.answer[0].ttl, 300);
+        assert_eq!(msg.answer[0].r_data, "93.184.216.34");
+        assert!(msg.authority.is_empty());
+        assert!(msg.additional.is_empty());
+    }
+
+    #[test]
+    fn test_dns_message_decode_query_encode_full_round_trip() {
+        let mut msg =
+            DnsMessage::new_query("example.com", RecordType::Mx, OpCodeOptions::StandardQuery);
+        msg.answer.push(AnswerSection::new(
+            "example.com",
+            300,
+            RecordData::mx(10, "mail.example.com"),
+        ));
+        msg.authority.push(AuthoritySection::new(
+            "example.com",
+            3600,
+            RecordData::Ns("ns1.example.com".to_string()),
+        ));
+        msg.additional.push(AdditionalSection::new(
+            "mail.example.com",
+            60,
+            RecordData::Txt(vec!["v=spf1".to_string(), "-all".to_string()]),
+        ));
+
+        let decoded = DnsMessage::decode_query(&msg.encode_full().unwrap()).unwrap();
+
+        assert_eq!(decoded.answer[0].r_data, msg.answer[0].r_data);
+        assert_eq!(decoded.authority[0].r_data, msg.authority[0].r_data);
+        assert_eq!(decoded.authority[0].ttl, 3600);
+        assert_eq!(decoded.additional[0].owner_name, "mail.example.com");
+        assert_eq!(decoded.additional[0].r_data, msg.additional[0].r_data);
+    }
+
+    #[test]
+    fn test_dns_message_decode_query_ptr_round_trip() {
+        let query = DnsMessage::new_query(
+            "34.216.184.93.in-addr.arpa",
+            RecordType::Ptr,
+            OpCodeOptions::StandardQuery,
+        );
+        let mut buf = query.encode_query();
+        buf[2..4].copy_from_slice(&0x8180u16.to_be_bytes()); // flags: standard response
+        buf[6..8].copy_from_slice(&1u16.to_be_bytes()); // an_count
+        buf.extend_from_slice(&[
+            0xC0, 0x0C, // pointer to the question name
+            0x00, 0x0C, // type PTR
+            0x00, 0x01, // class IN
+            0x00, 0x00, 0x0E, 0x10, // ttl 3600
+            0x00, 0x0D, // rdlength
+            7, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 3, b'c', b'o', b'm', 0,
+        ]);
+
+        let msg = DnsMessage::decode_query(&buf).unwrap();
+        assert_eq!(msg.answer[0].record_type, RecordType::Ptr as u16);
+        assert_eq!(msg.answer[0].r_data, "example.com");
+        assert_eq!(
+            msg.answer[0].record_data(),
+            Some(RecordData::ptr("example.com"))
+        );
+        assert!(
+            msg.summary()
+                .ends_with("34.216.184.93.in-addr.arpa 3600 IN PTR example.com")
+        );
+
+        assert_eq!(msg.encode_full().unwrap(), buf);
+    }
+
+    #[test]
+    fn test_dns_message_decode_query_truncated() {
+        let mut msg =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        msg.answer.push(AnswerSection::new(
+            "example.com",
+            300,
+            RecordData::a(Ipv4Addr::new(93, 184, 216, 34)),
+        ));
+        let bytes = msg.encode_full().unwrap();
+
+        assert_eq!(
+            DnsMessage::decode_query(&bytes[..8]),
+            Err(DecodeErrors::InvalidBufferSize)
+        );
+        assert_eq!(
+            DnsMessage::decode_query(&bytes[..bytes.len() - 1]),
+            Err(DecodeErrors::InvalidBufferSize)
+        );
+        assert!(matches!(
+            DnsMessage::decode_query(&bytes[..15]),
+            Err(DecodeErrors::Decompressor(_))
+        ));
+    }
 }
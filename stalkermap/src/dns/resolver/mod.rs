@@ -68,6 +68,7 @@
 //! ## Supported Record Types
 //!
 //! - `A` — IPv4 address records  
+//! - `NS` — Authoritative name servers  
 //! - `CNAME` — Canonical name (alias)  
 //! - `SOA` — Start of Authority  
 //! - `MX` — Mail Exchange  
@@ -75,6 +76,18 @@
 //! - `PTR`, `HINFO`, `MINFO`, and `WKS`
 //!
 //! Each record type has both blocking and asynchronous resolver functions.
+//! Every `resolve_*` function takes the name as `impl AsRef<str>`, so both `&str` and
+//! `String` (e.g. a name built with `format!`) can be passed directly.
+//!
+//! For the common cases, `resolve_ipv4_addrs` and `resolve_txt_records` (and their
+//! `_async` variants) return only the parsed records instead of the full `DnsMessage`.
+//! `resolve_ipv4_raw` returns a `DnsResponse` that also carries the raw response bytes
+//! and the address of the server that answered.
+//! Queries time out after `DEFAULT_QUERY_TIMEOUT` (3 seconds); `resolve_ipv4_async_timeout`
+//! takes a custom timeout instead.
+//! `resolve_ipv4_via` (and `resolve_ipv4_via_async`) send the query to one given server,
+//! bypassing the configured server list, which is handy for diagnostics.
+//! `resolve_txt_class` queries another DNS class, e.g. `CHAOS` for `version.bind`.
 //!
 //! ```rust,ignore
 //! use stalkermap::dns::resolver::{resolve_cname, resolve_mx, resolve_txt};
@@ -129,8 +142,9 @@ cfg_if::cfg_if! {
     if #[cfg(any(feature = "agnostic"))] {
         pub mod agnostic;
         pub use self::agnostic::{
-            AdditionalSection, AnswerSection, AuthoritySection, DnsHeaderFlags, DnsMessage, HeaderSection,
-            OpCodeOptions, QuestionSection, RecordType, generate_id
+            AdditionalSection, AnswerSection, AuthoritySection, DnsHeaderFlags, DnsHeaderFlagsBuilder,
+            DecodeErrors, DnsMessage, EncodeErrors, FlagError, HeaderSection, OpCodeOptions, QueryClass, QuestionSection, RecordData, RecordType, ResponseBuilder,
+            generate_id, record_type_name, set_id_seed
         };
     } else if #[cfg(any(feature = "std", feature = "tokio-dep"))] {
         mod standard;
@@ -138,12 +152,15 @@ cfg_if::cfg_if! {
             OpCodeOptions, RecordType
         };
         pub use self::standard::{
-            DnsMessage, DecodeQueryErrors
+            AdditionalSection, AnswerSection, AuthoritySection, DnsMessage, DecodeQueryErrors,
+            HeaderSection, QueryClass, QuestionSection, RecordData, set_id_seed
         };
+        pub use crate::dns::compressor::CompressorErrors;
     } else if #[cfg(doc)] {
         // For documentation builds only — provide dummy types
         #[allow(dead_code)]
         #[derive(Debug)]
+        #[non_exhaustive]
         pub enum DecodeQueryErrors {
             /// Placeholder type for documentation mode.
             NotApplicable,
@@ -184,11 +201,14 @@ pub mod transporter;
 
 cfg_if::cfg_if! {
     if #[cfg(any( feature = "std", feature = "tokio-dep", all(doc, not(feature = "agnostic"))))]  {
-        use std::{error::Error, fmt::Display, time::Duration};
-        use std::net::{UdpSocket};
+        use std::{error::Error, fmt::Display, time::{Duration, Instant}};
+        use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
         use transporter::get_servers;
 
-
+        /// Timeout applied to sending a query and waiting for its response.
+        ///
+        /// Used by every resolver function that doesn't take an explicit timeout.
+        pub const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(3);
 
         /// Resolves an IPv4 (`A`) record for the given domain name using a blocking UDP query.
         ///
@@ -202,14 +222,36 @@ cfg_if::cfg_if! {
         ///
         /// # Errors
         /// Returns [`ResolverErrors`] if the query could not be sent or decoded.
-        pub fn resolve_ipv4(name: &str) -> Result<DnsMessage, ResolverErrors>{
+        pub fn resolve_ipv4(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors>{
+            #[cfg(not(doc))]
+            {
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::A, OpCodeOptions::StandardQuery);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
+            match send_query_blocking(bytes, id) {
+                Ok(response) => Ok(response.message),
+                Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
+            }
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// Resolves the name server (`NS`) records for the given domain name.
+        ///
+        /// These records list the authoritative name servers of the zone, see
+        /// [`DnsMessage::name_servers`].
+        pub fn resolve_ns(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::A, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Ns, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
             match send_query_blocking(bytes, id) {
-                Ok(msg) => Ok(msg),
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
             }
             }
@@ -223,14 +265,14 @@ cfg_if::cfg_if! {
         /// Resolves a canonical name (`CNAME`) record for the given domain name.
         ///
         /// This record maps an alias name to its true, canonical domain name.
-        pub fn resolve_cname(name: &str) -> Result<DnsMessage, ResolverErrors> {
+        pub fn resolve_cname(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Cname, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Cname, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
             match send_query_blocking(bytes, id) {
-                Ok(msg) => Ok(msg),
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
             }
             }
@@ -244,14 +286,14 @@ cfg_if::cfg_if! {
         /// Resolves a Start of Authority (`SOA`) record for the given domain name.
         ///
         /// The SOA record defines the authoritative DNS server and zone parameters.
-        pub fn resolve_soa(name: &str) -> Result<DnsMessage, ResolverErrors> {
+        pub fn resolve_soa(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Soa, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Soa, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
             match send_query_blocking(bytes, id) {
-                Ok(msg) => Ok(msg),
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
             }
             }
@@ -266,14 +308,14 @@ cfg_if::cfg_if! {
         /// Resolves a Well-Known Services (`WKS`) record for the given domain name.
         ///
         /// The WKS record describes network services associated with an address.
-        pub fn resolve_wks(name: &str) -> Result<DnsMessage, ResolverErrors> {
+        pub fn resolve_wks(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Wks, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Wks, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
             match send_query_blocking(bytes, id) {
-                Ok(msg) => Ok(msg),
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
             }
             }
@@ -284,14 +326,14 @@ cfg_if::cfg_if! {
             }
         }
 
-        pub fn resolve_ptr(name: &str) -> Result<DnsMessage, ResolverErrors> {
+        pub fn resolve_ptr(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Ptr, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Ptr, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
             match send_query_blocking(bytes, id) {
-                Ok(msg) => Ok(msg),
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
             }
             }
@@ -304,14 +346,16 @@ cfg_if::cfg_if! {
         }
 
         /// Resolves a Host Information (`HINFO`) record for the given domain name.
-        pub fn resolve_hinfo(name: &str) -> Result<DnsMessage, ResolverErrors> {
+        ///
+        /// The record can be read with [`DnsMessage::hinfo`].
+        pub fn resolve_hinfo(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Hinfo, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Hinfo, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
             match send_query_blocking(bytes, id) {
-                Ok(msg) => Ok(msg),
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
             }
             }
@@ -323,14 +367,16 @@ cfg_if::cfg_if! {
         }
 
         /// Resolves a Mail Information (`MINFO`) record for the given domain name.
-        pub fn resolve_minfo(name: &str) -> Result<DnsMessage, ResolverErrors> {
+        ///
+        /// The record can be read with [`DnsMessage::minfo`].
+        pub fn resolve_minfo(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Minfo, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Minfo, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
             match send_query_blocking(bytes, id) {
-                Ok(msg) => Ok(msg),
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
             }
             }
@@ -344,14 +390,14 @@ cfg_if::cfg_if! {
         /// Resolves a Mail Exchange (`MX`) record for the given domain name.
         ///
         /// MX records define the mail servers responsible for handling email for the domain.
-        pub fn resolve_mx(name: &str) -> Result<DnsMessage, ResolverErrors> {
+        pub fn resolve_mx(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Mx, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Mx, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
             match send_query_blocking(bytes, id) {
-                Ok(msg) => Ok(msg),
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
             }
             }
@@ -365,14 +411,45 @@ cfg_if::cfg_if! {
         /// Resolves a Text (`TXT`) record for the given domain name.
         ///
         /// Commonly used for SPF, DKIM, and general verification data.
-        pub fn resolve_txt(name: &str) -> Result<DnsMessage, ResolverErrors> {
+        pub fn resolve_txt(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrors> {
+            #[cfg(not(doc))]
+            {
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Txt, OpCodeOptions::StandardQuery);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
+            match send_query_blocking(bytes, id) {
+                Ok(response) => Ok(response.message),
+                Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
+            }
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// Resolves a Text (`TXT`) record in the given DNS `class`.
+        ///
+        /// Mostly useful with [`QueryClass::Ch`] to fingerprint a name server, which
+        /// may report its software in `version.bind` (or its identity in `hostname.bind`).
+        ///
+        /// # Example
+        /// ```rust,ignore
+        /// let response = resolve_txt_class("version.bind", QueryClass::Ch)?;
+        /// println!("{:?}", response.txt_records());
+        /// ```
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrors`] if the query could not be sent or decoded.
+        pub fn resolve_txt_class(name: impl AsRef<str>, class: QueryClass) -> Result<DnsMessage, ResolverErrors> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Txt, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query_class(name.as_ref(), RecordType::Txt, OpCodeOptions::StandardQuery, class);
 
-            let bytes = msg.encode_query();
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
             match send_query_blocking(bytes, id) {
-                Ok(msg) => Ok(msg),
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
             }
             }
@@ -383,14 +460,153 @@ cfg_if::cfg_if! {
             }
         }
 
-        fn send_query_blocking(query: Vec<u8>, id: u16) -> Result<DnsMessage, UdpErrors> {
-            let servers = get_servers();
+        /// Resolves the IPv4 addresses of the given domain name.
+        ///
+        /// Same query as [`resolve_ipv4`], but returns only the addresses of the `A`
+        /// records in the answer section instead of the whole [`DnsMessage`].
+        ///
+        /// # Example
+        /// ```rust,ignore
+        /// for ip in resolve_ipv4_addrs("example.com")? {
+        ///     println!("IPv4: {ip}");
+        /// }
+        /// ```
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrors`] if the query could not be sent or decoded.
+        pub fn resolve_ipv4_addrs(name: impl AsRef<str>) -> Result<Vec<Ipv4Addr>, ResolverErrors> {
+            #[cfg(not(doc))]
+            {
+            resolve_ipv4(name).map(|msg| msg.ipv4_addresses())
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// Resolves the `TXT` records of the given domain name.
+        ///
+        /// Same query as [`resolve_txt`], but returns one `String` per record instead
+        /// of the whole [`DnsMessage`].
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrors`] if the query could not be sent or decoded.
+        pub fn resolve_txt_records(name: impl AsRef<str>) -> Result<Vec<String>, ResolverErrors> {
+            #[cfg(not(doc))]
+            {
+            resolve_txt(name).map(|msg| msg.txt_records())
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// A decoded DNS response together with its wire form and origin.
+        ///
+        /// Returned by the `*_raw` resolver functions, useful for debugging or packet capture.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct DnsResponse {
+            /// The decoded message.
+            pub message: DnsMessage,
+            /// The response bytes exactly as received.
+            pub raw: Vec<u8>,
+            /// The name server that answered.
+            pub server: SocketAddr,
+        }
+
+        impl DnsResponse {
+            /// Decodes `raw` and keeps it alongside the resulting [`DnsMessage`].
+            ///
+            /// # Errors
+            /// Returns [`DecodeQueryErrors`] if `raw` is not a valid DNS message.
+            pub fn from_raw(raw: Vec<u8>, server: SocketAddr) -> Result<DnsResponse, DecodeQueryErrors> {
+                #[cfg(not(doc))]
+                {
+                let message = DnsMessage::decode_query(&raw)?;
+                Ok(DnsResponse { message, raw, server })
+                }
+
+                #[cfg(doc)]
+                {
+                    unimplemented!("Stub for documentation only");
+                }
+            }
+        }
+
+        /// Resolves an IPv4 (`A`) record and returns the full [`DnsResponse`].
+        ///
+        /// Same query as [`resolve_ipv4`], but also exposes the raw response bytes and
+        /// the server that answered.
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrors`] if the query could not be sent or decoded.
+        pub fn resolve_ipv4_raw(name: impl AsRef<str>) -> Result<DnsResponse, ResolverErrors> {
+            #[cfg(not(doc))]
+            {
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::A, OpCodeOptions::StandardQuery);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
+            send_query_blocking(bytes, id).map_err(ResolverErrors::SendingUdpQuery)
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// Resolves an IPv4 (`A`) record by querying only `server`.
+        ///
+        /// Unlike [`resolve_ipv4`], the configured server list (see
+        /// [`get_servers`]) is ignored, which is useful to
+        /// diagnose a single resolver.
+        ///
+        /// # Example
+        /// ```rust,ignore
+        /// let response = resolve_ipv4_via("example.com", "9.9.9.9:53".parse()?)?;
+        /// ```
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrors`] if the query could not be sent or decoded, or
+        /// [`UdpErrors::NoResponse`] (wrapped) if `server` didn't answer in time.
+        pub fn resolve_ipv4_via(name: impl AsRef<str>, server: SocketAddr) -> Result<DnsMessage, ResolverErrors> {
+            #[cfg(not(doc))]
+            {
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::A, OpCodeOptions::StandardQuery);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrors::InvalidName)?;
+            match send_query_blocking_to(bytes, id, &[server.to_string()], DEFAULT_QUERY_TIMEOUT) {
+                Ok(response) => Ok(response.message),
+                Err(e) => Err(ResolverErrors::SendingUdpQuery(e))
+            }
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        fn send_query_blocking(query: Vec<u8>, id: u16) -> Result<DnsResponse, UdpErrors> {
+            send_query_blocking_to(query, id, &get_servers(), DEFAULT_QUERY_TIMEOUT)
+        }
+
+        fn send_query_blocking_to(
+            query: Vec<u8>,
+            id: u16,
+            servers: &[String],
+            timeout: Duration,
+        ) -> Result<DnsResponse, UdpErrors> {
             let svr_len = servers.len();
             let socket = match UdpSocket::bind("0.0.0.0:0") {
                 Ok(s) => s,
                 Err(e) => return Err(UdpErrors::SocketIo(e))
             };
-            match socket.set_read_timeout(Some(Duration::from_secs(3))) {
+            match socket.set_read_timeout(Some(timeout)) {
                 Ok(_) => {},
                 Err(e) => return Err(UdpErrors::SocketIo(e))
             }
@@ -407,36 +623,40 @@ cfg_if::cfg_if! {
                 }
             }
 
-            match socket.set_write_timeout(Some(Duration::from_secs(3))){
+            match socket.set_write_timeout(Some(timeout)){
                 Ok(_) => {},
                 Err(e) => return Err(UdpErrors::SocketIo(e))
             }
 
+            // Every read shares the same deadline, so discarding stale packets
+            // never extends the overall timeout.
+            let deadline = Instant::now() + timeout;
             let mut buf = [0u8; 512];
-            let (len, _src) = match socket.recv_from(&mut buf) {
-                Ok(v) => v,
-                Err(_) => return Err(UdpErrors::NoResponse)
-            };
-
-
-            let response = &buf[..len];
-            match internal::check_response_id([response[0], response[1]], id) {
-                true => {},
-                false => return Err(UdpErrors::IdResponseInvalid(id))
-            }
+            let packets = std::iter::from_fn(|| {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() || socket.set_read_timeout(Some(remaining)).is_err() {
+                    return None;
+                }
+                socket.recv_from(&mut buf).ok().map(|(len, src)| (buf[..len].to_vec(), src))
+            });
 
-            let dns_msg = match DnsMessage::decode_query(response) {
+            let (response, src) = match internal::match_response(id, packets) {
                 Ok(v) => v,
-                Err(e) => return Err(UdpErrors::DecodeQueryErrors(e))
+                Err(0) => return Err(UdpErrors::NoResponse),
+                Err(_) => return Err(UdpErrors::IdResponseInvalid(id))
             };
 
-            Ok(dns_msg)
+            match DnsResponse::from_raw(response, src) {
+                Ok(v) => Ok(v),
+                Err(e) => Err(UdpErrors::DecodeQueryErrors(e))
+            }
         }
 
         // Async functions (tokio-dep)
 
         /// Represents errors that may occur when sending or receiving DNS queries over UDP.
         #[derive(Debug)]
+        #[non_exhaustive]
         pub enum UdpErrors{
             SocketIo(std::io::Error),
             IdResponseInvalid(u16),
@@ -459,15 +679,21 @@ cfg_if::cfg_if! {
         }
 
         /// Represents high-level resolver errors exposed to users.
+        ///
+        /// `InvalidName` is returned before anything is sent, when the queried name
+        /// is not a valid DNS name.
         #[derive(Debug)]
+        #[non_exhaustive]
         pub enum ResolverErrors {
-            SendingUdpQuery(UdpErrors)
+            SendingUdpQuery(UdpErrors),
+            InvalidName(CompressorErrors)
         }
 
         impl Display for ResolverErrors{
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self {
-                    ResolverErrors::SendingUdpQuery(e) => write!(f, "{}", e)
+                    ResolverErrors::SendingUdpQuery(e) => write!(f, "{}", e),
+                    ResolverErrors::InvalidName(e) => write!(f, "Invalid domain name: {}", e)
                 }
             }
         }
@@ -484,9 +710,36 @@ cfg_if::cfg_if! {
         }
 
         mod internal {
+            /// Maximum number of packets read while waiting for the response to a query.
+            pub(crate) const MAX_RESPONSE_READS: usize = 3;
+
             pub(crate) fn check_response_id(id_in_bytes: [u8; 2], id: u16 ) -> bool{
                 id == u16::from_be_bytes(id_in_bytes)
             }
+
+            /// Returns `true` if `packet` carries the query ID `id`.
+            pub(crate) fn is_response_to(packet: &[u8], id: u16) -> bool {
+                packet.len() >= 2 && check_response_id([packet[0], packet[1]], id)
+            }
+
+            /// Returns the first packet answering the query `id`, reading at most
+            /// [`MAX_RESPONSE_READS`] packets.
+            ///
+            /// Delayed or duplicated responses to earlier queries are discarded.
+            /// On failure, returns how many packets were discarded (`0` means nothing was received).
+            pub(crate) fn match_response<S>(
+                id: u16,
+                packets: impl IntoIterator<Item = (Vec<u8>, S)>,
+            ) -> Result<(Vec<u8>, S), usize> {
+                let mut discarded = 0;
+                for (packet, src) in packets.into_iter().take(MAX_RESPONSE_READS) {
+                    if is_response_to(&packet, id) {
+                        return Ok((packet, src));
+                    }
+                    discarded += 1;
+                }
+                Err(discarded)
+            }
         }
     }
 }
@@ -510,14 +763,37 @@ cfg_if::cfg_if! {
         use tokio::time::{timeout, Duration as TokioDuration };
 
         #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
-        pub async fn resolve_ipv4_async(name: &str) -> Result<DnsMessage, ResolverErrorsAsync>{
+        pub async fn resolve_ipv4_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync>{
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::A, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::A, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
-            match send_query_async(bytes, id).await {
-                Ok(msg) => Ok(msg),
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
+                Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
+            }
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// Resolves the name server (`NS`) records for the given domain name.
+        ///
+        /// These records list the authoritative name servers of the zone, see
+        /// [`DnsMessage::name_servers`].
+        #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
+        pub async fn resolve_ns_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync> {
+            #[cfg(not(doc))]
+            {
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Ns, OpCodeOptions::StandardQuery);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
             }
             }
@@ -532,14 +808,14 @@ cfg_if::cfg_if! {
         ///
         /// This record maps an alias name to its true, canonical domain name.
         #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
-        pub async fn resolve_cname_async(name: &str) -> Result<DnsMessage, ResolverErrorsAsync> {
+        pub async fn resolve_cname_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Cname, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Cname, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
-            match send_query_async(bytes, id).await {
-                Ok(msg) => Ok(msg),
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
             }
             }
@@ -554,14 +830,14 @@ cfg_if::cfg_if! {
         ///
         /// The SOA record defines the authoritative DNS server and zone parameters.
         #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
-        pub async fn resolve_soa_async(name: &str) -> Result<DnsMessage, ResolverErrorsAsync> {
+        pub async fn resolve_soa_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Soa, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Soa, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
-            match send_query_async(bytes, id).await {
-                Ok(msg) => Ok(msg),
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
             }
             }
@@ -576,14 +852,14 @@ cfg_if::cfg_if! {
         ///
         /// The WKS record describes network services associated with an address.
         #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
-        pub async fn resolve_wks_async(name: &str) -> Result<DnsMessage, ResolverErrorsAsync> {
+        pub async fn resolve_wks_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Wks, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Wks, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
-            match send_query_async(bytes, id).await {
-                Ok(msg) => Ok(msg),
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
             }
             }
@@ -595,14 +871,14 @@ cfg_if::cfg_if! {
         }
 
         #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
-        pub async fn resolve_ptr_async(name: &str) -> Result<DnsMessage, ResolverErrorsAsync> {
+        pub async fn resolve_ptr_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Ptr, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Ptr, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
-            match send_query_async(bytes, id).await {
-                Ok(msg) => Ok(msg),
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
             }
             }
@@ -615,15 +891,17 @@ cfg_if::cfg_if! {
         }
 
         /// Resolves a Host Information (`HINFO`) record for the given domain name.
+        ///
+        /// The record can be read with [`DnsMessage::hinfo`].
         #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
-        pub async fn resolve_hinfo_async(name: &str) -> Result<DnsMessage, ResolverErrorsAsync> {
+        pub async fn resolve_hinfo_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Hinfo, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Hinfo, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
-            match send_query_async(bytes, id).await {
-                Ok(msg) => Ok(msg),
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
             }
             }
@@ -635,15 +913,17 @@ cfg_if::cfg_if! {
         }
 
         /// Resolves a Mail Information (`MINFO`) record for the given domain name.
+        ///
+        /// The record can be read with [`DnsMessage::minfo`].
         #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
-        pub async fn resolve_minfo_async(name: &str) -> Result<DnsMessage, ResolverErrorsAsync> {
+        pub async fn resolve_minfo_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Minfo, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Minfo, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
-            match send_query_async(bytes, id).await {
-                Ok(msg) => Ok(msg),
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
             }
             }
@@ -658,14 +938,14 @@ cfg_if::cfg_if! {
         ///
         /// MX records define the mail servers responsible for handling email for the domain.
         #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
-        pub async fn resolve_mx_async(name: &str) -> Result<DnsMessage, ResolverErrorsAsync> {
+        pub async fn resolve_mx_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Mx, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Mx, OpCodeOptions::StandardQuery);
 
-            let bytes = msg.encode_query();
-            match send_query_async(bytes, id).await {
-                Ok(msg) => Ok(msg),
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
             }
             }
@@ -680,14 +960,126 @@ cfg_if::cfg_if! {
         ///
         /// Commonly used for SPF, DKIM, and general verification data.
         #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
-        pub async fn resolve_txt_async(name: &str) -> Result<DnsMessage, ResolverErrorsAsync> {
+        pub async fn resolve_txt_async(name: impl AsRef<str>) -> Result<DnsMessage, ResolverErrorsAsync> {
+            #[cfg(not(doc))]
+            {
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::Txt, OpCodeOptions::StandardQuery);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
+                Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
+            }
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// Resolves a Text (`TXT`) record in the given DNS `class`.
+        ///
+        /// Async counterpart of [`resolve_txt_class`].
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrorsAsync`] if the query could not be sent or decoded.
+        #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
+        pub async fn resolve_txt_class_async(name: impl AsRef<str>, class: QueryClass) -> Result<DnsMessage, ResolverErrorsAsync> {
+            #[cfg(not(doc))]
+            {
+            let (msg, id) = DnsMessage::new_query_class(name.as_ref(), RecordType::Txt, OpCodeOptions::StandardQuery, class);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
+                Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
+            }
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// Resolves the IPv4 addresses of the given domain name.
+        ///
+        /// Async counterpart of [`resolve_ipv4_addrs`].
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrorsAsync`] if the query could not be sent or decoded.
+        #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
+        pub async fn resolve_ipv4_addrs_async(name: impl AsRef<str>) -> Result<Vec<Ipv4Addr>, ResolverErrorsAsync> {
+            #[cfg(not(doc))]
+            {
+            resolve_ipv4_async(name).await.map(|msg| msg.ipv4_addresses())
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// Resolves the `TXT` records of the given domain name.
+        ///
+        /// Async counterpart of [`resolve_txt_records`].
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrorsAsync`] if the query could not be sent or decoded.
+        #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
+        pub async fn resolve_txt_records_async(name: impl AsRef<str>) -> Result<Vec<String>, ResolverErrorsAsync> {
+            #[cfg(not(doc))]
+            {
+            resolve_txt_async(name).await.map(|msg| msg.txt_records())
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        /// Resolves an IPv4 (`A`) record and returns the full [`DnsResponse`].
+        ///
+        /// Async counterpart of [`resolve_ipv4_raw`].
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrorsAsync`] if the query could not be sent or decoded.
+        #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
+        pub async fn resolve_ipv4_raw_async(name: impl AsRef<str>) -> Result<DnsResponse, ResolverErrorsAsync> {
             #[cfg(not(doc))]
             {
-            let (msg, id) = DnsMessage::new_query(name, RecordType::Txt, OpCodeOptions::StandardQuery);
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::A, OpCodeOptions::StandardQuery);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            send_query_async(bytes, id, DEFAULT_QUERY_TIMEOUT).await.map_err(ResolverErrorsAsync::SendingUdpQuery)
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
 
-            let bytes = msg.encode_query();
-            match send_query_async(bytes, id).await {
-                Ok(msg) => Ok(msg),
+        /// Resolves an IPv4 (`A`) record, waiting at most `timeout` for each network step.
+        ///
+        /// Same as [`resolve_ipv4_async`] (which uses [`DEFAULT_QUERY_TIMEOUT`]), but lets
+        /// high-latency links wait longer or fail-fast scans give up sooner.
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrorsAsync`] if the query could not be sent or decoded, or
+        /// [`TokioUdpErrors::Elapsed`] (wrapped) if no response arrived in time.
+        #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
+        pub async fn resolve_ipv4_async_timeout(name: impl AsRef<str>, timeout: TokioDuration) -> Result<DnsMessage, ResolverErrorsAsync> {
+            #[cfg(not(doc))]
+            {
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::A, OpCodeOptions::StandardQuery);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async(bytes, id, timeout).await {
+                Ok(response) => Ok(response.message),
                 Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
             }
             }
@@ -698,16 +1090,48 @@ cfg_if::cfg_if! {
             }
         }
 
-        async fn send_query_async(query: Vec<u8>, id: u16) -> Result<DnsMessage, TokioUdpErrors> {
-            let servers = get_servers();
+        /// Resolves an IPv4 (`A`) record by querying only `server`.
+        ///
+        /// Async counterpart of [`resolve_ipv4_via`].
+        ///
+        /// # Errors
+        /// Returns [`ResolverErrorsAsync`] if the query could not be sent or decoded, or
+        /// [`TokioUdpErrors::Elapsed`] (wrapped) if `server` didn't answer in time.
+        #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
+        pub async fn resolve_ipv4_via_async(name: impl AsRef<str>, server: SocketAddr) -> Result<DnsMessage, ResolverErrorsAsync> {
+            #[cfg(not(doc))]
+            {
+            let (msg, id) = DnsMessage::new_query(name.as_ref(), RecordType::A, OpCodeOptions::StandardQuery);
+
+            let bytes = msg.try_encode_query().map_err(ResolverErrorsAsync::InvalidName)?;
+            match send_query_async_to(bytes, id, &[server.to_string()], DEFAULT_QUERY_TIMEOUT).await {
+                Ok(response) => Ok(response.message),
+                Err(e) => Err(ResolverErrorsAsync::SendingUdpQuery(e))
+            }
+            }
+
+            #[cfg(doc)]
+            {
+                unimplemented!("Stub for documentation only");
+            }
+        }
+
+        async fn send_query_async(query: Vec<u8>, id: u16, timeout_duration: TokioDuration) -> Result<DnsResponse, TokioUdpErrors> {
+            send_query_async_to(query, id, &get_servers(), timeout_duration).await
+        }
+
+        async fn send_query_async_to(
+            query: Vec<u8>,
+            id: u16,
+            servers: &[String],
+            timeout_duration: TokioDuration,
+        ) -> Result<DnsResponse, TokioUdpErrors> {
             let svr_len = servers.len();
             let socket = match TokioUdpSocket::bind("0.0.0.0:0").await {
                 Ok(s) => s,
                 Err(e) => return Err(TokioUdpErrors::SocketIo(e))
             };
 
-            let timeout_duration = TokioDuration::from_secs(3);
-
             let mut sv_cont = 0;
 
             for server in servers.iter() {
@@ -727,27 +1151,32 @@ cfg_if::cfg_if! {
 
             let mut buf = [0u8; 512];
 
-            let (len, _src) = match timeout(timeout_duration, socket.recv_from(&mut buf)).await {
-                Ok(Ok(v)) => v,
-                Ok(Err(_)) => return Err(TokioUdpErrors::NoResponse),
-                Err(e) => return Err(TokioUdpErrors::Elapsed(e))
+            // Same bounded read loop as `send_query_blocking`: stale packets are discarded
+            // until the matching ID arrives, all within a single deadline.
+            let deadline = Instant::now() + timeout_duration;
+            let mut discarded = 0;
+            let (len, src) = loop {
+                if discarded == internal::MAX_RESPONSE_READS {
+                    return Err(TokioUdpErrors::IdResponseInvalid(id));
+                }
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                match timeout(remaining, socket.recv_from(&mut buf)).await {
+                    Ok(Ok((len, src))) if internal::is_response_to(&buf[..len], id) => break (len, src),
+                    Ok(Ok(_)) => discarded += 1,
+                    Ok(Err(_)) if discarded == 0 => return Err(TokioUdpErrors::NoResponse),
+                    Err(e) if discarded == 0 => return Err(TokioUdpErrors::Elapsed(e)),
+                    _ => return Err(TokioUdpErrors::IdResponseInvalid(id))
+                }
             };
 
-            let response = &buf[..len];
-            match internal::check_response_id([response[0], response[1]], id) {
-                true => {},
-                false => return Err(TokioUdpErrors::IdResponseInvalid(id))
+            match DnsResponse::from_raw(buf[..len].to_vec(), src) {
+                Ok(v) => Ok(v),
+                Err(e) => Err(TokioUdpErrors::DecodeQueryErrors(e))
             }
-
-            let dns_msg = match DnsMessage::decode_query(response) {
-                Ok(v) => v,
-                Err(e) => return Err(TokioUdpErrors::DecodeQueryErrors(e))
-            };
-
-            Ok(dns_msg)
         }
 
         #[derive(Debug)]
+        #[non_exhaustive]
         pub enum TokioUdpErrors{
             SocketIo(std::io::Error),
             Elapsed(Elapsed),
@@ -774,15 +1203,21 @@ cfg_if::cfg_if! {
         impl Error for TokioUdpErrors {}
 
         /// Represents high-level resolver errors exposed to users.
+        ///
+        /// `InvalidName` is returned before anything is sent, when the queried name
+        /// is not a valid DNS name.
         #[derive(Debug)]
+        #[non_exhaustive]
         pub enum ResolverErrorsAsync {
-            SendingUdpQuery(TokioUdpErrors)
+            SendingUdpQuery(TokioUdpErrors),
+            InvalidName(CompressorErrors)
         }
 
         impl Display for ResolverErrorsAsync{
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self {
-                    ResolverErrorsAsync::SendingUdpQuery(e) => write!(f, "{}", e)
+                    ResolverErrorsAsync::SendingUdpQuery(e) => write!(f, "{}", e),
+                    ResolverErrorsAsync::InvalidName(e) => write!(f, "Invalid domain name: {}", e)
                 }
             }
         }
@@ -796,3 +1231,141 @@ cfg_if::cfg_if! {
         }
     }
 }
+
+#[cfg(all(test, any(feature = "std", feature = "tokio-dep")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dns_response_raw_round_trip() {
+        let (msg, id) =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        let mut raw = msg.encode_query();
+        raw[6..8].copy_from_slice(&1u16.to_be_bytes()); // an_count
+        // Answer: pointer to the question name, A, IN, TTL 300, RDLENGTH 4, 93.184.216.34
+        raw.extend_from_slice(&[0xC0, 0x0C, 0, 1, 0, 1, 0, 0, 1, 44, 0, 4, 93, 184, 216, 34]);
+
+        let server: SocketAddr = "1.1.1.1:53".parse().unwrap();
+        let response = DnsResponse::from_raw(raw.clone(), server).unwrap();
+
+        assert_eq!(response.raw, raw);
+        assert_eq!(response.server, server);
+        assert_eq!(response.message.header.id, id);
+        assert_eq!(
+            response.message.ipv4_addresses(),
+            vec![Ipv4Addr::new(93, 184, 216, 34)]
+        );
+        assert_eq!(
+            DnsMessage::decode_query(&response.raw).unwrap(),
+            response.message
+        );
+    }
+
+    #[test]
+    fn test_dns_match_response_skips_mismatched_ids() {
+        let stale = vec![0x12, 0x34, 0x81, 0x80];
+        let fresh = vec![0xAB, 0xCD, 0x81, 0x80];
+
+        let matched =
+            internal::match_response(0xABCD, vec![(stale.clone(), 1), (fresh.clone(), 2)]);
+        assert_eq!(matched, Ok((fresh, 2)));
+
+        let exhausted = internal::match_response(0xABCD, vec![(stale.clone(), 1); 4]);
+        assert_eq!(exhausted, Err(internal::MAX_RESPONSE_READS));
+
+        let nothing = internal::match_response::<u8>(0xABCD, Vec::new());
+        assert_eq!(nothing, Err(0));
+    }
+
+    #[test]
+    fn test_dns_send_query_via_silent_server() {
+        // A bound socket that never answers.
+        let silent = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let servers = vec![silent.local_addr().unwrap().to_string()];
+
+        let (msg, id) =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        let started = Instant::now();
+        let res =
+            send_query_blocking_to(msg.encode_query(), id, &servers, Duration::from_millis(50));
+
+        assert!(matches!(res, Err(UdpErrors::NoResponse)));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_dns_resolve_accepts_owned_names() {
+        // Compile-time check only: never called, so nothing goes to the network.
+        let _owned = |name: String| resolve_ipv4(name);
+        let _borrowed = |name: &String| resolve_mx(name);
+
+        // A local server answering the query with one A record.
+        let server = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+        let responder = std::thread::spawn(move || {
+            let mut buf = [0u8; 512];
+            let (len, from) = server.recv_from(&mut buf).unwrap();
+            let mut raw = buf[..len].to_vec();
+            raw[2..4].copy_from_slice(&0x8180u16.to_be_bytes()); // response, RD, RA
+            raw[6..8].copy_from_slice(&1u16.to_be_bytes()); // an_count
+            raw.extend_from_slice(&[0xC0, 0x0C, 0, 1, 0, 1, 0, 0, 1, 44, 0, 4, 93, 184, 216, 34]);
+            server.send_to(&raw, from).unwrap();
+        });
+
+        let name = format!("{}.{}", "example", "com");
+        let response = resolve_ipv4_via(name, addr).unwrap();
+        responder.join().unwrap();
+
+        assert_eq!(
+            response.ipv4_addresses(),
+            vec![Ipv4Addr::new(93, 184, 216, 34)]
+        );
+    }
+
+    #[test]
+    fn test_dns_resolve_rejects_invalid_names() {
+        // Nothing listens here: the name must be rejected before any query is sent.
+        let server: SocketAddr = "127.0.0.1:9".parse().unwrap();
+        let name = format!("{}.com", "a".repeat(70));
+
+        let res = resolve_ipv4_via(&name, server);
+        assert!(matches!(
+            res,
+            Err(ResolverErrors::InvalidName(CompressorErrors::LabelTooLong(
+                _
+            )))
+        ));
+    }
+
+    #[cfg(feature = "tokio-dep")]
+    #[tokio::test]
+    async fn test_dns_resolve_async_rejects_invalid_names() {
+        let server: SocketAddr = "127.0.0.1:9".parse().unwrap();
+        let name = format!("{}.com", "a".repeat(70));
+
+        let res = resolve_ipv4_via_async(&name, server).await;
+        assert!(matches!(
+            res,
+            Err(ResolverErrorsAsync::InvalidName(
+                CompressorErrors::LabelTooLong(_)
+            ))
+        ));
+    }
+
+    #[cfg(feature = "tokio-dep")]
+    #[tokio::test]
+    async fn test_dns_send_query_async_times_out() {
+        // A bound socket that never answers.
+        let silent = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let servers = vec![silent.local_addr().unwrap().to_string()];
+
+        let (msg, id) =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        let started = std::time::Instant::now();
+        let res =
+            send_query_async_to(msg.encode_query(), id, &servers, Duration::from_millis(50)).await;
+
+        assert!(matches!(res, Err(TokioUdpErrors::Elapsed(_))));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
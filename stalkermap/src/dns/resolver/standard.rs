@@ -32,20 +32,33 @@
 //!
 //! After receiving a response, the message can be parsed into the same `DnsMessage` structure
 //! to inspect answers, authorities, or additional sections.
-use crate::dns::compressor::{DecompressorErrors, MessageCompressor};
+use crate::dns::compressor::{CompressorErrors, DecompressorErrors, MessageCompressor};
 use std::{collections::HashMap, error::Error, fmt::Display, net::Ipv4Addr};
 
+pub use internal::set_id_seed;
+
 mod internal {
     use rand::Rng;
     use rand::SeedableRng;
     use rand::rngs::SmallRng;
+    use std::cell::RefCell;
+
+    thread_local! {
+        /// Per-thread generator, seeded once from the thread RNG and reused by every query.
+        static ID_RNG: RefCell<SmallRng> = RefCell::new(SmallRng::from_rng(&mut rand::rng()));
+    }
 
     /// Generates a random 16-bit ID for a DNS query.
     pub(crate) fn generate_id() -> u16 {
-        let mut thread_rng = rand::rng();
-        let mut rng = SmallRng::from_rng(&mut thread_rng);
+        ID_RNG.with(|rng| rng.borrow_mut().random::<u16>())
+    }
 
-        rng.random::<u16>()
+    /// Re-seeds the query ID generator of the current thread.
+    ///
+    /// After this call the IDs generated on this thread are a reproducible sequence,
+    /// which is mostly useful for tests. Other threads are not affected.
+    pub fn set_id_seed(seed: u64) {
+        ID_RNG.with(|rng| *rng.borrow_mut() = SmallRng::seed_from_u64(seed));
     }
 }
 
@@ -77,6 +90,16 @@ impl DnsMessage {
         target: &str,
         record_type: RecordType,
         query_type: OpCodeOptions,
+    ) -> (DnsMessage, u16) {
+        Self::new_query_class(target, record_type, query_type, QueryClass::In)
+    }
+
+    /// Creates a new query message in the given `class` and returns the `id` used in the query.
+    pub(crate) fn new_query_class(
+        target: &str,
+        record_type: RecordType,
+        query_type: OpCodeOptions,
+        class: QueryClass,
     ) -> (DnsMessage, u16) {
         let id = internal::generate_id();
         (
@@ -102,7 +125,7 @@ impl DnsMessage {
                 question: QuestionSection {
                     name: target.to_string(),
                     record_type: record_type as u16,
-                    class: 1,
+                    class: class as u16,
                 },
                 answer: Vec::new(),
                 authority: Vec::new(),
@@ -115,19 +138,76 @@ impl DnsMessage {
     /// Encodes the DNS query into bytes for sending over the network.
     ///
     /// This uses RFC1035-compliant compression on domain names via `MessageCompressor`.
+    ///
+    /// # Panics
+    /// Panics if the question name is not a valid DNS name (a label longer than
+    /// [`MAX_LABEL_LEN`](crate::dns::compressor::MAX_LABEL_LEN) bytes or a name longer
+    /// than [`MAX_NAME_LEN`](crate::dns::compressor::MAX_NAME_LEN) bytes). Use
+    /// [`DnsMessage::try_encode_query`] for names that haven't been validated.
+    #[cfg_attr(not(test), allow(dead_code))]
     pub(crate) fn encode_query(&self) -> Vec<u8> {
+        self.try_encode_query()
+            .expect("the question name must be a valid DNS name")
+    }
+
+    /// Encodes the DNS query into bytes, failing if the question name can't be encoded.
+    ///
+    /// # Errors
+    /// Returns [`CompressorErrors`] if a label is longer than 63 bytes or the name
+    /// longer than 255 bytes.
+    pub(crate) fn try_encode_query(&self) -> Result<Vec<u8>, CompressorErrors> {
         let mut message: Vec<u8> = Vec::new();
         let mut pointer_map: HashMap<String, usize> = HashMap::new();
 
         message.extend_from_slice(&self.header.to_bytes());
 
-        MessageCompressor::compress(&self.question.name, &mut message, &mut pointer_map).unwrap();
+        MessageCompressor::compress(&self.question.name, &mut message, &mut pointer_map)?;
         message.extend_from_slice(&self.question.record_type.to_be_bytes());
         message.extend_from_slice(&self.question.class.to_be_bytes());
-        message
+        Ok(message)
     }
 
+    /// Default cap on the total number of resource records accepted when decoding resolver responses.
+    pub const DEFAULT_MAX_RECORDS: usize = 1000;
+
     pub(crate) fn decode_query(buf: &[u8]) -> Result<DnsMessage, DecodeQueryErrors> {
+        Self::decode_with_max_records(buf, Self::DEFAULT_MAX_RECORDS)
+    }
+
+    /// Decodes a DNS message in strict mode: only responses are accepted.
+    ///
+    /// A message whose QR bit is unset is a query, not an answer to one, so it is
+    /// rejected before anything else is parsed. Otherwise this behaves like the
+    /// lenient decoder used by the resolvers, which ignores the QR bit.
+    ///
+    /// # Errors
+    /// - [`DecodeQueryErrors::NotAResponse`] if the QR bit is not set.
+    /// - Any other [`DecodeQueryErrors`] if the buffer is truncated or malformed.
+    pub fn decode_strict(buf: &[u8]) -> Result<DnsMessage, DecodeQueryErrors> {
+        if buf.len() < 12 {
+            return Err(DecodeQueryErrors::InvalidBufferSize);
+        }
+
+        if !DnsHeaderFlags::_from_u16(u16::from_be_bytes([buf[2], buf[3]])).qr {
+            return Err(DecodeQueryErrors::NotAResponse);
+        }
+
+        Self::decode_query(buf)
+    }
+
+    /// Decodes a DNS message, refusing messages that declare too many records.
+    ///
+    /// The sum of `an_count`, `ns_count` and `ar_count` is checked against `max_records`
+    /// before any record is parsed, so a malicious header (e.g. `an_count = 65535`) is
+    /// rejected up front instead of driving the parse loops.
+    ///
+    /// # Errors
+    /// - [`DecodeQueryErrors::TooManyRecords`] if the declared counts exceed `max_records`.
+    /// - Any other [`DecodeQueryErrors`] if the buffer is truncated or malformed.
+    pub fn decode_with_max_records(
+        buf: &[u8],
+        max_records: usize,
+    ) -> Result<DnsMessage, DecodeQueryErrors> {
         //header
         if buf.len() < 12 {
             return Err(DecodeQueryErrors::InvalidBufferSize);
@@ -143,6 +223,12 @@ impl DnsMessage {
         };
         let mut offset: usize = 12;
 
+        let declared_records =
+            header.an_count as usize + header.ns_count as usize + header.ar_count as usize;
+        if declared_records > max_records {
+            return Err(DecodeQueryErrors::TooManyRecords(declared_records));
+        }
+
         if header.qd_count > 1 {
             return Err(DecodeQueryErrors::MultipleQuestionSections);
         }
@@ -191,13 +277,15 @@ impl DnsMessage {
             offset += 10;
 
             let temp_l = offset + ard_length as usize;
+            if temp_l > buf.len() {
+                return Err(DecodeQueryErrors::InvalidBufferSize);
+            }
             let ar_data = &buf[offset..temp_l];
 
             // check type of rr and parse it as so
             let record_data: RecordData =
                 decode_rdata(arecord_type, buf, &mut offset, ar_data, ard_length)?;
-
-            //offset += ard_length as usize;
+            offset = temp_l;
 
             answer.push(AnswerSection {
                 owner_name: aowner_name,
@@ -234,10 +322,14 @@ impl DnsMessage {
             offset += 10;
 
             let temp_l = offset + nrd_length as usize;
+            if temp_l > buf.len() {
+                return Err(DecodeQueryErrors::InvalidBufferSize);
+            }
             let nr_data = &buf[offset..temp_l];
 
             let record_data: RecordData =
                 decode_rdata(nrecord_type, buf, &mut offset, nr_data, nrd_length)?;
+            offset = temp_l;
 
             authority.push(AuthoritySection {
                 owner_name: nowner_name,
@@ -274,10 +366,14 @@ impl DnsMessage {
             offset += 10;
 
             let temp_l = offset + ard_length as usize;
+            if temp_l > buf.len() {
+                return Err(DecodeQueryErrors::InvalidBufferSize);
+            }
             let ar_data = &buf[offset..temp_l];
 
             let record_data: RecordData =
                 decode_rdata(arecord_type, buf, &mut offset, ar_data, ard_length)?;
+            offset = temp_l;
 
             additional.push(AdditionalSection {
                 owner_name: aowner_name,
@@ -297,8 +393,136 @@ impl DnsMessage {
             additional,
         })
     }
+
+    /// Returns the smallest TTL (in seconds) found in the answer and authority records.
+    ///
+    /// Useful for deciding how long a response can be cached.
+    /// Negative TTLs are clamped to `0`.
+    ///
+    /// Returns `None` if the message has no answer or authority records.
+    pub fn min_ttl(&self) -> Option<u32> {
+        self.answer
+            .iter()
+            .map(|rr| rr.ttl)
+            .chain(self.authority.iter().map(|rr| rr.ttl))
+            .map(|ttl| ttl.max(0) as u32)
+            .min()
+    }
+
+    /// Iterates over every resource record of the message as `(owner_name, r_data)`.
+    ///
+    /// Records are yielded section by section: answer, then authority, then additional.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let response = resolve_mx("example.com")?;
+    /// for (owner, data) in response.all_records() {
+    ///     println!("{owner}: {data:?}");
+    /// }
+    /// ```
+    pub fn all_records(&self) -> impl Iterator<Item = (&str, &RecordData)> {
+        self.answer
+            .iter()
+            .map(|rr| (rr.owner_name.as_str(), &rr.r_data))
+            .chain(
+                self.authority
+                    .iter()
+                    .map(|rr| (rr.owner_name.as_str(), &rr.r_data)),
+            )
+            .chain(
+                self.additional
+                    .iter()
+                    .map(|rr| (rr.owner_name.as_str(), &rr.r_data)),
+            )
+    }
+
+    /// Returns the IPv4 addresses of every `A` record in the answer section.
+    ///
+    /// Other record types (e.g. a `CNAME` chain preceding the address) are skipped.
+    pub fn ipv4_addresses(&self) -> Vec<Ipv4Addr> {
+        self.answer
+            .iter()
+            .filter_map(|rr| match rr.r_data {
+                RecordData::A(ip) => Some(ip),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the name server of every `NS` record in the answer section.
+    ///
+    /// Other record types are skipped.
+    pub fn name_servers(&self) -> Vec<&str> {
+        self.answer
+            .iter()
+            .filter_map(|rr| match &rr.r_data {
+                RecordData::Ns(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the text of every `TXT` record in the answer section.
+    ///
+    /// A single TXT record may be split into several character-strings (e.g. long SPF
+    /// or DKIM values); those are concatenated so each record yields one `String`.
+    pub fn txt_records(&self) -> Vec<String> {
+        self.answer
+            .iter()
+            .filter_map(|rr| match &rr.r_data {
+                RecordData::Txt(parts) => Some(parts.concat()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns every `MX` record in the answer section as `(preference, exchange)`,
+    /// sorted the way mail clients try them.
+    ///
+    /// Lower preferences come first; ties are broken by exchange name.
+    pub fn mx_sorted(&self) -> Vec<(u16, String)> {
+        let mut records: Vec<(u16, String)> = self
+            .answer
+            .iter()
+            .filter_map(|rr| match &rr.r_data {
+                RecordData::Mx {
+                    preference,
+                    exchange,
+                } => Some((*preference, exchange.clone())),
+                _ => None,
+            })
+            .collect();
+        records.sort();
+        records
+    }
+
+    /// Returns the first `HINFO` record of the answer section as `(cpu, os)`.
+    ///
+    /// Returns `None` if the answer holds no `HINFO` record.
+    pub fn hinfo(&self) -> Option<(String, String)> {
+        self.answer.iter().find_map(|rr| match &rr.r_data {
+            RecordData::Hinfo { cpu, os } => Some((cpu.clone(), os.clone())),
+            _ => None,
+        })
+    }
+
+    /// Returns the first `MINFO` record of the answer section as `(rmailbx, emailbx)`.
+    ///
+    /// Returns `None` if the answer holds no `MINFO` record.
+    pub fn minfo(&self) -> Option<(String, String)> {
+        self.answer.iter().find_map(|rr| match &rr.r_data {
+            RecordData::Minfo { rmailbx, emailbx } => Some((rmailbx.clone(), emailbx.clone())),
+            _ => None,
+        })
+    }
 }
 
+/// Decodes the RDATA of a single resource record.
+///
+/// `offset` must point at the start of the RDATA inside the whole message `buf`, so
+/// compressed names can follow pointers to names anywhere earlier in the message.
+/// How far `offset` moves depends on the record type (and is not moved at all for
+/// unknown types), so callers resume at `start + RDLENGTH` instead of trusting it.
 fn decode_rdata(
     arecord_type: u16,
     buf: &[u8],
@@ -371,6 +595,10 @@ fn decode_rdata(
             },
         }),
         TYPE_WKS => {
+            // 4-byte address and 1-byte protocol, the bitmap may be empty.
+            if ar_data.len() < 5 {
+                return Err(DecodeQueryErrors::InvalidBufferSize);
+            }
             let val = RecordData::Wks {
                 adress: Ipv4Addr::new(ar_data[0], ar_data[1], ar_data[2], ar_data[3]),
                 protocol: ar_data[4],
@@ -408,16 +636,25 @@ fn decode_rdata(
                 Err(e) => return Err(DecodeQueryErrors::DecompressorError(e)),
             },
         }),
-        TYPE_MX => Ok(RecordData::Mx {
-            preference: {
-                *offset += 2;
-                u16::from_be_bytes([ar_data[0], ar_data[1]])
-            },
-            exchange: match MessageCompressor::decompress_name(buf, offset) {
+        TYPE_MX => {
+            if ar_data.len() < 3 {
+                return Err(DecodeQueryErrors::InvalidBufferSize);
+            }
+            let preference = u16::from_be_bytes([ar_data[0], ar_data[1]]);
+
+            // The exchange starts right after the 2-byte preference and may be
+            // (or end with) a pointer to a name earlier in the message.
+            *offset += 2;
+            let exchange = match MessageCompressor::decompress_name(buf, offset) {
                 Ok(s) => s,
                 Err(e) => return Err(DecodeQueryErrors::DecompressorError(e)),
-            },
-        }),
+            };
+
+            Ok(RecordData::Mx {
+                preference,
+                exchange,
+            })
+        }
         TYPE_TXT => {
             let mut texts = Vec::new();
             let mut pos = 0;
@@ -439,10 +676,15 @@ fn decode_rdata(
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum DecodeQueryErrors {
     InvalidBufferSize,
     MultipleQuestionSections,
     DecompressorError(DecompressorErrors),
+    /// The header declares more records than allowed (declared total).
+    TooManyRecords(usize),
+    /// The QR bit is unset, the message is a query (strict mode only).
+    NotAResponse,
 }
 
 impl Display for DecodeQueryErrors {
@@ -455,6 +697,16 @@ impl Display for DecodeQueryErrors {
                 write!(f, "DNS Resolver doesn't support multiple question sections")
             }
             DecodeQueryErrors::DecompressorError(e) => write!(f, "{}", e),
+            DecodeQueryErrors::TooManyRecords(n) => {
+                write!(
+                    f,
+                    "The DNS message declares {} records, more than the allowed maximum",
+                    n
+                )
+            }
+            DecodeQueryErrors::NotAResponse => {
+                write!(f, "The DNS message is not a response (QR bit unset)")
+            }
         }
     }
 }
@@ -656,6 +908,19 @@ pub(crate) enum RecordType {
     //Caa = 257,
 }
 
+/// DNS classes that can be queried (RFC 1035 §3.2.4).
+///
+/// - `In` – the Internet, used by almost every query.
+/// - `Ch` – CHAOS, used by servers to report their software and identity
+///   (e.g. `version.bind`, `hostname.bind`).
+/// - `Hs` – Hesiod.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryClass {
+    In = 1,
+    Ch = 3,
+    Hs = 4,
+}
+
 const TYPE_A: u16 = RecordType::A as u16;
 const TYPE_NS: u16 = RecordType::Ns as u16;
 const TYPE_CNAME: u16 = RecordType::Cname as u16;
@@ -706,6 +971,18 @@ pub enum RecordData {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_set_id_seed_is_reproducible() {
+        set_id_seed(42);
+        let first: Vec<u16> = (0..8).map(|_| internal::generate_id()).collect();
+
+        set_id_seed(42);
+        let second: Vec<u16> = (0..8).map(|_| internal::generate_id()).collect();
+
+        assert_eq!(first, second);
+        assert!(first.windows(2).any(|w| w[0] != w[1]));
+    }
+
     #[test]
     fn test_record_type_to_bytes() {
         let record_a = RecordType::A;
@@ -761,6 +1038,21 @@ mod tests {
         assert!(msg.additional.is_empty());
     }
 
+    #[test]
+    fn test_dns_message_try_encode_query_label_too_long() {
+        let label = "a".repeat(70);
+        let (msg, _id) = DnsMessage::new_query(
+            &format!("{}.com", label),
+            RecordType::A,
+            OpCodeOptions::StandardQuery,
+        );
+
+        assert!(matches!(
+            msg.try_encode_query(),
+            Err(CompressorErrors::LabelTooLong(l)) if l == label
+        ));
+    }
+
     #[test]
     fn test_dns_message_encode_query() {
         let (msg, _id) =
@@ -818,4 +1110,335 @@ mod tests {
             assert_eq!(encoded_type, rec as u16);
         }
     }
+
+    #[test]
+    fn test_dns_message_min_ttl() {
+        let (mut msg, _id) =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        assert_eq!(msg.min_ttl(), None);
+
+        for ttl in [300, 60, 3600] {
+            msg.answer.push(AnswerSection {
+                owner_name: "example.com".to_string(),
+                record_type: RecordType::A as u16,
+                class: 1,
+                ttl,
+                rd_length: 4,
+                r_data: RecordData::A(Ipv4Addr::new(93, 184, 216, 34)),
+            });
+        }
+
+        assert_eq!(msg.min_ttl(), Some(60));
+
+        msg.answer[0].ttl = -5;
+        assert_eq!(msg.min_ttl(), Some(0));
+    }
+
+    #[test]
+    fn test_dns_message_record_extraction() {
+        let (mut msg, _id) =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        assert!(msg.ipv4_addresses().is_empty());
+        assert!(msg.txt_records().is_empty());
+
+        msg.answer.push(AnswerSection {
+            owner_name: "www.example.com".to_string(),
+            record_type: RecordType::Cname as u16,
+            class: 1,
+            ttl: 60,
+            rd_length: 13,
+            r_data: RecordData::Cname("example.com".to_string()),
+        });
+        for ip in [Ipv4Addr::new(93, 184, 216, 34), Ipv4Addr::new(10, 0, 0, 1)] {
+            msg.answer.push(AnswerSection {
+                owner_name: "example.com".to_string(),
+                record_type: RecordType::A as u16,
+                class: 1,
+                ttl: 60,
+                rd_length: 4,
+                r_data: RecordData::A(ip),
+            });
+        }
+        msg.answer.push(AnswerSection {
+            owner_name: "example.com".to_string(),
+            record_type: RecordType::Txt as u16,
+            class: 1,
+            ttl: 60,
+            rd_length: 22,
+            r_data: RecordData::Txt(vec!["v=spf1 ".to_string(), "-all".to_string()]),
+        });
+
+        assert_eq!(
+            msg.ipv4_addresses(),
+            vec![Ipv4Addr::new(93, 184, 216, 34), Ipv4Addr::new(10, 0, 0, 1)]
+        );
+        assert_eq!(msg.txt_records(), vec!["v=spf1 -all".to_string()]);
+    }
+
+    #[test]
+    fn test_dns_message_mx_sorted() {
+        let (mut msg, _id) =
+            DnsMessage::new_query("example.com", RecordType::Mx, OpCodeOptions::StandardQuery);
+        for (preference, exchange) in [
+            (20, "backup.example.com"),
+            (10, "mx2.example.com"),
+            (10, "mx1.example.com"),
+        ] {
+            msg.answer.push(AnswerSection {
+                owner_name: "example.com".to_string(),
+                record_type: RecordType::Mx as u16,
+                class: 1,
+                ttl: 60,
+                rd_length: 0,
+                r_data: RecordData::Mx {
+                    preference,
+                    exchange: exchange.to_string(),
+                },
+            });
+        }
+
+        assert_eq!(
+            msg.mx_sorted(),
+            vec![
+                (10, "mx1.example.com".to_string()),
+                (10, "mx2.example.com".to_string()),
+                (20, "backup.example.com".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dns_message_name_servers() {
+        let (mut msg, _id) =
+            DnsMessage::new_query("example.com", RecordType::Ns, OpCodeOptions::StandardQuery);
+        for r_data in [
+            RecordData::Ns("a.iana-servers.net".to_string()),
+            RecordData::Cname("alias.example.com".to_string()),
+            RecordData::Ns("b.iana-servers.net".to_string()),
+        ] {
+            msg.answer.push(AnswerSection {
+                owner_name: "example.com".to_string(),
+                record_type: RecordType::Ns as u16,
+                class: 1,
+                ttl: 60,
+                rd_length: 0,
+                r_data,
+            });
+        }
+
+        assert_eq!(
+            msg.name_servers(),
+            vec!["a.iana-servers.net", "b.iana-servers.net"]
+        );
+    }
+
+    #[test]
+    fn test_dns_message_hinfo_minfo() {
+        let (mut msg, _id) = DnsMessage::new_query(
+            "example.com",
+            RecordType::Hinfo,
+            OpCodeOptions::StandardQuery,
+        );
+        assert_eq!(msg.hinfo(), None);
+        assert_eq!(msg.minfo(), None);
+
+        for (record_type, r_data) in [
+            (
+                RecordType::Cname,
+                RecordData::Cname("host.example.com".to_string()),
+            ),
+            (
+                RecordType::Hinfo,
+                RecordData::Hinfo {
+                    cpu: "INTEL-386".to_string(),
+                    os: "UNIX".to_string(),
+                },
+            ),
+            (
+                RecordType::Minfo,
+                RecordData::Minfo {
+                    rmailbx: "admin.example.com".to_string(),
+                    emailbx: "errors.example.com".to_string(),
+                },
+            ),
+        ] {
+            msg.answer.push(AnswerSection {
+                owner_name: "example.com".to_string(),
+                record_type: record_type as u16,
+                class: 1,
+                ttl: 60,
+                rd_length: 0,
+                r_data,
+            });
+        }
+
+        assert_eq!(
+            msg.hinfo(),
+            Some(("INTEL-386".to_string(), "UNIX".to_string()))
+        );
+        assert_eq!(
+            msg.minfo(),
+            Some((
+                "admin.example.com".to_string(),
+                "errors.example.com".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_decode_query_rejects_too_many_records() {
+        let mut header = [0u8; 12];
+        header[4..6].copy_from_slice(&1u16.to_be_bytes()); // qd_count
+        header[6..8].copy_from_slice(&65535u16.to_be_bytes()); // an_count
+
+        assert!(matches!(
+            DnsMessage::decode_query(&header),
+            Err(DecodeQueryErrors::TooManyRecords(65535))
+        ));
+        assert!(matches!(
+            DnsMessage::decode_with_max_records(&header, 10),
+            Err(DecodeQueryErrors::TooManyRecords(65535))
+        ));
+    }
+
+    #[test]
+    fn test_decode_strict_requires_qr_bit() {
+        let (msg, _id) =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        let mut bytes = msg.encode_query();
+        bytes[6..8].copy_from_slice(&1u16.to_be_bytes()); // an_count
+        bytes.extend_from_slice(&[0xC0, 0x0C, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 93, 184, 216, 34]);
+
+        // QR = 0: a query
+        assert!(!DnsHeaderFlags::_from_u16(u16::from_be_bytes([bytes[2], bytes[3]])).qr);
+        assert!(matches!(
+            DnsMessage::decode_strict(&bytes),
+            Err(DecodeQueryErrors::NotAResponse)
+        ));
+        assert!(DnsMessage::decode_query(&bytes).is_ok());
+
+        // QR = 1: a response
+        bytes[2] |= 0x80;
+        let strict = DnsMessage::decode_strict(&bytes).unwrap();
+        assert_eq!(strict, DnsMessage::decode_query(&bytes).unwrap());
+    }
+
+    #[test]
+    fn test_decode_query_truncated_rdata() {
+        let (msg, _id) =
+            DnsMessage::new_query("example.com", RecordType::A, OpCodeOptions::StandardQuery);
+        let mut bytes = msg.encode_query();
+        bytes[6..8].copy_from_slice(&1u16.to_be_bytes()); // an_count
+        // Owner: pointer to the question name, A, IN, TTL 60, RDLENGTH 4, but no RDATA
+        bytes.extend_from_slice(&[0xC0, 0x0C, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4]);
+
+        assert!(matches!(
+            DnsMessage::decode_query(&bytes),
+            Err(DecodeQueryErrors::InvalidBufferSize)
+        ));
+    }
+
+    #[test]
+    fn test_decode_query_short_wks_rdata() {
+        let (msg, _id) =
+            DnsMessage::new_query("example.com", RecordType::Wks, OpCodeOptions::StandardQuery);
+        let mut bytes = msg.encode_query();
+        bytes[6..8].copy_from_slice(&1u16.to_be_bytes()); // an_count
+        // Owner: pointer to the question name, WKS, IN, TTL 60, RDLENGTH 3, 3 bytes of RDATA
+        bytes.extend_from_slice(&[0xC0, 0x0C, 0, 11, 0, 1, 0, 0, 0, 60, 0, 3, 10, 0, 0]);
+
+        assert!(matches!(
+            DnsMessage::decode_query(&bytes),
+            Err(DecodeQueryErrors::InvalidBufferSize)
+        ));
+    }
+
+    #[test]
+    fn test_decode_query_compressed_mx_in_authority() {
+        let (msg, _id) =
+            DnsMessage::new_query("example.com", RecordType::Mx, OpCodeOptions::StandardQuery);
+        let mut bytes = msg.encode_query();
+        bytes[8..10].copy_from_slice(&2u16.to_be_bytes()); // ns_count
+        bytes[10..12].copy_from_slice(&1u16.to_be_bytes()); // ar_count
+
+        #[rustfmt::skip]
+        bytes.extend_from_slice(&[
+            // Authority 1 (offset 29): AAAA (not decoded), owner -> example.com, 16 bytes RDATA
+            0xC0, 0x0C, 0, 28, 0, 1, 0, 0, 0, 60, 0, 16,
+            0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+            // Authority 2 (offset 57): MX, owner -> example.com, preference 10,
+            // exchange "mail" + pointer back to example.com (label at offset 71)
+            0xC0, 0x0C, 0, 15, 0, 1, 0, 0, 0, 60, 0, 9,
+            0, 10, 4, b'm', b'a', b'i', b'l', 0xC0, 0x0C,
+            // Additional: A, owner -> mail.example.com (offset 71), 10.0.0.25
+            0xC0, 71, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4,
+            10, 0, 0, 25,
+        ]);
+
+        let decoded = DnsMessage::decode_query(&bytes).unwrap();
+
+        assert_eq!(
+            decoded.authority[0].r_data,
+            RecordData::Unknown(28, bytes[41..57].to_vec())
+        );
+        assert_eq!(
+            decoded.authority[1].r_data,
+            RecordData::Mx {
+                preference: 10,
+                exchange: "mail.example.com".to_string(),
+            }
+        );
+        assert_eq!(decoded.additional[0].owner_name, "mail.example.com");
+        assert_eq!(
+            decoded.additional[0].r_data,
+            RecordData::A(Ipv4Addr::new(10, 0, 0, 25))
+        );
+    }
+
+    #[test]
+    fn test_dns_message_all_records_order() {
+        let (mut msg, _id) =
+            DnsMessage::new_query("example.com", RecordType::Mx, OpCodeOptions::StandardQuery);
+
+        msg.answer.push(AnswerSection {
+            owner_name: "example.com".to_string(),
+            record_type: RecordType::Mx as u16,
+            class: 1,
+            ttl: 60,
+            rd_length: 20,
+            r_data: RecordData::Mx {
+                preference: 10,
+                exchange: "mail.example.com".to_string(),
+            },
+        });
+        msg.authority.push(AuthoritySection {
+            owner_name: "example.com".to_string(),
+            record_type: RecordType::Ns as u16,
+            class: 1,
+            ttl: 60,
+            rd_length: 18,
+            r_data: RecordData::Ns("ns1.example.com".to_string()),
+        });
+        msg.additional.push(AdditionalSection {
+            owner_name: "mail.example.com".to_string(),
+            record_type: RecordType::A as u16,
+            class: 1,
+            ttl: 60,
+            rd_length: 4,
+            r_data: RecordData::A(Ipv4Addr::new(10, 0, 0, 25)),
+        });
+
+        let records: Vec<(&str, &RecordData)> = msg.all_records().collect();
+
+        assert_eq!(records.len(), 3);
+        assert!(matches!(records[0], ("example.com", RecordData::Mx { .. })));
+        assert!(matches!(records[1], ("example.com", RecordData::Ns(_))));
+        assert_eq!(
+            records[2],
+            (
+                "mail.example.com",
+                &RecordData::A(Ipv4Addr::new(10, 0, 0, 25))
+            )
+        );
+    }
 }
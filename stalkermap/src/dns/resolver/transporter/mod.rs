@@ -34,12 +34,20 @@
 //!   `SocketAddr` (e.g. `ip:port` or `[ipv6]:port`). Returns `Err` if any entry
 //!   is invalid.
 //!
+//! - `set_servers_default_port(list: Vec<&str>) -> Result<(), TransporterErrors>`  
+//!   Same as `set_servers()`, but bare IP addresses (`8.8.8.8`, `2001:4860:4860::8888`)
+//!   get the default DNS port `53` appended.
+//!
 //! - `reset_servers()`  
 //!   Removes any custom list and restores the default servers.
 //!
 //! - `has_custom_servers() -> bool`  
 //!   Returns `true` if a custom list is currently active, otherwise `false`.
 //!
+//! - `with_servers(list: Vec<&str>, f) -> Result<T, TransporterErrors>`  
+//!   Temporarily activates `list` while running `f`, then restores the previous
+//!   state (even if `f` panics).
+//!
 //! ## Example
 //!
 //! ```rust,ignore
@@ -91,7 +99,7 @@
 //! The module is intentionally minimal and focused: it does *not* perform network I/O.
 //! It only supplies validated, shared server addresses for whatever transport layer
 //! (UDP/TCP/DoT/DoH) you choose to implement separately or with the resolver function helpers.
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::{error::Error, fmt::Display, sync::RwLock};
 
@@ -143,6 +151,47 @@ pub fn set_servers(list: Vec<&str>) -> Result<(), TransporterErrors> {
     Ok(())
 }
 
+/// Port appended by [`set_servers_default_port`] to entries without one.
+const DEFAULT_DNS_PORT: u16 = 53;
+
+/// Replaces the active server list, appending port `53` to entries without a port.
+///
+/// Entries may be a full [`SocketAddr`] or a bare IP address, with or without
+/// brackets for IPv6 (`"8.8.8.8"`, `"[::1]"`). Everything else is rejected like
+/// in [`set_servers`].
+///
+/// # Example
+/// ```rust,ignore
+/// use stalkermap::dns::resolver::transporter::{get_servers, set_servers_default_port};
+///
+/// set_servers_default_port(vec!["8.8.8.8", "1.1.1.1:5353"])?;
+/// assert_eq!(get_servers().as_slice(), ["8.8.8.8:53", "1.1.1.1:5353"]);
+/// ```
+///
+/// # Errors
+/// Returns [`TransporterErrors::InvalidServer`] if any entry is neither a socket
+/// address nor an IP address.
+pub fn set_servers_default_port(list: Vec<&str>) -> Result<(), TransporterErrors> {
+    let mut servers = Vec::with_capacity(list.len());
+    for name in list {
+        if name.parse::<SocketAddr>().is_ok() {
+            servers.push(name.to_string());
+            continue;
+        }
+
+        let bare = name
+            .strip_prefix('[')
+            .and_then(|n| n.strip_suffix(']'))
+            .unwrap_or(name);
+        match bare.parse::<IpAddr>() {
+            Ok(ip) => servers.push(SocketAddr::new(ip, DEFAULT_DNS_PORT).to_string()),
+            Err(_) => return Err(TransporterErrors::InvalidServer(name.to_string())),
+        }
+    }
+
+    set_servers(servers.iter().map(String::as_str).collect())
+}
+
 /// Resets the server list to the built-in defaults.
 ///
 /// This removes any previously set custom list.
@@ -159,8 +208,52 @@ pub fn has_custom_servers() -> bool {
     (*custom).is_some()
 }
 
+/// Runs `f` with `list` as the active server list, then restores the previous state.
+///
+/// The previous state (custom list or defaults) is restored when `f` returns
+/// **and** if `f` panics, so a failing test or worker cannot leak its servers.
+///
+/// Every entry must parse as a valid [`SocketAddr`], exactly like [`set_servers`].
+///
+/// # Caveat
+/// The server list is still a process-wide global: while `f` runs, **every**
+/// thread resolving names will see `list`. This helper only scopes the change
+/// in time, not per thread.
+///
+/// # Example
+/// ```rust,ignore
+/// use stalkermap::dns::resolver::transporter::{get_servers, with_servers};
+///
+/// let servers = with_servers(vec!["1.1.1.1:53"], || get_servers()).unwrap();
+/// assert_eq!(servers.as_slice(), ["1.1.1.1:53"]);
+/// ```
+pub fn with_servers<T>(list: Vec<&str>, f: impl FnOnce() -> T) -> Result<T, TransporterErrors> {
+    let previous = CUSTOM_SERVERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+
+    set_servers(list)?;
+    let _guard = RestoreServersGuard { previous };
+
+    Ok(f())
+}
+
+/// RAII guard restoring the custom server list captured before [`with_servers`].
+struct RestoreServersGuard {
+    previous: Option<Arc<Vec<String>>>,
+}
+
+impl Drop for RestoreServersGuard {
+    fn drop(&mut self) {
+        let mut custom = CUSTOM_SERVERS.write().unwrap_or_else(|e| e.into_inner());
+        *custom = self.previous.take();
+    }
+}
+
 /// Errors that can occur when setting the server list.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum TransporterErrors {
     /// Raised when a provided server string could not be parsed into a valid [`SocketAddr`].
     InvalidServer(String),
@@ -175,3 +268,84 @@ impl Display for TransporterErrors {
 }
 
 impl Error for TransporterErrors {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // The server list is global, serialize the tests touching it.
+    static SERVERS_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_transporter_with_servers_restores_state() {
+        let _lock = SERVERS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        reset_servers();
+
+        let inside = with_servers(vec!["1.1.1.1:53"], || {
+            assert!(has_custom_servers());
+            get_servers()
+        })
+        .unwrap();
+
+        assert_eq!(inside.as_slice(), ["1.1.1.1:53"]);
+        assert!(!has_custom_servers());
+
+        set_servers(vec!["9.9.9.9:53"]).unwrap();
+        with_servers(vec!["1.1.1.1:53"], || ()).unwrap();
+        assert_eq!(get_servers().as_slice(), ["9.9.9.9:53"]);
+
+        reset_servers();
+    }
+
+    #[test]
+    fn test_transporter_with_servers_restores_state_after_panic() {
+        let _lock = SERVERS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        reset_servers();
+
+        let res = std::panic::catch_unwind(|| {
+            with_servers(vec!["1.1.1.1:53"], || panic!("boom")).unwrap();
+        });
+
+        assert!(res.is_err());
+        assert!(!has_custom_servers());
+    }
+
+    #[test]
+    fn test_transporter_set_servers_default_port() {
+        let _lock = SERVERS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        reset_servers();
+
+        assert!(matches!(
+            set_servers(vec!["8.8.8.8"]),
+            Err(TransporterErrors::InvalidServer(_))
+        ));
+
+        set_servers_default_port(vec!["8.8.8.8"]).unwrap();
+        assert_eq!(get_servers().as_slice(), ["8.8.8.8:53"]);
+
+        set_servers_default_port(vec!["1.1.1.1:5353", "2001:db8::1", "[::1]"]).unwrap();
+        assert_eq!(
+            get_servers().as_slice(),
+            ["1.1.1.1:5353", "[2001:db8::1]:53", "[::1]:53"]
+        );
+
+        assert!(matches!(
+            set_servers_default_port(vec!["dns.google"]),
+            Err(TransporterErrors::InvalidServer(_))
+        ));
+        assert_eq!(get_servers().as_slice()[0], "1.1.1.1:5353");
+
+        reset_servers();
+    }
+
+    #[test]
+    fn test_transporter_with_servers_invalid_entry() {
+        let _lock = SERVERS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        reset_servers();
+
+        let res = with_servers(vec!["not-a-server"], || ());
+        assert!(matches!(res, Err(TransporterErrors::InvalidServer(_))));
+        assert!(!has_custom_servers());
+    }
+}
@@ -14,6 +14,7 @@
 //! - **Input Sanitization & Validation** - Type-safe input validation with composable filters
 //! - **Interactive Terminal Interface** - User-friendly CLI input with validation loops
 //! - **URL Parsing** - Comprehensive HTTP/HTTPS URL parsing with host validation
+//! - **CIDR Expansion** - Lazy iteration over the addresses of IPv4/IPv6 ranges
 //!
 //! ("std" feature)
 //! - **DNS Resolver** - Blocking DNS queries with support for multiple record types (A, MX, TXT, SOA, PTR, WKS, etc.)
@@ -28,6 +29,7 @@
 //!     - idle detection & graceful shutdown
 //!     - customizable log formatter
 //!     - customizable actions
+//! - **Reporter** - ASCII table rendering and per-state summaries of scan results
 //!
 //! ("Agnostic" feature)
 //! - **DNS message structure** - With encoder helpers (RFC1035 compliant)
@@ -55,6 +57,10 @@
 //!   - Can include opinionated helpers to accelerate scanner development.
 //!   - Scanner Engine
 //!
+//! - **`tracing` (optional, with `tokio-dep`)**
+//!   - Emits [`tracing`](https://docs.rs/tracing) events at scanner lifecycle points
+//!     (connect success/failure, graceful shutdown). Without it no events are emitted.
+//!
 //! ## Quick Start
 //!
 //! Add this to your `Cargo.toml`:
@@ -249,7 +255,7 @@
 //! - **`utils`** - Core utilities for input handling and URL parsing
 //! - **`dns`** - DNS resolution and query utilities
 //! - **`scanner`** - Port scanning and network discovery
-//! - **`reporter`** - Report generation and export (planned)
+//! - **`reporter`** - Report rendering for scan results (`tokio-dep`)
 //!
 //! ## Design Principles
 //!
@@ -298,3 +304,7 @@ pub mod utils;
 #[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
 #[cfg(feature = "tokio-dep")]
 pub mod scanner;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio-dep")))]
+#[cfg(feature = "tokio-dep")]
+pub mod reporter;
@@ -0,0 +1,301 @@
+//! # Reporter
+//!
+//! Helpers that turn collected scan results into human-readable reports.
+//!
+//! ## Available reports
+//!
+//! - [`to_table`] — aligned ASCII table of `target | port | state | banner` rows
+//! - [`summarize`] — counts of results per port state, e.g. `"142 open, 20 closed"`
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use stalkermap::reporter::to_table;
+//! use stalkermap::scanner::*;
+//!
+//! let scanner = Scanner::<StructuredFormatter>::new().build();
+//! let mut logs = scanner.get_logs_stream().await.unwrap();
+//!
+//! // ... add tasks, execute them and collect the records ...
+//! let mut records = Vec::new();
+//! while let Some(record) = logs.next().await {
+//!     if StructuredFormatter.is_idle_signal(&record) {
+//!         break;
+//!     }
+//!     records.push(record);
+//! }
+//!
+//! println!("{}", to_table(&records));
+//! println!("{}", summarize(&records));
+//! ```
+use crate::scanner::{Action, ActionIsPortOpen, LogFormatter, LogRecord, StructuredFormatter};
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Banners longer than this (in characters) are truncated with an ellipsis.
+const MAX_BANNER_WIDTH: usize = 40;
+
+const HEADERS: [&str; 4] = ["target", "port", "state", "banner"];
+
+/// Generic result key holding the port state, checked when [`ActionIsPortOpen`] didn't run.
+const CONNECTION_KEY: &str = "Connection";
+
+/// State counted for records without any port-state result.
+const UNKNOWN_STATE: &str = "unknown";
+
+/// Renders scan results as an aligned ASCII table.
+///
+/// Each record becomes a `target | port | state | banner` row, where the first
+/// three columns come from the action results written by [`ActionIsPortOpen`]
+/// and the banner is the first line of the record data. Column widths are
+/// computed from the data, so every line of the table has the same width.
+///
+/// Idle events are skipped.
+///
+/// # Example
+/// ```text
+/// target    | port | state  | banner
+/// ----------+------+--------+----------------
+/// 127.0.0.1 | 22   | open   | SSH-2.0-OpenSSH
+/// 10.0.0.1  | 80   | closed |
+/// ```
+pub fn to_table(records: &[LogRecord]) -> String {
+    let state_key = ActionIsPortOpen {}.name();
+
+    let rows: Vec<[String; 4]> = records
+        .iter()
+        .filter(|record| !StructuredFormatter.is_idle_signal(record))
+        .map(|record| {
+            let field = |key: &str| {
+                record
+                    .header_response
+                    .actions_results
+                    .get(key)
+                    .map(ToString::to_string)
+                    .unwrap_or_default()
+            };
+
+            [
+                field("target"),
+                field("port"),
+                field(state_key),
+                banner(&record.data),
+            ]
+        })
+        .collect();
+
+    let mut widths = HEADERS.map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = render_row(&HEADERS, &widths);
+    table.push('\n');
+    let separator: Vec<String> = widths
+        .iter()
+        .enumerate()
+        .map(|(i, width)| {
+            // Outer columns only have one padding space (no leading/trailing " | ").
+            let padding = if i == 0 || i == widths.len() - 1 {
+                1
+            } else {
+                2
+            };
+            "-".repeat(width + padding)
+        })
+        .collect();
+    table.push_str(&separator.join("+"));
+
+    for row in &rows {
+        table.push('\n');
+        table.push_str(&render_row(row, &widths));
+    }
+
+    table
+}
+
+/// Number of scan results per port state, as returned by [`summarize`].
+///
+/// `Display` renders the one-line summary, most frequent state first
+/// (ties in alphabetical order), e.g. `"142 open, 20 closed, 5 filtered"`,
+/// or `"no results"` when nothing was counted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Number of records for each state (`"open"`, `"closed"`, `"unknown"`, ...).
+    pub counts: BTreeMap<String, usize>,
+}
+
+impl Summary {
+    /// Returns the number of records with the given state (`0` if none).
+    pub fn count(&self, state: &str) -> usize {
+        self.counts.get(state).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of records counted, whatever their state.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.counts.is_empty() {
+            return write!(f, "no results");
+        }
+
+        let mut counts: Vec<(&String, &usize)> = self.counts.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        let parts: Vec<String> = counts
+            .iter()
+            .map(|(state, count)| format!("{} {}", count, state))
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Counts scan results by port state.
+///
+/// The state is read from the result written by [`ActionIsPortOpen`] (or by
+/// the engine when the connection failed), falling back to the generic
+/// `"Connection"` key. Records with neither are counted as `"unknown"`.
+///
+/// Idle events are skipped.
+///
+/// # Example
+/// ```rust,ignore
+/// let summary = summarize(&records);
+/// assert_eq!(summary.count("open"), 142);
+/// println!("{}", summary); // 142 open, 20 closed, 5 filtered
+/// ```
+pub fn summarize(records: &[LogRecord]) -> Summary {
+    let state_key = ActionIsPortOpen {}.name();
+    let mut summary = Summary::default();
+
+    for record in records
+        .iter()
+        .filter(|record| !StructuredFormatter.is_idle_signal(record))
+    {
+        let results = &record.header_response.actions_results;
+        let state = results
+            .get(state_key)
+            .or_else(|| results.get(CONNECTION_KEY))
+            .map(ToString::to_string)
+            .unwrap_or_else(|| UNKNOWN_STATE.to_string());
+
+        *summary.counts.entry(state).or_insert(0) += 1;
+    }
+
+    summary
+}
+
+/// Pads every cell to its column width and joins them with `" | "`.
+fn render_row<S: AsRef<str>>(cells: &[S], widths: &[usize; 4]) -> String {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<width$}", cell.as_ref(), width = width))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Returns the first non-empty line of `data`, truncated to [`MAX_BANNER_WIDTH`].
+fn banner(data: &str) -> String {
+    let line = data
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+
+    if line.chars().count() > MAX_BANNER_WIDTH {
+        let mut truncated: String = line.chars().take(MAX_BANNER_WIDTH - 1).collect();
+        truncated.push('…');
+        truncated
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::LogHeader;
+    use std::collections::HashMap;
+
+    fn record(target: &str, port: &str, state: &str, data: &str) -> LogRecord {
+        let mut actions_results = HashMap::new();
+        actions_results.insert("target".to_string(), target.into());
+        actions_results.insert("port".to_string(), port.into());
+        actions_results.insert("IsPortOpen".to_string(), state.into());
+
+        LogRecord {
+            task_seq: 1,
+            header_response: LogHeader { actions_results },
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_reporter_to_table_aligned() {
+        let records = vec![
+            record("127.0.0.1", "22", "open", "SSH-2.0-OpenSSH_9.6\r\n"),
+            record("scanme.example.org", "8080", "closed", ""),
+            record("10.0.0.1", "80", "open", &"A".repeat(100)),
+            StructuredFormatter.idle_output(),
+        ];
+
+        let table = to_table(&records);
+        let lines: Vec<&str> = table.lines().collect();
+
+        // header + separator + 3 rows, the idle record is skipped
+        assert_eq!(lines.len(), 5);
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+
+        assert!(lines[0].starts_with("target             | port | state "));
+        assert!(lines[1].starts_with("-------------------+------+-"));
+        assert!(lines[2].contains("| SSH-2.0-OpenSSH_9.6"));
+        assert!(lines[4].ends_with('…'));
+    }
+
+    #[test]
+    fn test_reporter_summarize_counts_by_state() {
+        let mut records = vec![
+            record("10.0.0.1", "22", "open", ""),
+            record("10.0.0.1", "80", "open", ""),
+            record("10.0.0.1", "443", "open", ""),
+            record("10.0.0.1", "21", "closed", ""),
+            record("10.0.0.1", "25", "filtered", ""),
+            record("10.0.0.1", "23", "closed", ""),
+            StructuredFormatter.idle_output(),
+        ];
+
+        let mut generic = record("10.0.0.2", "8080", "", "");
+        let results = &mut generic.header_response.actions_results;
+        results.remove("IsPortOpen");
+        results.insert("Connection".to_string(), "filtered".into());
+        records.push(generic);
+
+        let mut stateless = record("10.0.0.3", "9000", "", "");
+        stateless
+            .header_response
+            .actions_results
+            .remove("IsPortOpen");
+        records.push(stateless);
+
+        let summary = summarize(&records);
+        assert_eq!(summary.count("open"), 3);
+        assert_eq!(summary.count("closed"), 2);
+        assert_eq!(summary.count("filtered"), 2);
+        assert_eq!(summary.count("unknown"), 1);
+        assert_eq!(summary.count("refused"), 0);
+        assert_eq!(summary.total(), 8);
+        assert_eq!(
+            summary.to_string(),
+            "3 open, 2 closed, 2 filtered, 1 unknown"
+        );
+
+        assert_eq!(summarize(&[]).to_string(), "no results");
+    }
+}
@@ -1,22 +1,46 @@
 //! Defines the `Action` system used by the scanning engine.
 //!
 //! # Execution Flow
-//! For each action in the task:
+//! After the TCP handshake, a task runs in three phases:
 //!
-//! - If `set_read_from_successfull_connection()` returns `false`, the action
-//!   is called immediately after the TCP handshake via:
-//!   [`execute_after_successfull_connection`](Action::execute_after_successfull_connection).
+//! 1. **Write** — every payload returned by [`wants_write`](Action::wants_write)
+//!    is sent to the target, in action order.
+//! 2. **Read** — if any action's `set_read_from_successfull_connection()` returns
+//!    `true`, the engine performs a **single read**. It waits (up to the scanner
+//!    timeout) for data only if a payload was written or an action asks to
+//!    [`wait_for_data`](Action::wait_for_data); otherwise it only takes what the
+//!    target already sent.
+//! 3. **Execute** — each action is called, in order:
+//!     - if it reads, via
+//!       [`execute_after_successfull_connection_and_read`](Action::execute_after_successfull_connection_and_read),
+//!       with the bytes from the read phase and the shared result map,
+//!     - otherwise via
+//!       [`execute_after_successfull_connection`](Action::execute_after_successfull_connection).
 //!
-//! - If it returns `true`, the engine performs a **single non-blocking read**
-//!   on the socket and then calls:
-//!   [`execute_after_successfull_connection_and_read`](Action::execute_after_successfull_connection_and_read),
-//!   providing:
-//!     - a slice containing the bytes successfully read,
-//!     - the shared result map.
+//! All reading actions see the same bytes, which are the server's answer to
+//! everything written in phase 1. When several actions write, their payloads
+//! are sent back to back on the same connection, so combine only probes the
+//! target service can answer in sequence (e.g. don't mix a TLS `ClientHello`
+//! with a plain-text HTTP request).
+//!
+//! Actions that need more network I/O than the task's connection (e.g. to
+//! follow an HTTP redirect) can then do it in
+//! [`execute_follow_up`](Action::execute_follow_up), which is async and can open
+//! new connections with [`ScanContext::connect`]. Each action can bound its
+//! follow-up, and the task's read if it reads, with its own
+//! [`timeout`](Action::timeout).
+//!
+//! Once every action has run, the engine calls
+//! [`finalize`](Action::finalize) on each action (in order). Actions can use
+//! it to aggregate the results produced by the whole task.
 //!
 //! This makes it easy to build actions for:
 //! - simple port-state checks (like `IsPortOpen`),
-//! - banner grabbing,
+//! - TLS detection (like `TlsDetect`),
+//! - certificate inspection (like `TlsCn`),
+//! - banner grabbing and keyword matching (like `MatchBanner`),
+//! - HTTP redirect tracking (like `FollowRedirects`),
+//! - conditional chains, e.g. "grab the banner only if the port is open" (like `If`),
 //! - protocol heuristics,
 //! - service identification,
 //! - custom user-defined logic.
@@ -29,7 +53,28 @@
 //!
 //! The `actions!()` macro is provided for ergonomic construction of the
 //! `Vec<Box<dyn Action>>` required by the scanning engine.
-use std::collections::HashMap;
+//!
+//! # Results
+//! Actions store their results as typed [`ActionValue`]s, so structured data
+//! (numbers, flags, lists) doesn't have to be stringified and is serialized as
+//! the matching JSON type by `JsonFormatter`.
+//!
+//! # Cloning
+//! Every action must be cloneable through [`CloneAction`], which is
+//! implemented automatically for any `Action + Clone`. This makes
+//! `Box<dyn Action>` (and therefore `Task`) `Clone`, so the same scan can
+//! be queued again, e.g. for periodic monitoring.
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::{collections::HashMap, fmt::Display, net::SocketAddr, time::Duration};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    time::timeout,
+};
+
+use super::{AsyncConn, Connector};
+use crate::utils::{UrlParser, url::Scheme};
 
 /// Creates a `Vec<Box<dyn Action>>` from a list of action expressions.
 ///
@@ -54,6 +99,7 @@ macro_rules! actions {
 ///
 /// Implementors provide:
 /// - a unique [`name`](Action::name),
+/// - optional bytes to send after connect ([`wants_write`](Action::wants_write)),
 /// - whether the scanner should read data after connect,
 /// - a handler for the “just connected” case,
 /// - a handler for the “connected + read data” case.
@@ -70,7 +116,11 @@ macro_rules! actions {
 /// - Actions should insert their results into `actions_results` under a key
 ///   matching `name()`.
 /// - All actions in a task share the same result map.
-pub trait Action: Send + Sync + 'static {
+/// - Actions must be cloneable (see [`CloneAction`]); deriving `Clone` is enough.
+/// - Implementations overriding [`execute_follow_up`](Action::execute_follow_up)
+///   must be annotated with `#[async_trait]`.
+#[async_trait]
+pub trait Action: CloneAction + Send + Sync + 'static {
     /// Returns the static name of the action.
     ///
     /// This is used as the key when inserting values into
@@ -87,6 +137,32 @@ pub trait Action: Send + Sync + 'static {
     ///   [`execute_after_successfull_connection_and_read`](Self::execute_after_successfull_connection_and_read).
     fn set_read_from_successfull_connection(&self) -> bool;
 
+    /// Bytes to send to the target right after the TCP connection.
+    ///
+    /// The payloads of all actions are written (in action order) before the
+    /// engine's single read, so request/response probes can see the server's
+    /// answer. An action may write without reading, e.g. to send a request
+    /// whose answer is interpreted by another action.
+    ///
+    /// The default implementation sends nothing.
+    fn wants_write(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Whether the engine's read should wait for data even if no payload was written.
+    ///
+    /// After a payload from [`wants_write`](Self::wants_write) the read always
+    /// waits (up to the scanner timeout) for the answer. Otherwise it only takes
+    /// what the target already sent, unless an action returns `true` here, e.g.
+    /// to grab the banner of services that speak first (SSH, SMTP, FTP, ...).
+    /// On services that wait for the client instead, the task then idles for
+    /// the whole timeout.
+    ///
+    /// The default implementation returns `false`.
+    fn wait_for_data(&self) -> bool {
+        false
+    }
+
     /// Executed after a successful TCP connection **when the action does not
     /// require any socket data**.
     ///
@@ -98,14 +174,14 @@ pub trait Action: Send + Sync + 'static {
     fn execute_after_successfull_connection(
         &self,
         ctx: &ScanContext,
-        actions_results: &mut HashMap<String, String>,
+        actions_results: &mut HashMap<String, ActionValue>,
     );
 
     /// Executed after a successful TCP connection **when the action requested
     /// socket data to be read**.
     ///
     /// The engine supplies:
-    /// - `ctx`: scan metadata (`target`, `port`, `task_id`),
+    /// - `ctx`: scan metadata (`target`, `port`, `task_id`, `task_seq`),
     /// - `raw_data`: slice of the received bytes,
     /// - `actions_results`: shared mutable result map.
     ///
@@ -118,8 +194,158 @@ pub trait Action: Send + Sync + 'static {
         &self,
         ctx: &ScanContext,
         raw_data: &[u8],
-        actions_results: &mut HashMap<String, String>,
+        actions_results: &mut HashMap<String, ActionValue>,
     );
+
+    /// Executed after every action has run, before [`finalize`](Self::finalize).
+    ///
+    /// Unlike the other hooks this one is async, so actions can perform further
+    /// network I/O, e.g. open new connections with [`ScanContext::connect`].
+    /// `raw_data` holds the bytes from the task's read phase (empty if no action reads).
+    ///
+    /// The default implementation does nothing.
+    async fn execute_follow_up(
+        &self,
+        _ctx: &ScanContext<'_>,
+        _raw_data: &[u8],
+        _actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+    }
+
+    /// Time limit for this action's network I/O, independent of the scanner's
+    /// connection timeout.
+    ///
+    /// It bounds [`execute_follow_up`](Self::execute_follow_up) and, for an
+    /// action that reads, the task's shared read: the read waits at most for the
+    /// smallest limit among the reading actions. When exceeded, the engine
+    /// records `"<name>_timeout": true` in `actions_results` (the read then
+    /// yields no data). The synchronous hooks are not covered: they can't be
+    /// interrupted and must return quickly.
+    ///
+    /// The default implementation sets no limit.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Executed once after **all** actions of the task have run.
+    ///
+    /// At this point `actions_results` holds the output of every action in the
+    /// task, which makes this hook suitable for aggregator actions, e.g.
+    /// synthesizing a `"Summary"` entry from previous results.
+    ///
+    /// The default implementation does nothing.
+    fn finalize(&self, _ctx: &ScanContext, _actions_results: &mut HashMap<String, ActionValue>) {}
+}
+
+/// Allows cloning boxed actions.
+///
+/// Implemented automatically for every type that is both [`Action`] and
+/// [`Clone`], so implementors normally only need `#[derive(Clone)]`.
+pub trait CloneAction {
+    /// Returns a boxed copy of this action.
+    fn clone_box(&self) -> Box<dyn Action>;
+}
+
+impl<T> CloneAction for T
+where
+    T: Action + Clone,
+{
+    fn clone_box(&self) -> Box<dyn Action> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Action> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// A typed value stored by an action in `actions_results`.
+///
+/// Serialized without a tag, so each variant maps to the natural JSON type:
+///
+/// | Variant | JSON           |
+/// |---------|----------------|
+/// | `Str`   | `"open"`       |
+/// | `Int`   | `443`          |
+/// | `Bool`  | `true`         |
+/// | `List`  | `["22", "80"]` |
+///
+/// `From` conversions are provided for the common Rust types, and [`Display`]
+/// renders the value as plain text (lists are joined with `", "`).
+///
+/// # Example
+/// ```rust,no_run
+/// use stalkermap::scanner::ActionValue;
+///
+/// assert_eq!(ActionValue::from("open"), ActionValue::Str("open".to_string()));
+/// assert_eq!(ActionValue::from(443u16).to_string(), "443");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ActionValue {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+    List(Vec<String>),
+}
+
+impl ActionValue {
+    /// Returns the string if the value is a [`ActionValue::Str`].
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ActionValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Str(s) => write!(f, "{}", s),
+            Self::Int(n) => write!(f, "{}", n),
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::List(items) => write!(f, "{}", items.join(", ")),
+        }
+    }
+}
+
+impl From<String> for ActionValue {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<&str> for ActionValue {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<i64> for ActionValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u16> for ActionValue {
+    fn from(value: u16) -> Self {
+        Self::Int(value.into())
+    }
+}
+
+impl From<bool> for ActionValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<Vec<String>> for ActionValue {
+    fn from(value: Vec<String>) -> Self {
+        Self::List(value)
+    }
 }
 
 /// Basic action that simply reports whether the port is open.
@@ -127,6 +353,16 @@ pub trait Action: Send + Sync + 'static {
 /// This action does **not** request any socket read, because the successful
 /// handshake itself is sufficient to confirm that the port is accepting
 /// connections.
+///
+/// When the connection fails, the engine records the port state under the same
+/// key instead:
+/// - `"refused"` — the host actively refused the connection (nothing listening)
+/// - `"reset"` — the connection was reset or aborted during the handshake
+/// - `"unreachable"` — the host or network is unreachable
+/// - `"filtered"` — the OS gave up waiting for an answer
+/// - `"timeout"` — the scanner's own timeout elapsed first
+/// - `"closed"` — any other connection error
+#[derive(Clone)]
 pub struct ActionIsPortOpen {}
 
 impl Action for ActionIsPortOpen {
@@ -140,21 +376,816 @@ impl Action for ActionIsPortOpen {
     fn execute_after_successfull_connection(
         &self,
         ctx: &ScanContext,
-        actions_results: &mut HashMap<String, String>,
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        actions_results.insert(self.name().to_string(), "open".into());
+        actions_results.insert("target".to_string(), ctx.target_addr.into());
+        actions_results.insert("port".to_string(), ctx.port.into());
+    }
+
+    fn execute_after_successfull_connection_and_read(
+        &self,
+        _ctx: &ScanContext,
+        _raw_data: &[u8],
+        _actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        // This action never performs a read, so this method is intentionally empty.
+    }
+}
+
+/// Minimal TLS 1.2 `ClientHello` record used by [`ActionTlsDetect`].
+///
+/// Offers a handful of common cipher suites, no extensions and a fixed
+/// "random" value: it only has to be valid enough for a TLS server to answer
+/// with a handshake or an alert, no session is ever established.
+#[rustfmt::skip]
+const TLS_CLIENT_HELLO: [u8; 58] = [
+    // Record header: handshake (0x16), TLS 1.0 record version, length 53
+    0x16, 0x03, 0x01, 0x00, 0x35,
+    // Handshake header: ClientHello (0x01), length 49
+    0x01, 0x00, 0x00, 0x31,
+    // client_version: TLS 1.2
+    0x03, 0x03,
+    // random (32 bytes)
+    0x73, 0x74, 0x61, 0x6c, 0x6b, 0x65, 0x72, 0x6d, 0x61, 0x70, 0x2d, 0x74, 0x6c, 0x73, 0x2d, 0x64,
+    0x65, 0x74, 0x65, 0x63, 0x74, 0x2d, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x68, 0x65, 0x6c, 0x6f,
+    // session_id length
+    0x00,
+    // cipher_suites: length 10, ECDHE-RSA-AES128/256-GCM, RSA-AES128-GCM, RSA-AES128/256-CBC
+    0x00, 0x0a, 0xc0, 0x2f, 0xc0, 0x30, 0x00, 0x9c, 0x00, 0x2f, 0x00, 0x35,
+    // compression_methods: null
+    0x01, 0x00,
+];
+
+/// TLS record content type for handshake messages (e.g. `ServerHello`).
+const TLS_CONTENT_TYPE_HANDSHAKE: u8 = 0x16;
+/// TLS record content type for alerts.
+const TLS_CONTENT_TYPE_ALERT: u8 = 0x15;
+
+/// Action that detects whether the port speaks TLS.
+///
+/// Sends a minimal TLS 1.2 `ClientHello` and inspects the first byte of the
+/// response: a handshake (`0x16`) or alert (`0x15`) record means the server
+/// understood TLS. No crypto is performed.
+///
+/// Records `"TLS": "yes"` or `"TLS": "no"`.
+#[derive(Clone)]
+pub struct ActionTlsDetect {}
+
+impl Action for ActionTlsDetect {
+    fn name(&self) -> &'static str {
+        "TLS"
+    }
+
+    fn set_read_from_successfull_connection(&self) -> bool {
+        true
+    }
+
+    fn wants_write(&self) -> Option<Vec<u8>> {
+        Some(TLS_CLIENT_HELLO.to_vec())
+    }
+
+    fn execute_after_successfull_connection(
+        &self,
+        _ctx: &ScanContext,
+        _actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        // This action always reads the response, so this method is intentionally empty.
+    }
+
+    fn execute_after_successfull_connection_and_read(
+        &self,
+        _ctx: &ScanContext,
+        raw_data: &[u8],
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        let is_tls = matches!(
+            raw_data.first(),
+            Some(&TLS_CONTENT_TYPE_HANDSHAKE) | Some(&TLS_CONTENT_TYPE_ALERT)
+        );
+        actions_results.insert(
+            self.name().to_string(),
+            if is_tls { "yes" } else { "no" }.into(),
+        );
+    }
+}
+
+/// Largest TLS server handshake read by [`ActionTlsCertCn`].
+const MAX_TLS_HANDSHAKE_LEN: usize = 16 * 1024;
+/// TLS handshake message type of the server's certificate chain.
+const TLS_HANDSHAKE_CERTIFICATE: u8 = 0x0b;
+/// DER encoding of the `commonName` attribute OID (2.5.4.3).
+const OID_COMMON_NAME: [u8; 3] = [0x55, 0x04, 0x03];
+
+/// Action that records the common name (CN) of the server's TLS certificate.
+///
+/// Opens a new connection to the target, sends the same `ClientHello` as
+/// [`ActionTlsDetect`] (without SNI) and reads the server's handshake until the
+/// `Certificate` message arrives. The subject CN of the leaf certificate is then
+/// extracted by walking the DER structure, without any TLS or X.509 library.
+///
+/// Records `"TlsCn": "<common name>"`, and nothing when no CN could be found.
+///
+/// This is best-effort:
+/// - no crypto is performed and the certificate is not validated,
+/// - only TLS 1.2 (and older) handshakes are understood, since TLS 1.3 encrypts
+///   the certificate,
+/// - servers that require SNI may answer with an alert or a default certificate,
+/// - the CN is decoded as UTF-8 (lossy) whatever its ASN.1 string type.
+#[derive(Clone)]
+pub struct ActionTlsCertCn {}
+
+impl ActionTlsCertCn {
+    /// Concatenates the payloads of the complete handshake records at the start of `raw`.
+    fn handshake_bytes(raw: &[u8]) -> Vec<u8> {
+        let mut handshake = Vec::new();
+        let mut records = raw;
+
+        while let Some((header, rest)) = records.split_first_chunk::<5>() {
+            let len = u16::from_be_bytes([header[3], header[4]]) as usize;
+            let Some((payload, rest)) = rest.split_at_checked(len) else {
+                break;
+            };
+            if header[0] != TLS_CONTENT_TYPE_HANDSHAKE {
+                break;
+            }
+            handshake.extend_from_slice(payload);
+            records = rest;
+        }
+
+        handshake
+    }
+
+    /// Returns the DER bytes of the leaf (first) certificate of the `Certificate`
+    /// message, once it has been fully received.
+    fn leaf_certificate(mut handshake: &[u8]) -> Option<&[u8]> {
+        loop {
+            let (header, rest) = handshake.split_first_chunk::<4>()?;
+            let len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+
+            if header[0] == TLS_HANDSHAKE_CERTIFICATE {
+                // certificate_list length (3 bytes), then the first certificate's length (3 bytes).
+                let (lengths, certs) = rest.split_first_chunk::<6>()?;
+                let cert_len = u32::from_be_bytes([0, lengths[3], lengths[4], lengths[5]]);
+                return certs.get(..cert_len as usize);
+            }
+
+            handshake = rest.get(len..)?;
+        }
+    }
+
+    /// Splits the first DER element off `buf`, returning its tag, its content
+    /// and the bytes that follow it.
+    fn der_next(buf: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+        let (&tag, rest) = buf.split_first()?;
+        let (&first, rest) = rest.split_first()?;
+
+        let (len, rest) = match first {
+            0x00..=0x7f => (first as usize, rest),
+            0x81..=0x84 => {
+                let (len_bytes, rest) = rest.split_at_checked((first & 0x7f) as usize)?;
+                let len = len_bytes
+                    .iter()
+                    .fold(0usize, |len, &b| (len << 8) | b as usize);
+                (len, rest)
+            }
+            // Indefinite or oversized lengths never appear in a DER certificate.
+            _ => return None,
+        };
+
+        let (content, rest) = rest.split_at_checked(len)?;
+        Some((tag, content, rest))
+    }
+
+    /// Returns the subject CN of a DER-encoded X.509 certificate.
+    fn subject_cn(cert: &[u8]) -> Option<String> {
+        let (_, certificate, _) = Self::der_next(cert)?;
+        let (_, mut tbs, _) = Self::der_next(certificate)?;
+
+        // Skip the optional `[0] version`, then serialNumber, signature, issuer and validity.
+        if tbs.first() == Some(&0xa0) {
+            tbs = Self::der_next(tbs)?.2;
+        }
+        for _ in 0..4 {
+            tbs = Self::der_next(tbs)?.2;
+        }
+        let (_, mut subject, _) = Self::der_next(tbs)?;
+
+        // Name ::= SEQUENCE OF (SET OF (SEQUENCE { type OID, value ANY }))
+        while !subject.is_empty() {
+            let (_, mut rdn, rest) = Self::der_next(subject)?;
+            subject = rest;
+
+            while !rdn.is_empty() {
+                let (_, attribute, rest) = Self::der_next(rdn)?;
+                rdn = rest;
+
+                let (_, oid, value) = Self::der_next(attribute)?;
+                if oid == OID_COMMON_NAME {
+                    let (_, cn, _) = Self::der_next(value)?;
+                    return Some(String::from_utf8_lossy(cn).into_owned());
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Sends the `ClientHello` on a new connection and returns the leaf certificate's CN.
+    async fn fetch_cn(ctx: &ScanContext<'_>) -> Option<String> {
+        let addr = format!("{}:{}", ctx.target_addr, ctx.port);
+        let mut stream = ctx.connect(&addr).await.ok()?;
+        timeout(ctx.timeout, stream.write_all(&TLS_CLIENT_HELLO))
+            .await
+            .ok()?
+            .ok()?;
+
+        let mut response = Vec::new();
+        let mut buf = [0u8; 1024];
+        while response.len() < MAX_TLS_HANDSHAKE_LEN {
+            match timeout(ctx.timeout, stream.read(&mut buf)).await {
+                Ok(Ok(0)) | Ok(Err(_)) | Err(_) => break,
+                Ok(Ok(n)) => response.extend_from_slice(&buf[..n]),
+            }
+
+            let handshake = Self::handshake_bytes(&response);
+            if let Some(cert) = Self::leaf_certificate(&handshake) {
+                return Self::subject_cn(cert);
+            }
+        }
+
+        None
+    }
+}
+
+#[async_trait]
+impl Action for ActionTlsCertCn {
+    fn name(&self) -> &'static str {
+        "TlsCn"
+    }
+
+    fn set_read_from_successfull_connection(&self) -> bool {
+        false
+    }
+
+    fn execute_after_successfull_connection(
+        &self,
+        _ctx: &ScanContext,
+        _actions_results: &mut HashMap<String, ActionValue>,
     ) {
-        actions_results.insert(self.name().to_string(), "open".to_string());
-        actions_results.insert("target".to_string(), ctx.target_addr.to_string());
-        actions_results.insert("port".to_string(), ctx.port.to_string());
+        // The handshake is read in `execute_follow_up`, a certificate rarely fits in one read.
     }
 
     fn execute_after_successfull_connection_and_read(
         &self,
         _ctx: &ScanContext,
         _raw_data: &[u8],
-        _actions_results: &mut HashMap<String, String>,
+        _actions_results: &mut HashMap<String, ActionValue>,
     ) {
         // This action never performs a read, so this method is intentionally empty.
     }
+
+    async fn execute_follow_up(
+        &self,
+        ctx: &ScanContext<'_>,
+        _raw_data: &[u8],
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        if let Some(cn) = Self::fetch_cn(ctx).await {
+            actions_results.insert(self.name().to_string(), cn.into());
+        }
+    }
+}
+
+/// Action that searches the service banner for keywords.
+///
+/// Reads the socket and looks for each of the `needles` in the received bytes
+/// (case-insensitive, plain substring search). Records
+/// `result_key -> matched needle` for the first needle found, in the order
+/// given, or `result_key -> "none"` if none matched.
+///
+/// The read waits for the banner (see [`Action::wait_for_data`]): on services
+/// that wait for the client to speak first (e.g. HTTP), each task takes the
+/// scanner's whole `timeout_ms` and records `"none"`.
+///
+/// # Example
+/// ```rust,no_run
+/// use stalkermap::actions;
+/// use stalkermap::scanner::{Action, ActionMatchBanner};
+///
+/// let todo = actions!(ActionMatchBanner {
+///     needles: vec!["nginx".to_string(), "apache".to_string()],
+///     result_key: "WebServer".to_string(),
+/// });
+/// ```
+#[derive(Clone)]
+pub struct ActionMatchBanner {
+    /// Keywords to look for.
+    pub needles: Vec<String>,
+    /// Key under which the result is stored in `actions_results`.
+    pub result_key: String,
+}
+
+impl Action for ActionMatchBanner {
+    fn name(&self) -> &'static str {
+        "MatchBanner"
+    }
+
+    fn set_read_from_successfull_connection(&self) -> bool {
+        true
+    }
+
+    fn wait_for_data(&self) -> bool {
+        true
+    }
+
+    fn execute_after_successfull_connection(
+        &self,
+        _ctx: &ScanContext,
+        _actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        // This action always reads the banner, so this method is intentionally empty.
+    }
+
+    fn execute_after_successfull_connection_and_read(
+        &self,
+        _ctx: &ScanContext,
+        raw_data: &[u8],
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        let banner = String::from_utf8_lossy(raw_data).to_lowercase();
+        let matched = self
+            .needles
+            .iter()
+            .find(|needle| banner.contains(&needle.to_lowercase()))
+            .map_or("none", |needle| needle.as_str());
+
+        actions_results.insert(self.result_key.clone(), matched.into());
+    }
+}
+
+/// Action that runs sub-actions only when an earlier action produced a given result.
+///
+/// When `actions_results[condition_key]` renders (see [`ActionValue`]'s `Display`)
+/// as `condition_value`, every action in `then` is run, in order, with the same
+/// context, data and result map as the task's own actions. Otherwise they are skipped.
+///
+/// - The condition is checked when `ActionIf` runs, so the action setting
+///   `condition_key` must come **before** it in the task.
+/// - The engine reads from the socket if any `then` action reads.
+/// - Payloads from [`wants_write`](Action::wants_write) of the `then` actions are
+///   always sent: they are written before any action runs and the condition is known.
+/// - Follow-ups and [`finalize`](Action::finalize) of the `then` actions are also
+///   forwarded, under the same condition. A follow-up exceeding its own
+///   [`timeout`](Action::timeout) records `"<name>_timeout": true`, like the engine does.
+///
+/// `ActionIf` doesn't record anything itself.
+///
+/// # Example
+/// ```rust,no_run
+/// use stalkermap::actions;
+/// use stalkermap::scanner::{Action, ActionIf, ActionIsPortOpen, ActionMatchBanner};
+///
+/// let todo = actions!(
+///     ActionIsPortOpen {},
+///     ActionIf {
+///         condition_key: "IsPortOpen".to_string(),
+///         condition_value: "open".to_string(),
+///         then: actions!(ActionMatchBanner {
+///             needles: vec!["openssh".to_string()],
+///             result_key: "Ssh".to_string(),
+///         }),
+///     },
+/// );
+/// ```
+#[derive(Clone)]
+pub struct ActionIf {
+    /// Key looked up in `actions_results`.
+    pub condition_key: String,
+    /// Value the key must have for `then` to run.
+    pub condition_value: String,
+    /// Actions run when the condition holds.
+    pub then: Vec<Box<dyn Action>>,
+}
+
+impl ActionIf {
+    /// Returns `true` if `actions_results` holds `condition_key` with `condition_value`.
+    fn matches(&self, actions_results: &HashMap<String, ActionValue>) -> bool {
+        actions_results
+            .get(&self.condition_key)
+            .is_some_and(|value| value.to_string() == self.condition_value)
+    }
+
+    /// Runs the `then` actions, each through the hook matching its read flag.
+    fn run(
+        &self,
+        ctx: &ScanContext,
+        raw_data: &[u8],
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        if !self.matches(actions_results) {
+            return;
+        }
+
+        for a in &self.then {
+            match a.set_read_from_successfull_connection() {
+                true => {
+                    a.execute_after_successfull_connection_and_read(ctx, raw_data, actions_results)
+                }
+                false => a.execute_after_successfull_connection(ctx, actions_results),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Action for ActionIf {
+    fn name(&self) -> &'static str {
+        "If"
+    }
+
+    fn set_read_from_successfull_connection(&self) -> bool {
+        self.then
+            .iter()
+            .any(|a| a.set_read_from_successfull_connection())
+    }
+
+    fn wait_for_data(&self) -> bool {
+        self.then.iter().any(|a| a.wait_for_data())
+    }
+
+    fn wants_write(&self) -> Option<Vec<u8>> {
+        let payload: Vec<u8> = self
+            .then
+            .iter()
+            .filter_map(|a| a.wants_write())
+            .flatten()
+            .collect();
+        (!payload.is_empty()).then_some(payload)
+    }
+
+    fn execute_after_successfull_connection(
+        &self,
+        ctx: &ScanContext,
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        // No sub-action reads, so none of them looks at the data.
+        self.run(ctx, &[], actions_results);
+    }
+
+    fn execute_after_successfull_connection_and_read(
+        &self,
+        ctx: &ScanContext,
+        raw_data: &[u8],
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        self.run(ctx, raw_data, actions_results);
+    }
+
+    async fn execute_follow_up(
+        &self,
+        ctx: &ScanContext<'_>,
+        raw_data: &[u8],
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        if !self.matches(actions_results) {
+            return;
+        }
+
+        for a in &self.then {
+            let follow_up = a.execute_follow_up(ctx, raw_data, actions_results);
+            let finished = match a.timeout() {
+                Some(limit) => timeout(limit, follow_up).await.is_ok(),
+                None => {
+                    follow_up.await;
+                    true
+                }
+            };
+            if !finished {
+                actions_results.insert(format!("{}_timeout", a.name()), true.into());
+            }
+        }
+    }
+
+    fn finalize(&self, ctx: &ScanContext, actions_results: &mut HashMap<String, ActionValue>) {
+        if !self.matches(actions_results) {
+            return;
+        }
+
+        for a in &self.then {
+            a.finalize(ctx, actions_results);
+        }
+    }
+}
+
+/// Largest HTTP response read by [`ActionFollowRedirects`] for each hop
+/// (and by [`ActionDirEnum`] for each path).
+const MAX_HTTP_RESPONSE_LEN: usize = 8 * 1024;
+
+/// Action that follows HTTP redirects.
+///
+/// Sends a `GET /` request (with a `Host` header naming the target) on a new
+/// connection and, while the response is a redirect (`3xx`) with a
+/// `Location` header, requests that location on a new connection, up to
+/// `max_hops` times. Relative locations are resolved against the current URL
+/// as described in RFC 3986 (section 5.2): `//host/path` keeps the current
+/// scheme, `/path` the current host and `path` or `../path` the current directory.
+///
+/// Records the URL that was last requested in `"FinalLocation"` and the number
+/// of redirects followed in `"RedirectHops"`. Only plain `http` is followed:
+/// a redirect to `https` is recorded as the final location without being requested.
+///
+/// # Example
+/// ```rust,no_run
+/// use stalkermap::actions;
+/// use stalkermap::scanner::{Action, ActionFollowRedirects};
+///
+/// let todo = actions!(ActionFollowRedirects { max_hops: 5 });
+/// ```
+#[derive(Clone)]
+pub struct ActionFollowRedirects {
+    /// Maximum number of redirects to follow.
+    pub max_hops: u8,
+}
+
+impl ActionFollowRedirects {
+    /// Returns the status code and `Location` header of an HTTP response.
+    fn parse_response(raw: &[u8]) -> (Option<u16>, Option<String>) {
+        let response = String::from_utf8_lossy(raw);
+        let mut lines = response.lines();
+
+        let status = lines.next().and_then(http_status);
+
+        let location = lines.take_while(|line| !line.is_empty()).find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("location")
+                .then(|| value.trim().to_string())
+        });
+
+        (status, location)
+    }
+
+    /// Resolves `location` against the URL `current` was requested from.
+    fn resolve(current: &UrlParser, location: &str) -> Option<UrlParser> {
+        // The fragment is never sent to the server.
+        let location = location.split('#').next().unwrap_or_default();
+
+        if has_scheme(location) {
+            return location.parse().ok();
+        }
+        if location.starts_with("//") {
+            return format!("{}:{}", current.scheme, location).parse().ok();
+        }
+
+        let (path, query) = match location.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (location, None),
+        };
+        let base = current
+            .subdirectory
+            .split('?')
+            .next()
+            .filter(|base| !base.is_empty())
+            .unwrap_or("/");
+        let path = if path.starts_with('/') {
+            remove_dot_segments(path)
+        } else if path.is_empty() {
+            base.to_string()
+        } else {
+            let dir = &base[..base.rfind('/').map_or(0, |i| i + 1)];
+            remove_dot_segments(&format!("{}{}", dir, path))
+        };
+
+        let mut url = format!(
+            "{}://{}:{}{}",
+            current.scheme,
+            current.normalized_target(),
+            current.effective_port(),
+            path
+        );
+        if let Some(query) = query {
+            url.push('?');
+            url.push_str(query);
+        }
+        url.parse().ok()
+    }
+}
+
+/// Returns `true` if `reference` starts with a URI scheme (`http:`, `https:`, ...).
+fn has_scheme(reference: &str) -> bool {
+    reference.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+/// Removes the `.` and `..` segments of an absolute path (RFC 3986, section 5.2.4).
+fn remove_dot_segments(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/').skip(1) {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let mut resolved = format!("/{}", segments.join("/"));
+    // A trailing `.` or `..` still names a directory.
+    if (path.ends_with("/.") || path.ends_with("/..")) && !resolved.ends_with('/') {
+        resolved.push('/');
+    }
+    resolved
+}
+
+/// Returns the status code of an HTTP status line (`HTTP/1.1 200 OK` -> `200`).
+fn http_status(status_line: &str) -> Option<u16> {
+    status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+}
+
+/// Requests `url` on a new connection and returns the response bytes.
+async fn http_get(ctx: &ScanContext<'_>, url: &UrlParser) -> Option<Vec<u8>> {
+    let host = url.normalized_target();
+    let addr = format!("{}:{}", host, url.effective_port());
+    let path = if url.subdirectory.is_empty() {
+        "/"
+    } else {
+        url.subdirectory.as_str()
+    };
+
+    let mut stream = ctx.connect(&addr).await.ok()?;
+    let request = format!(
+        "GET {} HTTP/1.0\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        path, host
+    );
+    timeout(ctx.timeout, stream.write_all(request.as_bytes()))
+        .await
+        .ok()?
+        .ok()?;
+
+    let mut response = Vec::new();
+    let mut buf = [0u8; 1024];
+    while response.len() < MAX_HTTP_RESPONSE_LEN {
+        match timeout(ctx.timeout, stream.read(&mut buf)).await {
+            Ok(Ok(0)) | Ok(Err(_)) | Err(_) => break,
+            Ok(Ok(n)) => response.extend_from_slice(&buf[..n]),
+        }
+    }
+
+    Some(response)
+}
+
+#[async_trait]
+impl Action for ActionFollowRedirects {
+    fn name(&self) -> &'static str {
+        "FollowRedirects"
+    }
+
+    fn set_read_from_successfull_connection(&self) -> bool {
+        false
+    }
+
+    fn execute_after_successfull_connection(
+        &self,
+        _ctx: &ScanContext,
+        _actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        // Every request is sent in `execute_follow_up`, so `http_get` builds its `Host` header.
+    }
+
+    fn execute_after_successfull_connection_and_read(
+        &self,
+        _ctx: &ScanContext,
+        _raw_data: &[u8],
+        _actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        // This action never performs a read, so this method is intentionally empty.
+    }
+
+    async fn execute_follow_up(
+        &self,
+        ctx: &ScanContext<'_>,
+        _raw_data: &[u8],
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        let Ok(mut current) = UrlParser::from_parts(Scheme::Http, ctx.target_addr, ctx.port, "/")
+        else {
+            return;
+        };
+        let mut response = http_get(ctx, &current).await.unwrap_or_default();
+        let mut hops: u8 = 0;
+
+        while hops < self.max_hops {
+            let (Some(300..=399), Some(location)) = Self::parse_response(&response) else {
+                break;
+            };
+            let Some(next) = Self::resolve(&current, &location) else {
+                break;
+            };
+
+            hops += 1;
+            current = next;
+            if current.scheme != Scheme::Http {
+                break;
+            }
+            match http_get(ctx, &current).await {
+                Some(next_response) => response = next_response,
+                None => break,
+            }
+        }
+
+        actions_results.insert("FinalLocation".to_string(), current.full_url.into());
+        actions_results.insert("RedirectHops".to_string(), i64::from(hops).into());
+    }
+}
+
+/// Action that looks for existing paths on an HTTP server.
+///
+/// Requests every path of `paths` with `GET <path> HTTP/1.0`, one connection
+/// per path, and records those answered with any status other than `404` in
+/// `"FoundPaths"` (as an [`ActionValue::List`], in wordlist order). Paths that
+/// get no valid HTTP response (connection refused, timeout, ...) are not found.
+///
+/// Paths are sent as given, so they should already be percent-encoded; a
+/// missing leading `/` is added.
+///
+/// # Example
+/// ```rust,no_run
+/// use stalkermap::actions;
+/// use stalkermap::scanner::{Action, ActionDirEnum};
+///
+/// let todo = actions!(ActionDirEnum {
+///     paths: vec!["/admin".to_string(), "/backup".to_string()],
+/// });
+/// ```
+#[derive(Clone)]
+pub struct ActionDirEnum {
+    /// Wordlist of paths to request.
+    pub paths: Vec<String>,
+}
+
+#[async_trait]
+impl Action for ActionDirEnum {
+    fn name(&self) -> &'static str {
+        "DirEnum"
+    }
+
+    fn set_read_from_successfull_connection(&self) -> bool {
+        false
+    }
+
+    fn execute_after_successfull_connection(
+        &self,
+        _ctx: &ScanContext,
+        _actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        // Paths are requested in `execute_follow_up`, which can open new connections.
+    }
+
+    fn execute_after_successfull_connection_and_read(
+        &self,
+        _ctx: &ScanContext,
+        _raw_data: &[u8],
+        _actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+    }
+
+    async fn execute_follow_up(
+        &self,
+        ctx: &ScanContext<'_>,
+        _raw_data: &[u8],
+        actions_results: &mut HashMap<String, ActionValue>,
+    ) {
+        let mut found = Vec::new();
+
+        for path in &self.paths {
+            let Ok(url) = UrlParser::from_parts(Scheme::Http, ctx.target_addr, ctx.port, path)
+            else {
+                continue;
+            };
+            let Some(response) = http_get(ctx, &url).await else {
+                continue;
+            };
+
+            let status = String::from_utf8_lossy(&response)
+                .lines()
+                .next()
+                .and_then(http_status);
+            if matches!(status, Some(code) if code != 404) {
+                found.push(url.subdirectory);
+            }
+        }
+
+        actions_results.insert("FoundPaths".to_string(), found.into());
+    }
 }
 
 /// Contextual information supplied to each action during execution.
@@ -162,7 +1193,11 @@ impl Action for ActionIsPortOpen {
 /// The `ScanContext` describes:
 /// - the target IP/hostname,
 /// - the target port,
-/// - the internal Tokio task ID responsible for this scan.
+/// - the internal Tokio task ID responsible for this scan,
+/// - the sequence number assigned to the task when it was enqueued,
+/// - the scanner's network timeout,
+/// - the local address of the task's connection,
+/// - the scanner's [`Connector`], used by [`ScanContext::connect`].
 ///
 /// This allows actions to include metadata in their results, correlate logs,
 pub struct ScanContext<'a> {
@@ -172,4 +1207,32 @@ pub struct ScanContext<'a> {
     pub port: u16,
     /// Identifier of the Tokio task handling this connection attempt.
     pub task_id: tokio::task::Id,
+    /// Sequence number assigned when the task was enqueued (starts at `1`).
+    ///
+    /// Unlike `task_id`, it is known before the task runs and is also carried
+    /// into the log record, so results can be correlated with a specific enqueue.
+    pub task_seq: u64,
+    /// Timeout for network operations, from [`ScannerOptions::timeout_ms`](super::ScannerOptions::timeout_ms).
+    pub timeout: Duration,
+    /// Local address (source interface and port) of the task's connection.
+    ///
+    /// `None` if the OS (or a custom [`Connector`]) couldn't
+    /// report it. Useful for auditing which
+    /// interface a scan went out of, or for debugging NAT.
+    pub local_addr: Option<SocketAddr>,
+    /// Connector of the scanner (see [`Scanner::with_connector`](super::Scanner::with_connector)),
+    /// used by [`ScanContext::connect`].
+    pub connector: Arc<dyn Connector>,
+}
+
+impl ScanContext<'_> {
+    /// Opens a new connection to `addr` (e.g. `"127.0.0.1:80"`), waiting at
+    /// most [`timeout`](Self::timeout).
+    ///
+    /// Meant for [`Action::execute_follow_up`], when an action needs to reach
+    /// another host or port than the task's connection. The connection is
+    /// opened by the scanner's [`connector`](Self::connector), like the task's own.
+    pub async fn connect(&self, addr: &str) -> std::io::Result<Box<dyn AsyncConn>> {
+        self.connector.connect(addr, self.timeout).await
+    }
 }
@@ -1,7 +1,10 @@
 use parking_lot::Mutex;
 use std::mem::MaybeUninit;
 
-pub(super) type Buffer = Box<[MaybeUninit<u8>; 512]>;
+/// Size of the read buffers, i.e. the most bytes a single read can return.
+pub(super) const BUFFER_LEN: usize = 512;
+
+pub(super) type Buffer = Box<[MaybeUninit<u8>; BUFFER_LEN]>;
 
 pub(super) trait BufferExt {
     unsafe fn as_bytes(&self, len: usize) -> &[u8];
@@ -33,7 +36,7 @@ impl BufferPool {
         self.pool
             .lock()
             .pop()
-            .unwrap_or_else(|| Box::new([MaybeUninit::<u8>::uninit(); 512]) as Buffer)
+            .unwrap_or_else(|| Box::new([MaybeUninit::<u8>::uninit(); BUFFER_LEN]) as Buffer)
     }
 
     pub(super) fn put(&self, buf: Buffer) {
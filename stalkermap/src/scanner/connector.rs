@@ -0,0 +1,80 @@
+//! Pluggable transport used by the scanning engine to open task connections.
+//!
+//! For every task, the engine asks its [`Connector`] for a connection to
+//! `"<target>:<port>"` and then only talks to it through [`AsyncConn`]
+//! (`AsyncRead + AsyncWrite`). The default [`TcpConnector`] opens real TCP
+//! connections; a custom connector can be installed with
+//! [`Scanner::with_connector`](super::Scanner::with_connector), e.g. to route
+//! scans through a proxy or to test the engine without binding real ports.
+//!
+//! Connections opened by actions themselves (see [`ScanContext::connect`](super::ScanContext::connect))
+//! go through the same connector.
+//!
+//! # Example
+//! ```rust,no_run
+//! use async_trait::async_trait;
+//! use stalkermap::scanner::{AsyncConn, Connector};
+//! use std::time::Duration;
+//! use tokio::io::AsyncWriteExt;
+//!
+//! /// Every address "connects" to a server that sends an SSH banner.
+//! struct MockConnector;
+//!
+//! #[async_trait]
+//! impl Connector for MockConnector {
+//!     async fn connect(&self, _addr: &str, _timeout: Duration) -> std::io::Result<Box<dyn AsyncConn>> {
+//!         let (client, mut server) = tokio::io::duplex(64);
+//!         server.write_all(b"SSH-2.0-OpenSSH_9.6\r\n").await?;
+//!         Ok(Box::new(client))
+//!     }
+//! }
+//! ```
+
+use super::*;
+use std::net::SocketAddr;
+use tokio::io::{AsyncRead, AsyncWrite, DuplexStream};
+
+/// A connection returned by a [`Connector`].
+///
+/// Implemented for [`TcpStream`] and for Tokio's in-memory [`DuplexStream`].
+pub trait AsyncConn: AsyncRead + AsyncWrite + Send + Unpin {
+    /// Returns the local address of the connection, exposed to actions as
+    /// [`ScanContext::local_addr`].
+    ///
+    /// The default implementation returns an `Unsupported` error, for
+    /// connections that have no such address.
+    fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+}
+
+impl AsyncConn for TcpStream {
+    fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        TcpStream::local_addr(self)
+    }
+}
+
+impl AsyncConn for DuplexStream {}
+
+/// Opens the connection of every task, see the [module docs](self).
+#[async_trait]
+pub trait Connector: Send + Sync + 'static {
+    /// Opens a connection to `addr` (e.g. `"127.0.0.1:80"`), waiting at most `timeout`.
+    ///
+    /// # Errors
+    /// The error is mapped to the port state recorded under `IsPortOpen` (see
+    /// [`ConnectError::state`]). A [`ConnectError::Timeout`] converted into an
+    /// [`std::io::Error`] is reported as `"timeout"`.
+    async fn connect(&self, addr: &str, timeout: Duration) -> std::io::Result<Box<dyn AsyncConn>>;
+}
+
+/// Default [`Connector`], opening TCP connections with [`connect_with_timeout`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TcpConnector;
+
+#[async_trait]
+impl Connector for TcpConnector {
+    async fn connect(&self, addr: &str, timeout: Duration) -> std::io::Result<Box<dyn AsyncConn>> {
+        Ok(Box::new(connect_with_timeout(addr, timeout).await?))
+    }
+}
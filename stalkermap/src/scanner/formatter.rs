@@ -4,9 +4,12 @@
 //! action results into a concrete output type. This allows users to plug in
 //! custom formatting logic or choose between the built-in implementations:
 //!
-//! - [`RawFormatter`] — returns raw bytes (`Vec<u8>`)
+//! - [`RawFormatter`] — returns the read bytes (`Vec<u8>`), with control bytes escaped
 //! - [`StructuredFormatter`] — returns strongly-typed [`LogRecord`] structures
 //! - [`JsonFormatter`] — returns JSON strings
+//! - [`NmapGreppableFormatter`] — returns Nmap "greppable" (`-oG`) lines
+//! - [`ClosureFormatter`] — returns the `String` built by a closure, see
+//!   [`ClosureFormatter::from_fn`]
 //!
 //! Formatters also define an *idle output* via [`LogFormatter::idle_output`],
 //! which is used internally when the scanner enters an idle state (no tasks
@@ -19,8 +22,10 @@ use super::*;
 ///
 /// A `LogFormatter` controls how:
 ///
-/// - raw network bytes (`raw_data`)
-/// - action results (`HashMap<Actions, String>`)
+/// - the task sequence number (`task_seq`, see [`ScanContext::task_seq`])
+/// - raw network bytes (`raw_data`), i.e. everything the task's single read
+///   returned, the same bytes every reading action saw
+/// - action results (`HashMap<String, ActionValue>`)
 ///
 /// are converted into a user-facing output type.
 ///
@@ -42,6 +47,19 @@ use super::*;
 /// Users typically do **not** construct idle outputs manually; the scanner emits
 /// them automatically.
 ///
+/// ### File sinks
+///
+/// When a file sink is configured, every output is written to the file as the
+/// bytes returned by [`sink_line`](Self::sink_line). The built-in formatters
+/// write their raw bytes, JSON or greppable line; custom formatters fall back
+/// to `Debug` unless they override it.
+///
+/// ### Construction
+///
+/// [`Scanner::new`] builds the formatter through [`Default`], which all built-in
+/// formatters implement. Formatters that carry configuration and have no
+/// sensible default can be passed in with [`Scanner::new_with_formatter`].
+///
 /// # Examples
 ///
 /// Using a built-in formatter:
@@ -78,18 +96,38 @@ use super::*;
 pub trait LogFormatter: Send + Sync + 'static {
     type Output: Send + Sync + 'static + Clone + Debug + PartialEq;
 
-    fn format(&self, actions_results: HashMap<String, String>, raw_data: &[u8]) -> Self::Output;
+    fn format(
+        &self,
+        task_seq: u64,
+        actions_results: HashMap<String, ActionValue>,
+        raw_data: &[u8],
+    ) -> Self::Output;
 
     fn idle_output(&self) -> Self::Output;
 
     fn is_idle_signal(&self, output: &Self::Output) -> bool {
         *output == self.idle_output()
     }
+
+    /// Encodes an output as one line of a file sink (see [`Scanner::with_file_sink`]),
+    /// without the trailing newline.
+    ///
+    /// Defaults to the output's `Debug` representation.
+    fn sink_line(&self, output: &Self::Output) -> Vec<u8> {
+        format!("{:?}", output).into_bytes()
+    }
 }
 
 /// Formatter that returns scan results as raw bytes (`Vec<u8>`).
 ///
-/// Useful for low-level or binary protocols.
+/// Printable ASCII and newlines are kept as is; every other byte (ANSI escape
+/// sequences, `\r`, binary data, ...) is rendered as `\xNN`, so banners can't
+/// mangle a terminal. For example the bytes `[0x1b, 0x5b, 0x41]` become
+/// `\x1b[A`. A literal `\` is doubled (`\\`), so it can't be confused with an
+/// escape.
+///
+/// In a file sink (see [`LogFormatter::sink_line`]) newlines are written as
+/// `\n` too, so a banner can't inject fake log lines.
 pub struct RawFormatter;
 /// Formatter that produces strongly-typed [`LogRecord`] values.
 ///
@@ -111,8 +149,13 @@ impl LogFormatter for RawFormatter {
     type Output = Vec<u8>;
 
     /// Formats action results and raw network data into the associated output type.
-    fn format(&self, _actions_results: HashMap<String, String>, raw_data: &[u8]) -> Self::Output {
-        raw_data.to_vec()
+    fn format(
+        &self,
+        _task_seq: u64,
+        _actions_results: HashMap<String, ActionValue>,
+        raw_data: &[u8],
+    ) -> Self::Output {
+        escape_non_printable(raw_data)
     }
 
     /// Returns the formatter's representation of an idle event.
@@ -122,6 +165,35 @@ impl LogFormatter for RawFormatter {
     fn idle_output(&self) -> Self::Output {
         b"___IDLE___".to_vec()
     }
+
+    fn sink_line(&self, output: &Self::Output) -> Vec<u8> {
+        let mut line = Vec::with_capacity(output.len());
+        for &byte in output {
+            match byte {
+                b'\n' => line.extend_from_slice(b"\\n"),
+                _ => line.push(byte),
+            }
+        }
+        line
+    }
+}
+
+/// Keeps printable ASCII and `\n`, doubles `\` and renders every other byte as `\xNN`.
+fn escape_non_printable(raw_data: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut escaped = Vec::with_capacity(raw_data.len());
+    for &byte in raw_data {
+        match byte {
+            b'\\' => escaped.extend_from_slice(b"\\\\"),
+            b'\n' | 0x20..=0x7e => escaped.push(byte),
+            // Writing to a `Vec` never fails.
+            _ => {
+                let _ = write!(escaped, "\\x{:02x}", byte);
+            }
+        }
+    }
+    escaped
 }
 
 impl Default for RawFormatter {
@@ -133,8 +205,14 @@ impl Default for RawFormatter {
 impl LogFormatter for StructuredFormatter {
     type Output = LogRecord;
 
-    fn format(&self, actions_results: HashMap<String, String>, raw_data: &[u8]) -> Self::Output {
+    fn format(
+        &self,
+        task_seq: u64,
+        actions_results: HashMap<String, ActionValue>,
+        raw_data: &[u8],
+    ) -> Self::Output {
         LogRecord {
+            task_seq,
             header_response: LogHeader { actions_results },
             data: String::from_utf8_lossy(raw_data).into_owned(),
         }
@@ -142,12 +220,17 @@ impl LogFormatter for StructuredFormatter {
 
     fn idle_output(&self) -> Self::Output {
         LogRecord {
+            task_seq: 0,
             header_response: LogHeader {
                 actions_results: HashMap::new(),
             },
             data: "idle".to_string(),
         }
     }
+
+    fn sink_line(&self, output: &Self::Output) -> Vec<u8> {
+        serde_json::to_vec(output).unwrap()
+    }
 }
 
 impl Default for StructuredFormatter {
@@ -159,8 +242,14 @@ impl Default for StructuredFormatter {
 impl LogFormatter for JsonFormatter {
     type Output = String;
 
-    fn format(&self, actions_results: HashMap<String, String>, raw_data: &[u8]) -> Self::Output {
+    fn format(
+        &self,
+        task_seq: u64,
+        actions_results: HashMap<String, ActionValue>,
+        raw_data: &[u8],
+    ) -> Self::Output {
         serde_json::to_string(&LogRecord {
+            task_seq,
             header_response: LogHeader { actions_results },
             data: String::from_utf8_lossy(raw_data).into_owned(),
         })
@@ -170,6 +259,10 @@ impl LogFormatter for JsonFormatter {
     fn idle_output(&self) -> Self::Output {
         serde_json::json!({"type": "idle"}).to_string()
     }
+
+    fn sink_line(&self, output: &Self::Output) -> Vec<u8> {
+        output.clone().into_bytes()
+    }
 }
 
 impl Default for JsonFormatter {
@@ -177,3 +270,156 @@ impl Default for JsonFormatter {
         Self
     }
 }
+
+/// Formatter that produces lines in Nmap's greppable (`-oG`) format.
+///
+/// ```text
+/// Host: 127.0.0.1 ()<TAB>Ports: 80/open/tcp//http///
+/// ```
+///
+/// Like Nmap, the host and ports fields are separated by a tab.
+///
+/// The target, port and port state are taken from the action results
+/// (`"target"`, `"port"` and `"IsPortOpen"`, as written by
+/// [`ActionIsPortOpen`] and by the engine on connection failures).
+/// A `"timeout"` or `"unreachable"` state is reported as `filtered`, like
+/// Nmap does for unanswered probes, and `"refused"` / `"reset"` as `closed`.
+/// The service column is filled for well-known ports only.
+///
+/// The idle event is emitted as a comment line (`# idle`), which greppable
+/// consumers ignore.
+pub struct NmapGreppableFormatter;
+
+impl NmapGreppableFormatter {
+    /// Returns the conventional service name for well-known ports.
+    fn service_name(port: &str) -> &'static str {
+        match port {
+            "21" => "ftp",
+            "22" => "ssh",
+            "23" => "telnet",
+            "25" => "smtp",
+            "53" => "domain",
+            "80" => "http",
+            "110" => "pop3",
+            "143" => "imap",
+            "443" => "https",
+            "3306" => "mysql",
+            "5432" => "postgresql",
+            "8080" => "http-proxy",
+            _ => "",
+        }
+    }
+}
+
+impl LogFormatter for NmapGreppableFormatter {
+    type Output = String;
+
+    fn format(
+        &self,
+        _task_seq: u64,
+        actions_results: HashMap<String, ActionValue>,
+        _raw_data: &[u8],
+    ) -> Self::Output {
+        let field = |key: &str| {
+            actions_results
+                .get(key)
+                .map_or_else(String::new, |v| v.to_string())
+        };
+
+        let port = field("port");
+        let state = field(ActionIsPortOpen {}.name());
+        let state = match state.as_str() {
+            "timeout" | "unreachable" => "filtered",
+            "refused" | "reset" => "closed",
+            "" => "unknown",
+            state => state,
+        };
+
+        format!(
+            "Host: {} ()\tPorts: {}/{}/tcp//{}///",
+            field("target"),
+            port,
+            state,
+            Self::service_name(&port)
+        )
+    }
+
+    fn idle_output(&self) -> Self::Output {
+        "# idle".to_string()
+    }
+
+    fn sink_line(&self, output: &Self::Output) -> Vec<u8> {
+        output.clone().into_bytes()
+    }
+}
+
+impl Default for NmapGreppableFormatter {
+    fn default() -> Self {
+        Self
+    }
+}
+
+/// Formatter that builds each output with a closure, for one-off formats.
+///
+/// The closure receives the action results and the raw network bytes; the
+/// task sequence number is not passed. Idle events are emitted as the `idle`
+/// string given at construction. Usually created with [`ClosureFormatter::from_fn`].
+///
+/// # Example
+/// ```rust,no_run
+/// use stalkermap::scanner::{ClosureFormatter, Scanner};
+///
+/// let formatter = ClosureFormatter::from_fn(
+///     |results, _raw| format!("port {}", results["port"]),
+///     "idle",
+/// );
+/// let scanner = Scanner::new_with_formatter(formatter).build();
+/// ```
+pub struct ClosureFormatter<F> {
+    f: F,
+    idle: String,
+}
+
+impl<F> ClosureFormatter<F>
+where
+    F: Fn(&HashMap<String, ActionValue>, &[u8]) -> String + Send + Sync + 'static,
+{
+    /// Wraps `f`, emitting `idle` for idle events.
+    pub fn new(f: F, idle: impl Into<String>) -> Self {
+        Self {
+            f,
+            idle: idle.into(),
+        }
+    }
+
+    /// Creates a formatter from a closure and an idle string.
+    ///
+    /// Same as [`ClosureFormatter::new`], in the spirit of [`std::iter::from_fn`].
+    pub fn from_fn(f: F, idle: impl Into<String>) -> Self {
+        Self::new(f, idle)
+    }
+}
+
+impl<F> LogFormatter for ClosureFormatter<F>
+where
+    F: Fn(&HashMap<String, ActionValue>, &[u8]) -> String + Send + Sync + 'static,
+{
+    type Output = String;
+
+    fn format(
+        &self,
+        _task_seq: u64,
+        actions_results: HashMap<String, ActionValue>,
+        raw_data: &[u8],
+    ) -> Self::Output {
+        (self.f)(&actions_results, raw_data)
+    }
+
+    fn idle_output(&self) -> Self::Output {
+        self.idle.clone()
+    }
+
+    fn sink_line(&self, output: &Self::Output) -> Vec<u8> {
+        output.clone().into_bytes()
+    }
+}
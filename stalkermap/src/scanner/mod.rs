@@ -46,10 +46,14 @@
 //!                                v
 //! +------------------------------------------------------+
 //! |                     Stalker API                      |
-//! |   - add_task, add_multiple_tasks                     |
+//! |   - add_task, add_multiple_tasks, feed               |
+//! |   - add_multiple_tasks_dedup, drain_pending          |
 //! |   - execute_tasks                                    |
 //! |   - get_logs_stream                                  |
-//! |   - await_idle, shutdown_graceful                    |
+//! |   - await_idle, shutdown_graceful, shutdown_now      |
+//! |   - await_completed                                  |
+//! |   - pause, resume                                    |
+//! |   - describe                                         |
 //! +------------------------------+-----------------------+
 //!                                |
 //!                                v
@@ -64,6 +68,7 @@
 //! | - task queue        - log broadcast sender           |
 //! | - buffer pool       - formatter                      |
 //! | - counters          - Notify (idle)                  |
+//! | - connector         - TCP by default                 |
 //! +------------------------------------------------------+
 //! ```
 //!
@@ -73,8 +78,11 @@
 //!
 //! Each executed task invokes its associated [`Action`]'s,
 //! executed in order:
-//! - After a successful TCP connection
+//! - After a successful TCP connection (opened by the scanner's [`Connector`])
 //! - With optional non-blocking read depending on the action's configuration
+//! - Followed by an async [`execute_follow_up`](Action::execute_follow_up) pass,
+//!   where actions may open further connections
+//! - Followed by a [`finalize`](Action::finalize) pass once every action has run
 //!
 //! Action results are collected into a shared map and included in every log record.
 //!
@@ -103,8 +111,12 @@
 //! - the target host
 //! - the target port
 //! - the Tokio task ID handling the connection
+//! - the task's sequence number (`task_seq`), assigned on enqueue and also
+//!   carried into each [`LogRecord`]
+//! - the scanner's network timeout, also used by [`ScanContext::connect`]
 //!
-//! All actions share a mutable `actions_results: HashMap<String, String>`,
+//! All actions share a mutable `actions_results: HashMap<String, ActionValue>`
+//! (see [`ActionValue`] for the typed result values),
 //! allowing actions to:
 //! - contribute results
 //! - detect results from previous actions in the same task
@@ -125,6 +137,9 @@
 //! This allows writing event loops that pause when the scanner is idle and
 //! automatically resume when new tasks arrive.
 //!
+//! Logs can also be appended to a file with [`Scanner::with_file_sink`], in
+//! addition to any stream.
+//!
 //! ---
 //!
 //! ## Idle Detection
@@ -203,6 +218,12 @@
 //!
 //! This makes shutdown and idle detection **fully deterministic**.
 //!
+//! ### Forceful shutdown
+//!
+//! `shutdown_now()` doesn't wait for idle: it cancels running and queued tasks
+//! (they stop at their next checkpoint without logging) and closes the log
+//! channel immediately, e.g. for Ctrl-C handling.
+//!
 //!
 //! ---
 //!
@@ -287,6 +308,8 @@
 //! - Log formatting is fully user-defined via [`LogFormatter`].  
 //! - Shutdown is **cooperative**: running tasks are allowed to finish.  
 //! - `await_idle()` is deterministic and never races tasks due to internal counters.  
+//! - With the `tracing` feature, connect results (`debug`/`warn`) and graceful shutdown
+//!   (`debug`) are reported as `tracing` events under the `stalkermap::scanner` target.  
 //!
 //! The design emphasizes:
 //!
@@ -298,34 +321,60 @@ use async_trait::async_trait;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, VecDeque},
-    fmt::Debug,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{Debug, Display},
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    net::IpAddr,
+    ops::RangeInclusive,
+    path::PathBuf,
     sync::{
         Arc,
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
     },
     time::Duration,
 };
 use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
     net::TcpStream,
     sync::{
         Notify, Semaphore,
-        broadcast::{self},
+        broadcast::{self, error::RecvError},
     },
-    task::yield_now,
+    task::{JoinHandle, yield_now},
     time::timeout,
 };
 use tokio_stream::{StreamExt, wrappers::BroadcastStream};
 use tokio_util::sync::CancellationToken;
 
 pub mod actions;
-pub use actions::{Action, ActionIsPortOpen, ScanContext};
+pub use actions::{
+    Action, ActionDirEnum, ActionFollowRedirects, ActionIf, ActionIsPortOpen, ActionMatchBanner,
+    ActionTlsCertCn, ActionTlsDetect, ActionValue, CloneAction, ScanContext,
+};
 pub mod formatter;
-pub use formatter::{JsonFormatter, LogFormatter, RawFormatter, StructuredFormatter};
+pub use formatter::{
+    ClosureFormatter, JsonFormatter, LogFormatter, NmapGreppableFormatter, RawFormatter,
+    StructuredFormatter,
+};
+pub mod connector;
+pub use connector::{AsyncConn, Connector, TcpConnector};
 mod buffer_pool;
+
+/// Emits a `tracing` event when the `tracing` feature is enabled, does nothing otherwise.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($arg)+);
+    };
+}
+
 use crate::{
-    scanner::buffer_pool::{Buffer, BufferExt, BufferPool},
-    utils::{TargetType, UrlParser},
+    scanner::buffer_pool::{BUFFER_LEN, Buffer, BufferExt, BufferPool},
+    utils::{
+        Scheme, TargetType, UrlParser, UrlParserErrors,
+        cidr::{CidrError, expand_ipv4, expand_ipv6},
+    },
 };
 
 /// High-level asynchronous interface for the scanning engine.
@@ -358,17 +407,77 @@ pub trait Stalker: Send + Sync + 'static {
     /// Adds multiple pre-built tasks to the scanning queue.
     fn add_multiple_tasks(&self, tasks: Vec<Task>);
 
+    /// Same as [`add_multiple_tasks`](Stalker::add_multiple_tasks), but skips
+    /// duplicated targets, and returns the number of tasks actually queued.
+    ///
+    /// Two tasks are duplicates when they have the same `target` and
+    /// [`effective_port`](UrlParser::effective_port), whatever their actions.
+    /// Only the given tasks and the tasks still waiting in the queue are
+    /// compared: a target whose task already started (or finished) is queued again.
+    fn add_multiple_tasks_dedup(&self, tasks: Vec<Task>) -> usize;
+
+    /// Enqueues the tasks of `source` lazily, as the scanner frees capacity.
+    ///
+    /// Tasks are pulled from the source only while fewer than `batch_size` tasks
+    /// are waiting in the queue, so huge scans (e.g. every port of a host, see
+    /// [`PortRangeSource`]) never materialize all their tasks at once. The scanner
+    /// is not idle until the source is exhausted. Once the scanner shuts down,
+    /// the source is dropped without being pulled any further.
+    ///
+    /// The default implementation enqueues every task of the source right away.
+    ///
+    /// # Panics
+    /// The scanner built by [`Scanner::build`] pulls from the source on a spawned
+    /// Tokio task, so it panics if called outside a Tokio runtime.
+    fn feed(&self, mut source: Box<dyn TaskSource>) {
+        while let Some(task) = source.next_task() {
+            self.add_multiple_tasks(vec![task]);
+        }
+    }
+
     /// Returns the total number of tasks on the `TaskPool`.
     fn total_tasks(&self) -> usize;
     /// Returns the total number of pending tasks.
     fn total_tasks_on_queue(&self) -> usize;
 
+    /// Removes every queued (not yet started) task and returns them in queue order.
+    ///
+    /// Running tasks are not affected. The drained tasks keep their
+    /// `task_seq`; they can be re-prioritized and enqueued again with
+    /// [`add_multiple_tasks`](Stalker::add_multiple_tasks), which assigns new ones.
+    /// Tasks a [`feed`](Stalker::feed) source hasn't produced yet stay in the source.
+    fn drain_pending(&self) -> Vec<Task>;
+
+    /// Returns `true` if no task is pending or running.
+    ///
+    /// The idle event from [`Stalker::await_idle`] is broadcast only once, so a
+    /// subscriber that joins late can use this to check the current state instead.
+    fn is_idle(&self) -> bool {
+        self.total_tasks_on_queue() == 0
+    }
+
     /// Executes all tasks currently in the queue asynchronously.
     ///
     /// Tasks are executed concurrently up to the `batch_size` limit.
     /// Each task runs at most once, and log events are streamed via the configured formatter.
     fn execute_tasks(&self);
 
+    /// Stops starting queued tasks until [`resume`](Stalker::resume) is called.
+    ///
+    /// Nothing is cancelled: running tasks complete and new tasks can still be
+    /// added, they just stay queued. Useful to back off, e.g. when a firewall
+    /// starts blocking. While paused with tasks queued the scanner is not idle,
+    /// so [`await_idle`](Stalker::await_idle) and
+    /// [`shutdown_graceful`](Stalker::shutdown_graceful) wait for the resume.
+    /// [`shutdown_now`](Stalker::shutdown_now) still discards the queue.
+    fn pause(&self);
+
+    /// Starts executing queued tasks again after [`pause`](Stalker::pause).
+    fn resume(&self);
+
+    /// Returns `true` between [`pause`](Stalker::pause) and [`resume`](Stalker::resume).
+    fn is_paused(&self) -> bool;
+
     /// Returns a stream of log events produced during task execution.
     async fn get_logs_stream(&self) -> Option<TaskAwareStream<<Self::F as LogFormatter>::Output>>;
 
@@ -377,21 +486,117 @@ pub trait Stalker: Send + Sync + 'static {
     /// All running tasks will continue until completion, but no new tasks will be accepted.
     async fn shutdown_graceful(&self);
 
+    /// Shuts the scanner down immediately, without waiting for idle.
+    ///
+    /// Running tasks are cancelled: they stop at their next cancellation checkpoint
+    /// (before connecting, after connecting, after the read and during
+    /// [`Action::execute_follow_up`]) without sending a log. Queued tasks are
+    /// discarded the same way. The log channel is closed right away, so every
+    /// stream ends. Meant for e.g. Ctrl-C handling.
+    async fn shutdown_now(&self);
+
     /// Signals the scanner to idle.
     ///
     /// All running tasks will continue until completion, new tasks will be accepted.
     async fn await_idle(&self);
+
+    /// Waits until at least `n` tasks have completed since the scanner was built.
+    ///
+    /// Unlike [`await_idle`](Stalker::await_idle), other tasks may still be
+    /// pending or running when this returns, which is useful for staged scans.
+    /// Cancelled tasks count as completed. Never returns if fewer than `n`
+    /// tasks are ever executed.
+    async fn await_completed(&self, n: usize);
+
+    /// Returns a snapshot of the scanner's configuration and live counters.
+    ///
+    /// Useful for REPL-style tools or status lines. The values are read
+    /// independently, so they may be slightly out of sync while tasks run.
+    fn describe(&self) -> ScannerStatus;
+
+    /// Returns the number of log streams currently subscribed.
+    ///
+    /// A file sink (see [`Scanner::with_file_sink`]) counts as a subscriber.
+    /// While this is `0`, task results are not formatted at all and are
+    /// counted in [`ScanMetrics::dropped_logs`]. Returns `0` after shutdown.
+    fn subscriber_count(&self) -> usize;
+
+    /// Returns a lock-free snapshot of the scanner's counters.
+    ///
+    /// Cheaper than [`describe`](Stalker::describe): it only loads atomics and
+    /// never allocates, so it can be polled at high frequency, e.g. by a TUI
+    /// dashboard running in a separate task.
+    fn metrics(&self) -> ScanMetrics;
+}
+
+/// Snapshot of a scanner's counters, returned by [`Stalker::metrics`].
+///
+/// Each counter is read independently, so while tasks run the values may be
+/// slightly out of sync with each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanMetrics {
+    /// Tasks queued but not yet started.
+    pub pending: usize,
+    /// Tasks currently running.
+    pub active: usize,
+    /// Tasks that finished running (successfully or not).
+    pub completed: usize,
+    /// Result logs that could not be broadcast because no stream was subscribed,
+    /// or that the file sink skipped because it lagged behind.
+    pub dropped_logs: usize,
+    /// Tasks enqueued since the scanner was built.
+    pub queued_total: usize,
+}
+
+/// Snapshot of a scanner's configuration and counters, returned by [`Stalker::describe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannerStatus {
+    /// Maximum number of tasks allowed to run simultaneously.
+    pub batch_size: usize,
+    /// Timeout for network operations, in milliseconds.
+    pub timeout_ms: u64,
+    /// Tasks queued but not yet started.
+    pub pending_tasks: usize,
+    /// Tasks currently running.
+    pub active_tasks: usize,
+    /// Tasks that finished running (successfully or not) since the scanner was built.
+    pub completed_tasks: usize,
+    /// Whether at least one log stream is currently subscribed.
+    pub has_log_subscribers: bool,
+}
+
+impl Display for ScannerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Batch size: {}\nTimeout (ms): {}\nPending tasks: {}\nActive tasks: {}\nCompleted tasks: {}\nLog subscribers: {}",
+            self.batch_size,
+            self.timeout_ms,
+            self.pending_tasks,
+            self.active_tasks,
+            self.completed_tasks,
+            if self.has_log_subscribers {
+                "yes"
+            } else {
+                "no"
+            }
+        )
+    }
 }
 
 /// Thread-safe queue of pending tasks.
 type TaskPool = Arc<Mutex<VecDeque<Task>>>;
 
+/// Callback invoked with every result log, see [`Scanner::on_task_complete`].
+pub type TaskCompleteCallback<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
 /// Structured representation of a scanner log entry.
 ///
 /// Produced by [`StructuredFormatter`] and [`JsonFormatter`], and emitted
 /// through the broadcast channel.
 ///
 /// Contains:
+/// - `task_seq`: sequence number of the task that produced the entry (`0` for idle events)
 /// - `header_response`: map of action → result
 /// - `data`: raw or decoded bytes from the TCP probe
 ///
@@ -400,13 +605,15 @@ type TaskPool = Arc<Mutex<VecDeque<Task>>>;
 /// when the scanner becomes idle.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LogRecord {
+    #[serde(default)]
+    pub task_seq: u64,
     pub header_response: LogHeader,
     pub data: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LogHeader {
-    pub actions_results: HashMap<String, String>,
+    pub actions_results: HashMap<String, ActionValue>,
 }
 
 /// A unit of work to be executed by the scanning engine.
@@ -418,6 +625,8 @@ pub struct LogHeader {
 /// Tasks run **at most once**, are placed in a FIFO queue, and
 /// are consumed by [`execute_tasks`](Stalker::execute_tasks).
 ///
+/// A task can be cloned to queue the same scan again.
+///
 /// # Examples
 /// ```rust,ignore
 /// let task = Task::new(
@@ -425,17 +634,181 @@ pub struct LogHeader {
 ///     UrlParser::from_str("https://127.0.0.1:443").unwrap(),
 /// );
 /// ```
+#[derive(Clone)]
 pub struct Task {
     /// Actions that define the workflow for this task.
     todo: Vec<Box<dyn Action>>,
     /// The target (host, URL, IP, etc.) to be scanned.
     target: UrlParser,
+    /// Sequence number, assigned by the scanner when the task is enqueued.
+    seq: u64,
 }
 
 impl Task {
     /// Creates a new task for the given `target` with the specified `actions`.
     pub fn new(todo: Vec<Box<dyn Action>>, target: UrlParser) -> Self {
-        Self { todo, target }
+        Self {
+            todo,
+            target,
+            seq: 0,
+        }
+    }
+
+    /// Returns what identifies the task's target for deduplication.
+    fn dedup_key(&self) -> (String, u16) {
+        (self.target.target.clone(), self.target.effective_port())
+    }
+}
+
+/// Builds one [`Task`] per valid target line, collecting the lines that failed to parse.
+///
+/// Each line is trimmed and parsed with [`UrlParser::new`]. On success a task is
+/// created with the actions returned by `actions_factory`; on failure the line's
+/// index (0-based) and the parsing error are recorded instead.
+///
+/// Pairs well with [`Terminal::ask_multiline`](crate::utils::Terminal::ask_multiline).
+///
+/// # Example
+/// ```rust,ignore
+/// let lines = Terminal::ask_multiline("Paste the targets, finish with END:", "END");
+/// let (tasks, errors) = tasks_from_lines(&lines, || actions!(ActionIsPortOpen {}));
+///
+/// for (line, e) in errors {
+///     eprintln!("line {}: {}", line + 1, e);
+/// }
+/// scanner.add_multiple_tasks(tasks);
+/// ```
+pub fn tasks_from_lines(
+    lines: &[String],
+    actions_factory: impl Fn() -> Vec<Box<dyn Action>>,
+) -> (Vec<Task>, Vec<(usize, UrlParserErrors)>) {
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        match UrlParser::new(line.trim()) {
+            Ok(target) => tasks.push(Task::new(actions_factory(), target)),
+            Err(e) => errors.push((index, e)),
+        }
+    }
+
+    (tasks, errors)
+}
+
+/// A lazy producer of [`Task`]s, consumed by [`Stalker::feed`].
+///
+/// Implemented for every `Iterator<Item = Task>`, so adapters such as
+/// `(1..=1024).map(...)` can be fed directly.
+pub trait TaskSource: Send + 'static {
+    /// Returns the next task, or `None` once the source is exhausted.
+    fn next_task(&mut self) -> Option<Task>;
+}
+
+impl<I> TaskSource for I
+where
+    I: Iterator<Item = Task> + Send + 'static,
+{
+    fn next_task(&mut self) -> Option<Task> {
+        self.next()
+    }
+}
+
+/// Yields one [`Task`] per port of a range on a single host, built on demand.
+///
+/// # Example
+/// ```rust,ignore
+/// let source = PortRangeSource::new("127.0.0.1", 1..=65535, actions!(ActionIsPortOpen {}))?;
+/// scanner.feed(Box::new(source));
+/// scanner.execute_tasks();
+/// ```
+pub struct PortRangeSource {
+    host: String,
+    ports: RangeInclusive<u16>,
+    actions: Vec<Box<dyn Action>>,
+}
+
+impl PortRangeSource {
+    /// Creates a source scanning `ports` of `host` with a copy of `actions` per task.
+    ///
+    /// `host` is an IPv4 address, a bracketed IPv6 address or a DNS name.
+    ///
+    /// # Errors
+    /// Returns [`UrlParserErrors`] if `host` is not a valid target.
+    pub fn new(
+        host: &str,
+        ports: RangeInclusive<u16>,
+        actions: Vec<Box<dyn Action>>,
+    ) -> Result<Self, UrlParserErrors> {
+        UrlParser::new(&format!("http://{}", host))?;
+
+        Ok(Self {
+            host: host.to_string(),
+            ports,
+            actions,
+        })
+    }
+}
+
+impl Iterator for PortRangeSource {
+    type Item = Task;
+
+    fn next(&mut self) -> Option<Task> {
+        // The host was validated in `new`, so only port 0 (the default port) can fail.
+        self.ports.by_ref().find_map(|port| {
+            let target = UrlParser::new(&format!("http://{}:{}", self.host, port)).ok()?;
+            Some(Task::new(self.actions.clone(), target))
+        })
+    }
+}
+
+/// Yields one [`Task`] per address of a CIDR range on a single port, built on demand.
+///
+/// Both IPv4 (`192.168.1.0/24`) and IPv6 (`2001:db8::/120`) ranges are accepted,
+/// see [`expand_ipv4`] and [`expand_ipv6`].
+///
+/// # Example
+/// ```rust,ignore
+/// let source = CidrSource::new("192.168.1.0/24", 22, actions!(ActionIsPortOpen {}))?;
+/// scanner.feed(Box::new(source));
+/// scanner.execute_tasks();
+/// ```
+pub struct CidrSource {
+    addrs: Box<dyn Iterator<Item = IpAddr> + Send>,
+    port: u16,
+    actions: Vec<Box<dyn Action>>,
+}
+
+impl CidrSource {
+    /// Creates a source scanning `port` of every address of `cidr` with a copy
+    /// of `actions` per task.
+    ///
+    /// # Errors
+    /// Returns [`CidrError`] if `cidr` is not a valid IPv4 or IPv6 range.
+    pub fn new(cidr: &str, port: u16, actions: Vec<Box<dyn Action>>) -> Result<Self, CidrError> {
+        let addrs: Box<dyn Iterator<Item = IpAddr> + Send> = if cidr.contains(':') {
+            Box::new(expand_ipv6(cidr)?.map(IpAddr::V6))
+        } else {
+            Box::new(expand_ipv4(cidr)?.map(IpAddr::V4))
+        };
+
+        Ok(Self {
+            addrs,
+            port,
+            actions,
+        })
+    }
+}
+
+impl Iterator for CidrSource {
+    type Item = Task;
+
+    fn next(&mut self) -> Option<Task> {
+        // Addresses are always valid targets, `from_parts` can't fail here.
+        self.addrs.by_ref().find_map(|addr| {
+            let target =
+                UrlParser::from_parts(Scheme::Http, &addr.to_string(), self.port, "").ok()?;
+            Some(Task::new(self.actions.clone(), target))
+        })
     }
 }
 
@@ -494,20 +867,49 @@ impl<T: Clone + Send + Sync + 'static> TaskAwareStream<T> {
 /// # Fields
 /// - `batch_size`: maximum number of tasks allowed to run simultaneously
 /// - `timeout_ms`: network timeout applied to connection attempts
+/// - `emit_idle_events`: whether [`await_idle`](Stalker::await_idle) broadcasts the idle event
+/// - `resolve_localhost_fast`: whether `localhost` targets connect to `127.0.0.1` directly
+/// - `max_read_bytes`: most bytes kept from the read of a connection
 ///
 /// # Defaults
 /// ```rust,ignore
 /// ScannerOptions {
 ///     batch_size: 100,
 ///     timeout_ms: 500,
+///     emit_idle_events: true,
+///     resolve_localhost_fast: true,
+///     max_read_bytes: 512,
 /// }
 /// ```
 #[derive(Clone, Debug)]
 pub struct ScannerOptions {
     /// Maximum number of tasks processed in a single batch.
+    ///
+    /// At least `1`: [`Scanner::with_options`] raises `0`, which would
+    /// otherwise never let a task run.
     pub batch_size: usize,
     /// Timeout for network operations, in milliseconds.
+    ///
+    /// At least `1`: [`Scanner::with_options`] raises `0`.
     pub timeout_ms: u64,
+    /// Broadcast the formatter's idle output when the scanner becomes idle.
+    ///
+    /// Useful for reactive consumers; batch consumers can disable it to get a
+    /// stream of real results only.
+    pub emit_idle_events: bool,
+    /// Connect to `127.0.0.1` for `localhost` targets instead of skipping them.
+    ///
+    /// DNS targets are otherwise not scanned (the scanner does not resolve
+    /// names); `localhost` is the one name that needs no resolver. The
+    /// `"target"` result still reports `localhost`.
+    pub resolve_localhost_fast: bool,
+    /// Most bytes kept from the read of a connection.
+    ///
+    /// When the target sends more, the data is cut at the cap and
+    /// `"Truncated": true` is recorded in `actions_results` (visible to the actions).
+    /// Reads are also bounded by the internal 512-byte buffer, so larger
+    /// values behave like the default.
+    pub max_read_bytes: usize,
 }
 
 impl Default for ScannerOptions {
@@ -515,6 +917,9 @@ impl Default for ScannerOptions {
         Self {
             batch_size: 100,
             timeout_ms: 500,
+            emit_idle_events: true,
+            resolve_localhost_fast: true,
+            max_read_bytes: BUFFER_LEN,
         }
     }
 }
@@ -548,8 +953,16 @@ where
     pub options: ScannerOptions,
     /// Shared queue of pending tasks.
     task_pool: TaskPool,
+    // Idle-detection counters. See `ActiveTasksGuard` for the ordering protocol.
     pending_tasks: Arc<AtomicUsize>,
     active_tasks: Arc<AtomicUsize>,
+    completed_tasks: Arc<AtomicUsize>,
+    /// Result logs sent while nobody was subscribed.
+    dropped_logs: Arc<AtomicUsize>,
+    /// Source of `task_seq` values, incremented on every enqueue.
+    next_task_seq: Arc<AtomicU64>,
+    /// Set the first time `execute_tasks` runs, idle is never reported before that.
+    started: Arc<AtomicBool>,
     buffer_pool: Arc<BufferPool>,
     /// Broadcast channel for log events.
     logger_tx: Arc<Mutex<Option<broadcast::Sender<<F as LogFormatter>::Output>>>>,
@@ -557,33 +970,260 @@ where
     pub logger_format: Arc<F>,
     cancellation_token: Arc<CancellationToken>,
     idle_notify: Arc<Notify>,
+    /// Notified whenever a task completes, wakes up [`Stalker::await_completed`].
+    completed_notify: Arc<Notify>,
+    /// Notified whenever a task leaves the queue, wakes up feeders waiting for room.
+    dequeue_notify: Arc<Notify>,
+    /// Set by `pause`, the executor starts no task while it is `true`.
+    paused: Arc<AtomicBool>,
+    /// Notified by `resume`, wakes up the paused executor.
+    resume_notify: Arc<Notify>,
+    /// File every log is appended to, see [`Scanner::with_file_sink`].
+    file_sink: Option<Arc<File>>,
+    /// Writer task of the file sink, awaited on shutdown.
+    sink_task: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// Called with every result log before it is broadcast, see [`Scanner::on_task_complete`].
+    on_task_complete: Option<TaskCompleteCallback<<F as LogFormatter>::Output>>,
+    /// Opens the connection of every task, see [`Scanner::with_connector`].
+    connector: Arc<dyn Connector>,
 }
 
 /// RAII guard for accurate active task counting.
 ///
-/// When dropped, it decrements `active_tasks` and emits an idle notification
-/// if no active or pending tasks remain.
+/// When dropped, it counts the task as completed (waking up
+/// [`Stalker::await_completed`]), decrements `active_tasks` and emits an idle
+/// notification if no active or pending tasks remain.
 ///
 /// This ensures:
 /// - no race conditions
 /// - deterministic idle detection
 /// - correct behavior even under cancellation or panics
+///
+/// # Memory ordering
+/// Every counter update is an atomic RMW, so no increment/decrement is ever
+/// lost regardless of the ordering used. Orderings only matter when one
+/// counter is used to reason about another, which happens in idle detection:
+///
+/// - A task moves from pending to active as `active += 1` (`Relaxed`) followed by
+///   `pending -= 1` (`Release`).
+/// - Idle checks load `pending` (`Acquire`) **before** `active` (`Acquire`). If the
+///   load observes the `pending` decrement, the earlier `active` increment is
+///   visible too, so a task in transit can never be seen in neither counter.
+/// - The guard counts the task as completed (`Relaxed`) before decrementing
+///   `active` (`AcqRel`), so whoever observes `active == 0` also observes the
+///   completion.
+/// - `pending += 1` in `add_task` can be `Relaxed`: the task is pushed under the
+///   task pool mutex afterwards, which orders it before the pop (and the matching
+///   decrement) on the executor side.
+/// - A running [`Stalker::feed`] holds one extra `pending` slot until its source is
+///   exhausted (released with `Release`), so the scanner is never seen idle between
+///   two pulls.
+///
+/// Informational reads (`describe`) use `Relaxed`, they are only a snapshot.
 struct ActiveTasksGuard {
     active_tasks: Arc<AtomicUsize>,
     pending_tasks: Arc<AtomicUsize>,
+    completed_tasks: Arc<AtomicUsize>,
     idle_notify: Arc<Notify>,
+    completed_notify: Arc<Notify>,
 }
 
 impl Drop for ActiveTasksGuard {
     fn drop(&mut self) {
-        if self.active_tasks.fetch_sub(1, Ordering::SeqCst) == 1
-            && self.pending_tasks.load(Ordering::SeqCst) == 0
+        self.completed_tasks.fetch_add(1, Ordering::Relaxed);
+        self.completed_notify.notify_waiters();
+        // A stale `pending` here only means a spurious or skipped notification: a task
+        // still pending will become active later and its own guard notifies when done.
+        if self.active_tasks.fetch_sub(1, Ordering::AcqRel) == 1
+            && self.pending_tasks.load(Ordering::Acquire) == 0
         {
             self.idle_notify.notify_waiters();
         }
     }
 }
 
+/// Maps a failed connection attempt to the port state recorded under `IsPortOpen`.
+///
+/// A refused connection means nothing listens on the port; a reset, an
+/// unreachable host or an OS-level timeout usually points to a firewall.
+/// Any other error is reported as `"closed"`.
+fn connect_error_state(kind: std::io::ErrorKind) -> &'static str {
+    match kind {
+        std::io::ErrorKind::ConnectionRefused => "refused",
+        std::io::ErrorKind::ConnectionReset | std::io::ErrorKind::ConnectionAborted => "reset",
+        std::io::ErrorKind::HostUnreachable | std::io::ErrorKind::NetworkUnreachable => {
+            "unreachable"
+        }
+        std::io::ErrorKind::TimedOut => "filtered",
+        _ => "closed",
+    }
+}
+
+/// Error returned by [`connect_with_timeout`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConnectError {
+    /// The connection attempt failed (refused, reset, unreachable, ...).
+    Io(std::io::Error),
+    /// The timeout (held by the variant) elapsed before the connection was established.
+    Timeout(Duration),
+}
+
+impl ConnectError {
+    /// Returns the port state the engine records under `IsPortOpen` for this error.
+    ///
+    /// See [`ActionIsPortOpen`] for the possible values.
+    pub fn state(&self) -> &'static str {
+        match self {
+            Self::Io(e) => connect_error_state(e.kind()),
+            Self::Timeout(_) => "timeout",
+        }
+    }
+}
+
+impl Display for ConnectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "connection error: {}", e),
+            Self::Timeout(limit) => {
+                write!(f, "connection timed out after {} ms", limit.as_millis())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConnectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Timeout(_) => None,
+        }
+    }
+}
+
+/// Recovers the [`ConnectError::Timeout`] wrapped by the conversion below,
+/// any other error becomes [`ConnectError::Io`].
+impl From<std::io::Error> for ConnectError {
+    fn from(value: std::io::Error) -> Self {
+        match value
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<ConnectError>())
+        {
+            Some(ConnectError::Timeout(limit)) => Self::Timeout(*limit),
+            _ => Self::Io(value),
+        }
+    }
+}
+
+impl From<ConnectError> for std::io::Error {
+    fn from(value: ConnectError) -> Self {
+        match value {
+            ConnectError::Io(e) => e,
+            timeout @ ConnectError::Timeout(_) => {
+                std::io::Error::new(std::io::ErrorKind::TimedOut, timeout)
+            }
+        }
+    }
+}
+
+/// Opens a TCP connection to `addr` (e.g. `"127.0.0.1:80"`), waiting at most `timeout`.
+///
+/// This is what the engine does for every task, exposed for custom actions that
+/// open their own connections (see also [`ScanContext::connect`]).
+///
+/// # Errors
+/// Returns [`ConnectError::Io`] if the attempt fails and [`ConnectError::Timeout`]
+/// if `timeout` elapses first. [`ConnectError::state`] maps both to a port state.
+///
+/// # Example
+/// ```rust,no_run
+/// use stalkermap::scanner::connect_with_timeout;
+/// use std::time::Duration;
+///
+/// # async fn probe() {
+/// match connect_with_timeout("127.0.0.1:22", Duration::from_millis(500)).await {
+///     Ok(_stream) => println!("open"),
+///     Err(e) => println!("{}: {}", e.state(), e),
+/// }
+/// # }
+/// ```
+pub async fn connect_with_timeout(
+    addr: &str,
+    timeout: Duration,
+) -> Result<TcpStream, ConnectError> {
+    match tokio::time::timeout(timeout, TcpStream::connect(addr)).await {
+        Ok(Ok(stream)) => Ok(stream),
+        Ok(Err(e)) => Err(ConnectError::Io(e)),
+        Err(_) => Err(ConnectError::Timeout(timeout)),
+    }
+}
+
+/// Broadcasts a task's result log, counting it as dropped if no stream receives it.
+///
+/// The `on_task_complete` callback, if any, sees the log first. The log is only
+/// built when at least one stream is subscribed or a callback is set, so
+/// formatting is skipped entirely when nobody listens.
+fn send_log<T>(
+    logs_tx: &Mutex<Option<broadcast::Sender<T>>>,
+    dropped_logs: &AtomicUsize,
+    on_task_complete: Option<&TaskCompleteCallback<T>>,
+    log: impl FnOnce() -> T,
+) {
+    // Clone the sender so the lock is not held while formatting.
+    let logs_tx = logs_tx
+        .lock()
+        .as_ref()
+        .filter(|logs_tx| logs_tx.receiver_count() > 0)
+        .cloned();
+
+    let sent = if logs_tx.is_some() || on_task_complete.is_some() {
+        let log = log();
+        if let Some(callback) = on_task_complete {
+            callback(&log);
+        }
+        logs_tx.is_some_and(|logs_tx| logs_tx.send(log).is_ok())
+    } else {
+        false
+    };
+
+    if !sent {
+        dropped_logs.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Appends every log received on `logs_rx` to `file`, until the channel closes.
+///
+/// Runs on a blocking thread. The file is flushed on idle events (which are not
+/// written) and once more when the channel closes. Logs skipped because the sink
+/// lagged behind the channel are counted in `dropped_logs`.
+fn run_file_sink<F: LogFormatter>(
+    file: &File,
+    formatter: &F,
+    mut logs_rx: broadcast::Receiver<F::Output>,
+    dropped_logs: &AtomicUsize,
+) {
+    let mut writer = BufWriter::new(file);
+
+    loop {
+        match logs_rx.blocking_recv() {
+            Ok(log) if formatter.is_idle_signal(&log) => {
+                let _ = writer.flush();
+            }
+            Ok(log) => {
+                let mut line = formatter.sink_line(&log);
+                line.push(b'\n');
+                let _ = writer.write_all(&line);
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                dropped_logs.fetch_add(skipped as usize, Ordering::Relaxed);
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+
+    let _ = writer.flush();
+}
+
 /// Internal runtime implementing the [`Stalker`] trait.
 ///
 /// This is the operational engine:
@@ -607,11 +1247,34 @@ where
     type F = F;
 
     fn add_task(&self, task: Vec<Box<dyn Action>>, target: UrlParser) {
-        self.0.pending_tasks.fetch_add(1, Ordering::SeqCst);
-        let mut pool = { self.0.task_pool.lock() };
+        self.0.push_task(Task::new(task, target));
+    }
 
-        pool.push_back(Task { todo: task, target });
-        self.0.idle_notify.notify_waiters();
+    fn feed(&self, mut source: Box<dyn TaskSource>) {
+        self.0.pending_tasks.fetch_add(1, Ordering::Relaxed);
+        let scanner = self.0.clone();
+        let capacity = scanner.options.batch_size.max(1);
+
+        tokio::task::spawn(async move {
+            'feed: loop {
+                while scanner.task_pool.lock().len() >= capacity {
+                    tokio::select! {
+                        _ = scanner.dequeue_notify.notified() => {}
+                        _ = scanner.cancellation_token.cancelled() => break 'feed,
+                    }
+                }
+                if scanner.cancellation_token.is_cancelled() {
+                    break;
+                }
+
+                match source.next_task() {
+                    Some(task) => scanner.push_task(task),
+                    None => break,
+                }
+            }
+
+            scanner.pending_tasks.fetch_sub(1, Ordering::Release);
+        });
     }
 
     fn total_tasks(&self) -> usize {
@@ -619,20 +1282,48 @@ where
     }
 
     fn total_tasks_on_queue(&self) -> usize {
-        self.0.pending_tasks.load(Ordering::SeqCst) + self.0.active_tasks.load(Ordering::SeqCst)
+        // Same load order as `await_idle`, see `ActiveTasksGuard`.
+        let pending = self.0.pending_tasks.load(Ordering::Acquire);
+        pending + self.0.active_tasks.load(Ordering::Acquire)
+    }
+
+    fn drain_pending(&self) -> Vec<Task> {
+        let tasks: Vec<Task> = self.0.task_pool.lock().drain(..).collect();
+        if tasks.is_empty() {
+            return tasks;
+        }
+
+        // Same ordering as a task leaving the queue, see `ActiveTasksGuard`.
+        let pending = self
+            .0
+            .pending_tasks
+            .fetch_sub(tasks.len(), Ordering::AcqRel)
+            - tasks.len();
+        self.0.dequeue_notify.notify_one();
+        if pending == 0 && self.0.active_tasks.load(Ordering::Acquire) == 0 {
+            self.0.idle_notify.notify_waiters();
+        }
+
+        tasks
     }
 
     fn execute_tasks(&self) {
-        let batch_size = Arc::new(Semaphore::new(self.0.options.batch_size));
+        self.0.started.store(true, Ordering::Release);
+        // `options` is public, so it may have been set without `with_options`.
+        let batch_size = Arc::new(Semaphore::new(self.0.options.batch_size.max(1)));
         let scanner = self.0.clone();
 
         tokio::task::spawn(async move {
             loop {
                 let timeout_t = scanner.options.timeout_ms;
+                let max_read_bytes = scanner.options.max_read_bytes;
 
+                scanner.wait_while_paused().await;
                 let maybe_task = { scanner.task_pool.lock().pop_front() };
 
                 if let Some(task) = maybe_task {
+                    scanner.dequeue_notify.notify_one();
+
                     let permit = match batch_size.clone().acquire_owned().await {
                         Ok(p) => p,
                         Err(_) => {
@@ -641,16 +1332,22 @@ where
                     };
 
                     let logs_tx = scanner.logger_tx.clone();
+                    let dropped_logs = scanner.dropped_logs.clone();
                     let log_format = scanner.logger_format.clone();
+                    let on_task_complete = scanner.on_task_complete.clone();
+                    let connector = scanner.connector.clone();
 
+                    let is_fast_localhost = scanner.options.resolve_localhost_fast
+                        && task.target.target_type == TargetType::Dns
+                        && task.target.target.eq_ignore_ascii_case("localhost");
                     let addr = format!(
                         "{}:{}",
-                        task.target.target,
-                        if task.target.port == 0 {
-                            80
+                        if is_fast_localhost {
+                            "127.0.0.1".to_string()
                         } else {
-                            task.target.port
-                        }
+                            task.target.normalized_target()
+                        },
+                        task.target.effective_port()
                     );
 
                     let buffer_pool = scanner.buffer_pool.clone();
@@ -658,16 +1355,22 @@ where
 
                     let active_tasks = scanner.active_tasks.clone();
                     let pending_tasks = scanner.pending_tasks.clone();
+                    let completed_tasks = scanner.completed_tasks.clone();
                     let idle_notify = scanner.idle_notify.clone();
+                    let completed_notify = scanner.completed_notify.clone();
 
-                    active_tasks.fetch_add(1, Ordering::SeqCst);
-                    pending_tasks.fetch_sub(1, Ordering::SeqCst);
+                    // Increment `active` before releasing the `pending` decrement so idle
+                    // checks never see the task in neither counter (see `ActiveTasksGuard`).
+                    active_tasks.fetch_add(1, Ordering::Relaxed);
+                    pending_tasks.fetch_sub(1, Ordering::Release);
 
                     tokio::task::spawn(async move {
                         let _guard = ActiveTasksGuard {
                             active_tasks,
                             pending_tasks,
+                            completed_tasks,
                             idle_notify,
+                            completed_notify,
                         };
 
                         if cancel_token.is_cancelled() {
@@ -675,48 +1378,49 @@ where
                         }
 
                         // For now the scanner wont resolve DNS names because of it's cacheless nature.
-                        if task.target.target_type == TargetType::Dns {
+                        if task.target.target_type == TargetType::Dns && !is_fast_localhost {
                             return;
                         }
 
                         let mut buf = buffer_pool.get();
 
-                        let stream = match timeout(
-                            Duration::from_millis(timeout_t),
-                            TcpStream::connect(addr),
-                        )
-                        .await
+                        let mut stream = match connector
+                            .connect(&addr, Duration::from_millis(timeout_t))
+                            .await
+                            .map_err(ConnectError::from)
                         {
-                            Ok(Ok(s)) => s,
-                            Ok(Err(e)) => {
-                                let mut actions_results: HashMap<String, String> = HashMap::new();
-                                actions_results.insert(
-                                    ActionIsPortOpen {}.name().to_string(),
-                                    "closed".to_string(),
-                                );
-                                let msg = (format!("connection error: {}", e)).into_bytes();
-                                let log = log_format.format(actions_results, &msg);
-
-                                if let Some(logs_tx) = logs_tx.lock().as_ref() {
-                                    logs_tx.send(log).ok();
-                                }
-
-                                buffer_pool.put(buf as Buffer);
-                                drop(permit);
-                                return;
+                            Ok(s) => {
+                                trace_event!(debug, task_seq = task.seq, addr = %addr, "connected");
+                                s
                             }
                             Err(e) => {
-                                let mut actions_results: HashMap<String, String> = HashMap::new();
+                                trace_event!(
+                                    warn,
+                                    task_seq = task.seq,
+                                    addr = %addr,
+                                    state = e.state(),
+                                    error = %e,
+                                    "connection failed"
+                                );
+
+                                let mut actions_results: HashMap<String, ActionValue> =
+                                    HashMap::new();
                                 actions_results.insert(
                                     ActionIsPortOpen {}.name().to_string(),
-                                    "timeout".to_string(),
+                                    e.state().into(),
+                                );
+                                actions_results.insert(
+                                    "target".to_string(),
+                                    task.target.target.clone().into(),
+                                );
+                                actions_results.insert("port".to_string(), task.target.port.into());
+                                let msg = e.to_string().into_bytes();
+                                send_log(
+                                    &logs_tx,
+                                    &dropped_logs,
+                                    on_task_complete.as_ref(),
+                                    || log_format.format(task.seq, actions_results, &msg),
                                 );
-                                let msg = (format!("connection timed out: {}", e)).into_bytes();
-                                let log = log_format.format(actions_results, &msg);
-
-                                if let Some(logs_tx) = logs_tx.lock().as_ref() {
-                                    logs_tx.send(log).ok();
-                                }
 
                                 buffer_pool.put(buf as Buffer);
                                 drop(permit);
@@ -724,34 +1428,120 @@ where
                             }
                         };
 
+                        if cancel_token.is_cancelled() {
+                            buffer_pool.put(buf as Buffer);
+                            return;
+                        }
+
                         let ctx = ScanContext {
                             target_addr: &task.target.target,
                             port: task.target.port,
                             task_id: tokio::task::id(),
+                            task_seq: task.seq,
+                            timeout: Duration::from_millis(timeout_t),
+                            local_addr: stream.local_addr().ok(),
+                            connector,
                         };
 
-                        let mut actions_results: HashMap<String, String> = HashMap::new();
+                        // Phase 1: write every payload, in action order.
+                        let mut written = false;
+                        for a in &task.todo {
+                            if let Some(payload) = a.wants_write() {
+                                written |= matches!(
+                                    timeout(
+                                        Duration::from_millis(timeout_t),
+                                        stream.write_all(&payload),
+                                    )
+                                    .await,
+                                    Ok(Ok(()))
+                                );
+                            }
+                        }
+
+                        // Phase 2: a single read shared by every reading action.
                         let mut raw_data: &[u8] = &[];
+                        let mut truncated = false;
+                        let mut read_timed_out: Vec<&'static str> = Vec::new();
+                        if task
+                            .todo
+                            .iter()
+                            .any(|a| a.set_read_from_successfull_connection())
+                        {
+                            // After a payload (or for actions waiting for a banner), give the
+                            // target a chance to answer, within the smallest `Action::timeout`
+                            // of the reading actions. Returns as soon as some data is
+                            // available, with whatever is already buffered.
+                            let waiting = written || task.todo.iter().any(|a| a.wait_for_data());
+                            let action_limit = task
+                                .todo
+                                .iter()
+                                .filter(|a| a.set_read_from_successfull_connection())
+                                .filter_map(|a| a.timeout())
+                                .min();
+                            let wait = match waiting {
+                                true => action_limit
+                                    .map_or(Duration::from_millis(timeout_t), |l| {
+                                        l.min(Duration::from_millis(timeout_t))
+                                    }),
+                                false => Duration::ZERO,
+                            };
+                            let read_len = max_read_bytes.min(BUFFER_LEN);
+                            let len = match timeout(
+                                wait,
+                                stream.read(&mut buf.as_bytes_mut()[..read_len]),
+                            )
+                            .await
+                            {
+                                Ok(Ok(n)) => n,
+                                Ok(Err(_)) => 0,
+                                Err(_) => {
+                                    if waiting {
+                                        read_timed_out.extend(
+                                            task.todo
+                                                .iter()
+                                                .filter(|a| {
+                                                    a.set_read_from_successfull_connection()
+                                                        && a.timeout().is_some_and(|l| l <= wait)
+                                                })
+                                                .map(|a| a.name()),
+                                        );
+                                    }
+                                    0
+                                }
+                            };
+                            // A full read may hide more data: check, without waiting, whether
+                            // one more byte is already available.
+                            if len > 0 && len == read_len {
+                                let mut extra = [0u8; 1];
+                                truncated = matches!(
+                                    timeout(Duration::ZERO, stream.read(&mut extra)).await,
+                                    Ok(Ok(1))
+                                );
+                            }
+
+                            // SAFETY: `read()` writes exactly `len` bytes into the provided buffer,
+                            // and `len` is guaranteed to be <= buffer size. In case of any read error or
+                            // failure, `len` is set to 0, ensuring no uninitialized memory is ever read.
+                            // Therefore, the slice created here only covers initialized memory.
+                            raw_data = unsafe { buf.as_bytes(len) };
+                        }
+
+                        if cancel_token.is_cancelled() {
+                            buffer_pool.put(buf as Buffer);
+                            return;
+                        }
+
+                        // Phase 3: execute the actions.
+                        let mut actions_results: HashMap<String, ActionValue> = HashMap::new();
+                        if truncated {
+                            actions_results.insert("Truncated".to_string(), true.into());
+                        }
+                        for name in read_timed_out {
+                            actions_results.insert(format!("{}_timeout", name), true.into());
+                        }
                         for a in &task.todo {
                             match a.set_read_from_successfull_connection() {
                                 true => {
-                                    let len = match stream.try_read(buf.as_bytes_mut()) {
-                                        Ok(n) => n,
-                                        Err(ref e)
-                                            if e.kind() == tokio::io::ErrorKind::WouldBlock =>
-                                        {
-                                            0
-                                        }
-                                        Err(_) => 0,
-                                    };
-
-                                    // SAFETY: `try_read()` writes exactly `len` bytes into the provided buffer,
-                                    // and `len` is guaranteed to be <= buffer size. In case of any read error or
-                                    // failure, `len` is set to 0, ensuring no uninitialized memory is ever read.
-                                    // Therefore, the slice created here only covers initialized memory.
-                                    raw_data = unsafe { buf.as_bytes(len) };
-
-                                    // Set Action Results
                                     a.execute_after_successfull_connection_and_read(
                                         &ctx,
                                         raw_data,
@@ -767,11 +1557,43 @@ where
                             }
                         }
 
-                        let log = log_format.format(actions_results, raw_data);
+                        // Phase 4: follow-ups, which may open new connections.
+                        for a in &task.todo {
+                            let follow_up =
+                                a.execute_follow_up(&ctx, raw_data, &mut actions_results);
+                            let finished = tokio::select! {
+                                _ = cancel_token.cancelled() => None,
+                                finished = async {
+                                    match a.timeout() {
+                                        Some(limit) => timeout(limit, follow_up).await.is_ok(),
+                                        None => {
+                                            follow_up.await;
+                                            true
+                                        }
+                                    }
+                                } => Some(finished),
+                            };
+
+                            match finished {
+                                Some(true) => {}
+                                Some(false) => {
+                                    actions_results
+                                        .insert(format!("{}_timeout", a.name()), true.into());
+                                }
+                                None => {
+                                    buffer_pool.put(buf as Buffer);
+                                    return;
+                                }
+                            }
+                        }
 
-                        if let Some(logs_tx) = logs_tx.lock().as_ref() {
-                            logs_tx.send(log).ok();
+                        for a in &task.todo {
+                            a.finalize(&ctx, &mut actions_results);
                         }
+
+                        send_log(&logs_tx, &dropped_logs, on_task_complete.as_ref(), || {
+                            log_format.format(task.seq, actions_results, raw_data)
+                        });
                         buffer_pool.put(buf as Buffer);
                         drop(permit);
                     });
@@ -785,12 +1607,32 @@ where
     fn add_multiple_tasks(&self, tasks: Vec<Task>) {
         self.0.idle_notify.notify_waiters();
         let mut pool = { self.0.task_pool.lock() };
-        tasks.into_iter().for_each(|t| {
-            self.0.pending_tasks.fetch_add(1, Ordering::SeqCst);
+        tasks.into_iter().for_each(|mut t| {
+            self.0.pending_tasks.fetch_add(1, Ordering::Relaxed);
+            t.seq = self.0.next_seq();
             pool.push_back(t)
         });
     }
 
+    fn add_multiple_tasks_dedup(&self, tasks: Vec<Task>) -> usize {
+        self.0.idle_notify.notify_waiters();
+        let mut pool = { self.0.task_pool.lock() };
+        let mut seen: HashSet<(String, u16)> = pool.iter().map(Task::dedup_key).collect();
+
+        let mut queued = 0;
+        for mut t in tasks {
+            if !seen.insert(t.dedup_key()) {
+                continue;
+            }
+            self.0.pending_tasks.fetch_add(1, Ordering::Relaxed);
+            t.seq = self.0.next_seq();
+            pool.push_back(t);
+            queued += 1;
+        }
+
+        queued
+    }
+
     async fn get_logs_stream(&self) -> Option<TaskAwareStream<<Self::F as LogFormatter>::Output>> {
         self.0
             .logger_tx
@@ -801,27 +1643,111 @@ where
 
     async fn await_idle(&self) {
         loop {
-            let active = self.0.active_tasks.load(Ordering::SeqCst);
-            let pending = self.0.pending_tasks.load(Ordering::SeqCst);
+            // `pending` must be loaded first, see `ActiveTasksGuard`.
+            let pending = self.0.pending_tasks.load(Ordering::Acquire);
+            let active = self.0.active_tasks.load(Ordering::Acquire);
 
-            if pending == 0 && active == 0 {
-                let event = self.0.logger_format.idle_output();
+            if pending == 0 && active == 0 && self.0.has_started() {
+                if self.0.options.emit_idle_events {
+                    let event = self.0.logger_format.idle_output();
 
-                if let Some(logs_tx) = self.0.logger_tx.lock().as_ref() {
-                    logs_tx.send(event).ok();
+                    if let Some(logs_tx) = self.0.logger_tx.lock().as_ref() {
+                        logs_tx.send(event).ok();
+                    }
                 }
                 break;
             }
+
+            // Let the tasks being waited on make progress on this worker.
+            yield_now().await;
         }
     }
 
-    async fn shutdown_graceful(&self) {
-        self.await_idle().await;
-        self.0.cancellation_token.cancel();
+    async fn await_completed(&self, n: usize) {
+        loop {
+            // Registered before the check so a completion in between isn't missed.
+            let notified = self.0.completed_notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
 
-        if let Some(logs_tx) = self.0.logger_tx.lock().take() {
+            if self.0.completed_tasks.load(Ordering::Relaxed) >= n {
+                break;
+            }
+
+            notified.await;
+        }
+    }
+
+    fn describe(&self) -> ScannerStatus {
+        ScannerStatus {
+            batch_size: self.0.options.batch_size,
+            timeout_ms: self.0.options.timeout_ms,
+            pending_tasks: self.0.pending_tasks.load(Ordering::Relaxed),
+            active_tasks: self.0.active_tasks.load(Ordering::Relaxed),
+            completed_tasks: self.0.completed_tasks.load(Ordering::Relaxed),
+            has_log_subscribers: self.subscriber_count() > 0,
+        }
+    }
+
+    fn subscriber_count(&self) -> usize {
+        self.0
+            .logger_tx
+            .lock()
+            .as_ref()
+            .map_or(0, |logs_tx| logs_tx.receiver_count())
+    }
+
+    fn pause(&self) {
+        self.0.paused.store(true, Ordering::Release);
+    }
+
+    fn resume(&self) {
+        self.0.paused.store(false, Ordering::Release);
+        self.0.resume_notify.notify_waiters();
+    }
+
+    fn is_paused(&self) -> bool {
+        self.0.paused.load(Ordering::Acquire)
+    }
+
+    fn metrics(&self) -> ScanMetrics {
+        ScanMetrics {
+            pending: self.0.pending_tasks.load(Ordering::Relaxed),
+            active: self.0.active_tasks.load(Ordering::Relaxed),
+            completed: self.0.completed_tasks.load(Ordering::Relaxed),
+            dropped_logs: self.0.dropped_logs.load(Ordering::Relaxed),
+            queued_total: (self.0.next_task_seq.load(Ordering::Relaxed) - 1) as usize,
+        }
+    }
+
+    async fn shutdown_graceful(&self) {
+        trace_event!(debug, "graceful shutdown requested, waiting for idle");
+        self.await_idle().await;
+        self.0.cancellation_token.cancel();
+
+        if let Some(logs_tx) = self.0.logger_tx.lock().take() {
+            drop(logs_tx);
+        }
+
+        // Closing the channel ends the file sink, wait for its last flush.
+        let sink_task = self.0.sink_task.lock().take();
+        if let Some(sink_task) = sink_task {
+            sink_task.await.ok();
+        }
+        trace_event!(debug, "graceful shutdown complete");
+    }
+
+    async fn shutdown_now(&self) {
+        self.0.cancellation_token.cancel();
+
+        if let Some(logs_tx) = self.0.logger_tx.lock().take() {
             drop(logs_tx);
         }
+
+        let sink_task = self.0.sink_task.lock().take();
+        if let Some(sink_task) = sink_task {
+            sink_task.await.ok();
+        }
     }
 }
 
@@ -830,8 +1756,21 @@ where
     F: LogFormatter + Default,
 {
     /// Creates a new [`Scanner`] with default configuration.
+    ///
+    /// The formatter is created with `F::default()`; use
+    /// [`Scanner::new_with_formatter`] for formatters that don't implement [`Default`].
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
+        Self::new_with_formatter(F::default())
+    }
+}
+
+impl<F> Scanner<F>
+where
+    F: LogFormatter,
+{
+    /// Creates a new [`Scanner`] with default configuration and the given formatter instance.
+    pub fn new_with_formatter(formatter: F) -> Self {
         let (sender, _) = broadcast::channel::<F::Output>(1024);
 
         Self {
@@ -839,18 +1778,34 @@ where
             task_pool: Arc::new(Mutex::new(VecDeque::new())),
             pending_tasks: Arc::new(AtomicUsize::new(0)),
             active_tasks: Arc::new(AtomicUsize::new(0)),
+            completed_tasks: Arc::new(AtomicUsize::new(0)),
+            dropped_logs: Arc::new(AtomicUsize::new(0)),
+            next_task_seq: Arc::new(AtomicU64::new(1)),
+            started: Arc::new(AtomicBool::new(false)),
             buffer_pool: Arc::new(BufferPool::new()),
             logger_tx: Arc::new(Mutex::new(Some(sender))),
-            logger_format: Arc::new(F::default()),
+            logger_format: Arc::new(formatter),
             cancellation_token: Arc::new(CancellationToken::new()),
             idle_notify: Arc::new(Notify::new()),
+            completed_notify: Arc::new(Notify::new()),
+            dequeue_notify: Arc::new(Notify::new()),
+            paused: Arc::new(AtomicBool::new(false)),
+            resume_notify: Arc::new(Notify::new()),
+            file_sink: None,
+            sink_task: Arc::new(Mutex::new(None)),
+            on_task_complete: None,
+            connector: Arc::new(TcpConnector),
         }
     }
 
     /// Builds a ready-to-use [`Stalker`] implementation using `BuiltScanner`.
     ///
     /// This returns an [`Arc<dyn Stalker>`] that can safely be shared across threads.
+    ///
+    /// # Panics
+    /// Panics if a file sink is configured and this is called outside a Tokio runtime.
     pub fn build(self) -> Arc<dyn Stalker<F = F> + Send + Sync + 'static> {
+        self.spawn_file_sink();
         Arc::new(BuiltScanner(Arc::new(self)))
     }
 
@@ -860,14 +1815,147 @@ where
         T: Stalker + Send + Sync + 'static,
         FF: FnOnce(Arc<Scanner<F>>) -> T,
     {
+        self.spawn_file_sink();
         Arc::new(f(Arc::new(self)))
     }
 
     /// Sets custom configuration of the [`Scanner`].
+    ///
+    /// A `batch_size` or `timeout_ms` of `0` is raised to `1`.
     pub fn with_options(mut self, options: ScannerOptions) -> Self {
-        self.options = options;
+        self.options = ScannerOptions {
+            batch_size: options.batch_size.max(1),
+            timeout_ms: options.timeout_ms.max(1),
+            ..options
+        };
+        self
+    }
+
+    /// Calls `callback` with the result log of every completed task, e.g. to
+    /// update a progress bar without polling.
+    ///
+    /// The callback runs right after the log is produced and before it is
+    /// broadcast, even when no log stream is subscribed. It is called on the
+    /// task's Tokio worker, possibly from several tasks at once, so it must be
+    /// cheap and must not block. Tasks that produce no log (e.g. cancelled ones)
+    /// don't call it.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let done = Arc::new(AtomicUsize::new(0));
+    /// let counter = done.clone();
+    /// let scanner = Scanner::<StructuredFormatter>::new()
+    ///     .on_task_complete(Arc::new(move |_record: &LogRecord| {
+    ///         counter.fetch_add(1, Ordering::Relaxed);
+    ///     }))
+    ///     .build();
+    /// ```
+    pub fn on_task_complete(mut self, callback: TaskCompleteCallback<F::Output>) -> Self {
+        self.on_task_complete = Some(callback);
+        self
+    }
+
+    /// Replaces the [`TcpConnector`] used to open the connection of every task.
+    ///
+    /// The engine only reads and writes through the returned [`AsyncConn`], so a
+    /// custom connector can e.g. tunnel the scan, or feed canned bytes to test
+    /// actions and formatters without any real networking (see [`connector`]).
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let scanner = Scanner::<StructuredFormatter>::new()
+    ///     .with_connector(Arc::new(MockConnector))
+    ///     .build();
+    /// ```
+    pub fn with_connector(mut self, connector: Arc<dyn Connector>) -> Self {
+        self.connector = connector;
         self
     }
+
+    /// Appends every log to the file at `path`, in addition to the log stream.
+    ///
+    /// The file is created if needed and opened in append mode right away, so an
+    /// unwritable path is reported here. Once built, a background task writes each
+    /// log as one line (see [`LogFormatter::sink_line`]), flushes the file whenever
+    /// the scanner becomes idle, and closes it on [`Stalker::shutdown_graceful`],
+    /// which waits for the last write.
+    ///
+    /// The sink counts as a log subscriber, so result logs are not reported as
+    /// dropped while it runs, unless it falls so far behind the log channel that
+    /// it has to skip some: those are counted in [`ScanMetrics::dropped_logs`].
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let scanner = Scanner::<JsonFormatter>::new()
+    ///     .with_file_sink(PathBuf::from("scan.jsonl"))?
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the [`std::io::Error`] raised while opening the file.
+    pub fn with_file_sink(mut self, path: PathBuf) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        self.file_sink = Some(Arc::new(file));
+        Ok(self)
+    }
+
+    /// Starts the file sink writer, if one is configured.
+    fn spawn_file_sink(&self) {
+        let Some(file) = self.file_sink.clone() else {
+            return;
+        };
+        let Some(logs_rx) = self.logger_tx.lock().as_ref().map(|tx| tx.subscribe()) else {
+            return;
+        };
+        let formatter = self.logger_format.clone();
+        let dropped_logs = self.dropped_logs.clone();
+
+        let sink_task = tokio::task::spawn_blocking(move || {
+            run_file_sink(&file, &*formatter, logs_rx, &dropped_logs)
+        });
+        *self.sink_task.lock() = Some(sink_task);
+    }
+
+    /// Assigns the task its sequence number and pushes it onto the queue.
+    fn push_task(&self, mut task: Task) {
+        self.pending_tasks.fetch_add(1, Ordering::Relaxed);
+        task.seq = self.next_seq();
+        self.task_pool.lock().push_back(task);
+        self.idle_notify.notify_waiters();
+    }
+
+    /// Returns the next task sequence number.
+    ///
+    /// Only uniqueness matters, so `Relaxed` is enough.
+    fn next_seq(&self) -> u64 {
+        self.next_task_seq.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Returns `true` once `execute_tasks` has run, or if no task was ever enqueued.
+    ///
+    /// Keeps `await_idle` from returning before execution begins, while a scanner
+    /// that never received a task can still be shut down.
+    fn has_started(&self) -> bool {
+        self.started.load(Ordering::Acquire) || self.next_task_seq.load(Ordering::Relaxed) == 1
+    }
+
+    /// Waits while the scanner is paused, or until it is cancelled.
+    async fn wait_while_paused(&self) {
+        while self.paused.load(Ordering::Acquire) {
+            let resumed = self.resume_notify.notified();
+            tokio::pin!(resumed);
+            // Register before re-checking, so a `resume` in between is not missed.
+            resumed.as_mut().enable();
+            if !self.paused.load(Ordering::Acquire) {
+                break;
+            }
+
+            tokio::select! {
+                _ = resumed => {}
+                _ = self.cancellation_token.cancelled() => break,
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -883,6 +1971,7 @@ mod tests {
         let scanner_custom = Scanner::<JsonFormatter>::new().with_options(ScannerOptions {
             batch_size: 100,
             timeout_ms: 2_000,
+            ..Default::default()
         });
 
         assert_eq!(scanner.options.batch_size, 100);
@@ -891,6 +1980,45 @@ mod tests {
         assert_eq!(scanner_custom.options.timeout_ms, 2_000);
     }
 
+    #[tokio::test]
+    async fn test_scanner_options_zero_values_are_clamped() {
+        let scanner = Scanner::<StructuredFormatter>::new().with_options(ScannerOptions {
+            batch_size: 0,
+            timeout_ms: 0,
+            ..Default::default()
+        });
+        assert_eq!(scanner.options.batch_size, 1);
+        assert_eq!(scanner.options.timeout_ms, 1);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut conns = Vec::new();
+            while let Ok((conn, _)) = listener.accept().await {
+                conns.push(conn);
+            }
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                batch_size: 0,
+                ..Default::default()
+            })
+            .build();
+        for _ in 0..3 {
+            scanner.add_task(
+                actions!(ActionIsPortOpen {}),
+                UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+            );
+        }
+        scanner.execute_tasks();
+
+        timeout(Duration::from_secs(5), scanner.await_idle())
+            .await
+            .unwrap();
+        assert_eq!(scanner.metrics().completed, 3);
+    }
+
     #[tokio::test]
     async fn test_scanner_add_task() {
         let scanner = Scanner::<RawFormatter>::new().build();
@@ -970,4 +2098,2233 @@ mod tests {
         assert_eq!(scanner.total_tasks_on_queue(), 0);
         scanner.shutdown_graceful().await;
     }
+
+    #[derive(Clone)]
+    struct ActionSummary {}
+
+    impl Action for ActionSummary {
+        fn name(&self) -> &'static str {
+            "Summary"
+        }
+
+        fn set_read_from_successfull_connection(&self) -> bool {
+            false
+        }
+
+        fn execute_after_successfull_connection(
+            &self,
+            _ctx: &ScanContext,
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+
+        fn execute_after_successfull_connection_and_read(
+            &self,
+            _ctx: &ScanContext,
+            _raw_data: &[u8],
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+
+        fn finalize(&self, _ctx: &ScanContext, actions_results: &mut HashMap<String, ActionValue>) {
+            let summary = actions_results
+                .get(ActionIsPortOpen {}.name())
+                .cloned()
+                .unwrap_or_else(|| "".into());
+            actions_results.insert(self.name().to_string(), summary);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_scanner_action_finalize() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionSummary {}, ActionIsPortOpen {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            log.header_response.actions_results.get("Summary"),
+            Some(&ActionValue::from("open"))
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_describe() {
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                batch_size: 42,
+                timeout_ms: 500,
+                ..Default::default()
+            })
+            .build();
+
+        let status = scanner.describe();
+        assert_eq!(status.pending_tasks, 0);
+        assert!(!status.has_log_subscribers);
+
+        let _logs = scanner.get_logs_stream().await.unwrap();
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str("https://127.0.0.1:80").unwrap(),
+        );
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str("https://127.0.0.1:443").unwrap(),
+        );
+
+        let status = scanner.describe();
+        assert_eq!(status.pending_tasks, 2);
+        assert_eq!(status.active_tasks, 0);
+        assert_eq!(status.completed_tasks, 0);
+        assert!(status.has_log_subscribers);
+        assert!(status.to_string().contains("Batch size: 42"));
+    }
+
+    #[tokio::test]
+    async fn test_scanner_is_idle_for_late_subscriber() {
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        assert!(scanner.is_idle());
+
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str("http://127.0.0.1:1").unwrap(),
+        );
+        assert!(!scanner.is_idle());
+
+        scanner.execute_tasks();
+        scanner.await_idle().await;
+
+        // Subscribing after the idle event was sent.
+        let _logs = scanner.get_logs_stream().await.unwrap();
+        assert!(scanner.is_idle());
+    }
+
+    #[tokio::test]
+    async fn test_scanner_await_idle_waits_for_execution() {
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        scanner.add_multiple_tasks(vec![
+            Task::new(
+                actions!(ActionIsPortOpen {}),
+                UrlParser::from_str("http://127.0.0.1:1").unwrap(),
+            ),
+            Task::new(
+                actions!(ActionIsPortOpen {}),
+                UrlParser::from_str("http://127.0.0.1:2").unwrap(),
+            ),
+        ]);
+
+        let waiter = {
+            let scanner = scanner.clone();
+            tokio::spawn(async move { scanner.await_idle().await })
+        };
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        scanner.execute_tasks();
+        tokio::time::timeout(Duration::from_secs(5), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(scanner.describe().completed_tasks, 2);
+    }
+
+    #[tokio::test]
+    async fn test_scanner_await_completed() {
+        // Accepts connections and keeps them open without ever answering.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut conns = Vec::new();
+            while let Ok((conn, _)) = listener.accept().await {
+                conns.push(conn);
+            }
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                timeout_ms: 3000,
+                ..Default::default()
+            })
+            .build();
+
+        // Three refused connections finish right away, the two reads on the
+        // silent listener only end with the timeout.
+        let mut tasks: Vec<Task> = (1..=3)
+            .map(|p| {
+                Task::new(
+                    actions!(ActionIsPortOpen {}),
+                    UrlParser::from_str(&format!("http://127.0.0.1:{}", p)).unwrap(),
+                )
+            })
+            .collect();
+        for _ in 0..2 {
+            tasks.push(Task::new(
+                actions!(ActionTlsDetect {}),
+                UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+            ));
+        }
+        scanner.add_multiple_tasks(tasks);
+        scanner.execute_tasks();
+
+        tokio::time::timeout(Duration::from_secs(2), scanner.await_completed(3))
+            .await
+            .unwrap();
+
+        let metrics = scanner.metrics();
+        assert!(metrics.completed >= 3);
+        assert!(metrics.completed < 5);
+
+        scanner.shutdown_now().await;
+    }
+
+    /// Formatter with configuration and no `Default` implementation.
+    struct PrefixFormatter {
+        prefix: String,
+    }
+
+    impl LogFormatter for PrefixFormatter {
+        type Output = String;
+
+        fn format(
+            &self,
+            task_seq: u64,
+            _actions_results: HashMap<String, ActionValue>,
+            _raw_data: &[u8],
+        ) -> Self::Output {
+            format!("{}{}", self.prefix, task_seq)
+        }
+
+        fn idle_output(&self) -> Self::Output {
+            format!("{}idle", self.prefix)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scanner_new_with_formatter() {
+        let scanner = Scanner::new_with_formatter(PrefixFormatter {
+            prefix: "scan-".to_string(),
+        })
+        .build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str("http://127.0.0.1:1").unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(log, "scan-1");
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_connection_refused_state() {
+        // Bind then drop a listener, so nothing listens on the port anymore.
+        let port = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap().port()
+        };
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            log.header_response.actions_results.get("IsPortOpen"),
+            Some(&ActionValue::from("refused"))
+        );
+        assert_eq!(
+            connect_error_state(std::io::ErrorKind::TimedOut),
+            "filtered"
+        );
+        assert_eq!(connect_error_state(std::io::ErrorKind::Other), "closed");
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn test_scanner_tracing_warns_on_connect_failure() {
+        use tracing::{Event, Level, Metadata, Subscriber, span};
+
+        /// Counts `WARN` events emitted by the scanner.
+        struct WarnCounter(Arc<AtomicUsize>);
+
+        impl Subscriber for WarnCounter {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let metadata = event.metadata();
+                if *metadata.level() == Level::WARN
+                    && metadata.target().starts_with("stalkermap::scanner")
+                {
+                    self.0.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let warnings = Arc::new(AtomicUsize::new(0));
+        // `#[tokio::test]` runs every task on this thread, so a thread-local default is enough.
+        let _default = tracing::subscriber::set_default(WarnCounter(warnings.clone()));
+
+        let port = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap().port()
+        };
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(warnings.load(Ordering::Relaxed), 1);
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_connect_with_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let open = listener.local_addr().unwrap().to_string();
+        let closed = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap().to_string()
+        };
+
+        assert!(
+            connect_with_timeout(&open, Duration::from_secs(5))
+                .await
+                .is_ok()
+        );
+
+        let refused = connect_with_timeout(&closed, Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert!(matches!(refused, ConnectError::Io(_)));
+        assert_eq!(refused.state(), "refused");
+
+        // A listener that never accepts stops answering handshakes once its
+        // backlog is full, so a further connection attempt times out.
+        let socket = tokio::net::TcpSocket::new_v4().unwrap();
+        socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let full = socket.listen(1).unwrap();
+        let full_addr = full.local_addr().unwrap().to_string();
+
+        let mut held = Vec::new();
+        let timed_out = loop {
+            match connect_with_timeout(&full_addr, Duration::from_millis(200)).await {
+                Ok(stream) if held.len() < 16 => held.push(stream),
+                Ok(_) => panic!("backlog never filled up"),
+                Err(e) => break e,
+            }
+        };
+        assert!(matches!(timed_out, ConnectError::Timeout(_)));
+        assert_eq!(timed_out.state(), "timeout");
+    }
+
+    #[tokio::test]
+    async fn test_scanner_pause_resume() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { while let Ok((_socket, _)) = listener.accept().await {} });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+        let url = UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap();
+
+        scanner.pause();
+        assert!(scanner.is_paused());
+        scanner.add_task(actions!(ActionIsPortOpen {}), url.clone());
+        scanner.add_task(actions!(ActionIsPortOpen {}), url);
+        scanner.execute_tasks();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let metrics = scanner.metrics();
+        assert_eq!(metrics.pending, 2);
+        assert_eq!(metrics.completed, 0);
+        assert!(!scanner.is_idle());
+
+        scanner.resume();
+        assert!(!scanner.is_paused());
+        for _ in 0..2 {
+            let log = timeout(Duration::from_secs(5), logs.next())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                log.header_response.actions_results.get("IsPortOpen"),
+                Some(&ActionValue::from("open"))
+            );
+        }
+
+        scanner.shutdown_graceful().await;
+        assert_eq!(scanner.metrics().completed, 2);
+    }
+
+    #[tokio::test]
+    async fn test_scanner_add_multiple_tasks_dedup() {
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let task = |url: &str| Task::new(actions!(ActionIsPortOpen {}), url.parse().unwrap());
+
+        let queued = scanner.add_multiple_tasks_dedup(vec![
+            task("http://127.0.0.1:1"),
+            task("http://127.0.0.1:2"),
+            task("http://127.0.0.1:1"),
+        ]);
+        assert_eq!(queued, 2);
+        assert_eq!(scanner.total_tasks(), 2);
+        assert_eq!(scanner.metrics().pending, 2);
+
+        // Duplicates of queued tasks are skipped too, the implicit port 80 included.
+        let queued = scanner.add_multiple_tasks_dedup(vec![
+            task("http://127.0.0.1:2"),
+            task("http://127.0.0.1"),
+            task("http://127.0.0.1:80"),
+        ]);
+        assert_eq!(queued, 1);
+        assert_eq!(scanner.total_tasks(), 3);
+
+        // Once drained from the queue, the same targets can be queued again.
+        let drained = scanner.drain_pending();
+        assert_eq!(scanner.add_multiple_tasks_dedup(drained), 3);
+
+        scanner.shutdown_now().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_on_task_complete() {
+        let completed = Arc::new(AtomicUsize::new(0));
+        let counter = completed.clone();
+
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .on_task_complete(Arc::new(move |record: &LogRecord| {
+                assert!(record.task_seq > 0);
+                counter.fetch_add(1, Ordering::Relaxed);
+            }))
+            .build();
+
+        // No log stream is subscribed, the callback still sees every log.
+        for port in [1, 2, 3] {
+            scanner.add_task(
+                actions!(ActionIsPortOpen {}),
+                UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+            );
+        }
+        scanner.execute_tasks();
+
+        timeout(Duration::from_secs(5), scanner.await_idle())
+            .await
+            .unwrap();
+        assert_eq!(completed.load(Ordering::Relaxed), 3);
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_drain_pending() {
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+
+        for port in [1, 2, 3] {
+            scanner.add_task(
+                actions!(ActionIsPortOpen {}),
+                UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+            );
+        }
+        assert_eq!(scanner.total_tasks(), 3);
+
+        let drained = scanner.drain_pending();
+        assert_eq!(drained.len(), 3);
+        assert_eq!(
+            drained.iter().map(|t| t.target.port).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(scanner.total_tasks(), 0);
+        assert_eq!(scanner.metrics().pending, 0);
+        assert!(scanner.is_idle());
+        assert!(scanner.drain_pending().is_empty());
+
+        // Idle detection still completes once execution starts.
+        scanner.execute_tasks();
+        timeout(Duration::from_secs(1), scanner.await_idle())
+            .await
+            .unwrap();
+        assert_eq!(scanner.metrics().completed, 0);
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_subscriber_count() {
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        assert_eq!(scanner.subscriber_count(), 0);
+
+        let logs = scanner.get_logs_stream().await.unwrap();
+        assert_eq!(scanner.subscriber_count(), 1);
+
+        drop(logs);
+        assert_eq!(scanner.subscriber_count(), 0);
+
+        // Without subscribers the result is not formatted, only counted as dropped.
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str("http://127.0.0.1:1").unwrap(),
+        );
+        scanner.execute_tasks();
+        scanner.await_idle().await;
+        assert_eq!(scanner.metrics().dropped_logs, 1);
+
+        scanner.shutdown_graceful().await;
+        assert_eq!(scanner.subscriber_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_scanner_resolve_localhost_fast() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let _conn = listener.accept().await;
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str(&format!("http://localhost:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let results = &log.header_response.actions_results;
+        assert_eq!(results.get("IsPortOpen"), Some(&ActionValue::from("open")));
+        assert_eq!(results.get("target"), Some(&ActionValue::from("localhost")));
+
+        scanner.shutdown_graceful().await;
+
+        // Disabled: `localhost` is a DNS target like any other and is skipped.
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                resolve_localhost_fast: false,
+                ..Default::default()
+            })
+            .build();
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str(&format!("http://localhost:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+        scanner.await_idle().await;
+        assert_eq!(scanner.metrics().dropped_logs, 0);
+        scanner.shutdown_graceful().await;
+    }
+
+    #[test]
+    fn test_raw_formatter_escapes_control_bytes() {
+        let output = RawFormatter.format(1, HashMap::new(), &[0x1b, 0x5b, 0x41]);
+        assert_eq!(output, b"\\x1b[A");
+
+        let output = RawFormatter.format(1, HashMap::new(), b"SSH-2.0\r\nok\n\x00\xff");
+        assert_eq!(output, b"SSH-2.0\\x0d\nok\n\\x00\\xff");
+        // One record is one line of a file sink.
+        assert_eq!(
+            RawFormatter.sink_line(&output),
+            b"SSH-2.0\\x0d\\nok\\n\\x00\\xff"
+        );
+
+        // A literal `\x1b` in the banner stays distinguishable from an escaped ESC byte.
+        let output = RawFormatter.format(1, HashMap::new(), b"C:\\x1b");
+        assert_eq!(output, b"C:\\\\x1b");
+    }
+
+    #[tokio::test]
+    async fn test_scanner_closure_formatter() {
+        let formatter =
+            ClosureFormatter::from_fn(|results, _raw| format!("ports:{}", results["port"]), "idle");
+        assert!(formatter.is_idle_signal(&"idle".to_string()));
+
+        let scanner = Scanner::new_with_formatter(formatter).build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str("http://127.0.0.1:1").unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(log, "ports:1");
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_without_idle_events() {
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                emit_idle_events: false,
+                ..Default::default()
+            })
+            .build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str("http://127.0.0.1:1").unwrap(),
+        );
+        scanner.execute_tasks();
+        scanner.await_idle().await;
+        scanner.shutdown_graceful().await;
+
+        let mut records = Vec::new();
+        while let Some(log) = timeout(Duration::from_secs(5), logs.next()).await.unwrap() {
+            records.push(log);
+        }
+
+        assert_eq!(records.len(), 1);
+        assert!(!StructuredFormatter.is_idle_signal(&records[0]));
+    }
+
+    #[tokio::test]
+    async fn test_scanner_file_sink() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let _conn = listener.accept().await;
+        });
+
+        let path = std::env::temp_dir().join(format!(
+            "stalkermap-file-sink-{}-{}.jsonl",
+            std::process::id(),
+            port
+        ));
+        let scanner = Scanner::<JsonFormatter>::new()
+            .with_file_sink(path.clone())
+            .unwrap()
+            .build();
+
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+        scanner.await_idle().await;
+        scanner.shutdown_graceful().await;
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let record: LogRecord = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(
+            record.header_response.actions_results.get("IsPortOpen"),
+            Some(&ActionValue::from("open"))
+        );
+        assert_eq!(scanner.metrics().dropped_logs, 0);
+    }
+
+    #[test]
+    fn test_file_sink_counts_lagged_logs() {
+        let path = std::env::temp_dir().join(format!(
+            "stalkermap-file-sink-lagged-{}.log",
+            std::process::id()
+        ));
+        let file = File::create(&path).unwrap();
+
+        let (logs_tx, logs_rx) = broadcast::channel::<Vec<u8>>(2);
+        for line in [b"first", b"secnd", b"third", b"forth"] {
+            logs_tx.send(line.to_vec()).unwrap();
+        }
+        drop(logs_tx);
+
+        let dropped_logs = AtomicUsize::new(0);
+        run_file_sink(&file, &RawFormatter, logs_rx, &dropped_logs);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(contents, "third\nforth\n");
+        assert_eq!(dropped_logs.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_scanner_metrics_polling() {
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let poller = {
+            let scanner = scanner.clone();
+            let done = done.clone();
+            tokio::spawn(async move {
+                let mut snapshots = 0usize;
+                while !done.load(Ordering::Relaxed) {
+                    let metrics = scanner.metrics();
+                    assert!(metrics.completed <= metrics.queued_total);
+                    snapshots += 1;
+                    yield_now().await;
+                }
+                snapshots
+            })
+        };
+
+        scanner.add_multiple_tasks(
+            (1..=20)
+                .map(|port| {
+                    Task::new(
+                        actions!(ActionIsPortOpen {}),
+                        UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+                    )
+                })
+                .collect(),
+        );
+        scanner.execute_tasks();
+        scanner.await_idle().await;
+
+        done.store(true, Ordering::Relaxed);
+        assert!(poller.await.unwrap() > 0);
+
+        let metrics = scanner.metrics();
+        assert_eq!(metrics.queued_total, 20);
+        assert_eq!(metrics.completed, metrics.queued_total);
+        assert_eq!((metrics.pending, metrics.active), (0, 0));
+        // Nobody subscribed, so every result log was dropped.
+        assert_eq!(metrics.dropped_logs, 20);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_scanner_feed_bounds_queue() {
+        let batch_size = 10;
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                batch_size,
+                ..Default::default()
+            })
+            .build();
+
+        let source =
+            PortRangeSource::new("127.0.0.1", 1..=1000, actions!(ActionIsPortOpen {})).unwrap();
+        scanner.feed(Box::new(source));
+        assert!(!scanner.is_idle());
+
+        let done = Arc::new(AtomicBool::new(false));
+        let poller = {
+            let scanner = scanner.clone();
+            let done = done.clone();
+            tokio::spawn(async move {
+                let mut peak = 0;
+                while !done.load(Ordering::Relaxed) {
+                    peak = peak.max(scanner.total_tasks());
+                    yield_now().await;
+                }
+                peak
+            })
+        };
+
+        scanner.execute_tasks();
+        scanner.await_idle().await;
+        done.store(true, Ordering::Relaxed);
+
+        assert!(poller.await.unwrap() <= batch_size);
+        let metrics = scanner.metrics();
+        assert_eq!(metrics.queued_total, 1000);
+        assert_eq!(metrics.completed, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_scanner_feed_stops_on_shutdown() {
+        let batch_size = 10;
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                batch_size,
+                ..Default::default()
+            })
+            .build();
+
+        let source =
+            PortRangeSource::new("127.0.0.1", 1..=1000, actions!(ActionIsPortOpen {})).unwrap();
+        scanner.feed(Box::new(source));
+        while scanner.total_tasks() < batch_size {
+            yield_now().await;
+        }
+
+        // The queue is full and never executed: only the shutdown can end the feed.
+        scanner.shutdown_now().await;
+        timeout(Duration::from_secs(5), async {
+            while scanner.metrics().pending > batch_size {
+                yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(scanner.metrics().queued_total, batch_size);
+    }
+
+    #[test]
+    fn test_scanner_cidr_source() {
+        let source = CidrSource::new("127.0.0.1/30", 8080, actions!(ActionIsPortOpen {})).unwrap();
+        let urls: Vec<String> = source.map(|task| task.target.full_url).collect();
+        assert_eq!(
+            urls,
+            [
+                "http://127.0.0.0:8080",
+                "http://127.0.0.1:8080",
+                "http://127.0.0.2:8080",
+                "http://127.0.0.3:8080",
+            ]
+        );
+
+        let mut source = CidrSource::new("::1/128", 22, actions!(ActionIsPortOpen {})).unwrap();
+        let task = source.next().unwrap();
+        assert_eq!(task.target.full_url, "http://[::1]:22");
+        assert_eq!(task.todo.len(), 1);
+        assert!(source.next().is_none());
+
+        assert!(matches!(
+            CidrSource::new("10.0.0.0/40", 80, actions!(ActionIsPortOpen {})),
+            Err(CidrError::InvalidPrefix(_))
+        ));
+    }
+
+    #[derive(Clone)]
+    struct ActionSlowFollowUp {
+        limit: Option<Duration>,
+    }
+
+    #[async_trait]
+    impl Action for ActionSlowFollowUp {
+        fn name(&self) -> &'static str {
+            "SlowFollowUp"
+        }
+
+        fn set_read_from_successfull_connection(&self) -> bool {
+            false
+        }
+
+        fn execute_after_successfull_connection(
+            &self,
+            _ctx: &ScanContext,
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+
+        fn execute_after_successfull_connection_and_read(
+            &self,
+            _ctx: &ScanContext,
+            _raw_data: &[u8],
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+
+        async fn execute_follow_up(
+            &self,
+            _ctx: &ScanContext<'_>,
+            _raw_data: &[u8],
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        }
+
+        fn timeout(&self) -> Option<Duration> {
+            self.limit
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scanner_shutdown_now_cancels_running_tasks() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let _conn = listener.accept().await;
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionSlowFollowUp { limit: None }),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+        while scanner.metrics().active == 0 {
+            yield_now().await;
+        }
+
+        timeout(Duration::from_secs(1), scanner.shutdown_now())
+            .await
+            .unwrap();
+        let closed = timeout(Duration::from_secs(1), logs.next()).await.unwrap();
+        assert!(closed.is_none());
+
+        timeout(Duration::from_secs(1), async {
+            while scanner.metrics().active != 0 {
+                yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(scanner.metrics().completed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let _conn = listener.accept().await;
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(
+                ActionIsPortOpen {},
+                ActionSlowFollowUp {
+                    limit: Some(Duration::from_millis(50)),
+                }
+            ),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let results = log.header_response.actions_results;
+        assert_eq!(
+            results.get("SlowFollowUp_timeout"),
+            Some(&ActionValue::Bool(true))
+        );
+        assert_eq!(results.get("IsPortOpen"), Some(&ActionValue::from("open")));
+        assert!(!results.contains_key("IsPortOpen_timeout"));
+    }
+
+    #[tokio::test]
+    async fn test_scanner_task_clone() {
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+
+        let task = Task::new(
+            actions!(ActionIsPortOpen {}, ActionSummary {}),
+            UrlParser::from_str("https://127.0.0.1:80").unwrap(),
+        );
+        let cloned = task.clone();
+        assert_eq!(cloned.todo.len(), 2);
+        assert_eq!(cloned.todo[1].name(), "Summary");
+        assert_eq!(cloned.target, task.target);
+
+        scanner.add_multiple_tasks(vec![task, cloned]);
+        assert_eq!(scanner.total_tasks(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_tls_detect() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut hello = [0u8; 5];
+            socket.read_exact(&mut hello).await.unwrap();
+            assert_eq!(hello[0], 0x16);
+            socket.write_all(&[0x16]).await.unwrap();
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionTlsDetect {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            log.header_response.actions_results.get("TLS"),
+            Some(&ActionValue::from("yes"))
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    /// Server flight captured from a test server: `ServerHello`, then a
+    /// `Certificate` (leaf `CN=scanme.stalkermap.test`, issued by
+    /// `CN=Stalkermap Test CA`) and `ServerHelloDone` in a second record.
+    #[rustfmt::skip]
+    const TLS_SERVER_FLIGHT: &[u8] = &[
+        0x16, 0x03, 0x03, 0x00, 0x2a, 0x02, 0x00, 0x00, 0x26, 0x03, 0x03, 0x40, 0x41, 0x42, 0x43, 0x44,
+        0x45, 0x46, 0x47, 0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f, 0x50, 0x51, 0x52, 0x53, 0x54,
+        0x55, 0x56, 0x57, 0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f, 0x00, 0xc0, 0x2f, 0x00, 0x16,
+        0x03, 0x03, 0x01, 0xcb, 0x0b, 0x00, 0x01, 0xc3, 0x00, 0x01, 0xc0, 0x00, 0x01, 0xbd, 0x30, 0x82,
+        0x01, 0xb9, 0x30, 0x82, 0x01, 0x5f, 0xa0, 0x03, 0x02, 0x01, 0x02, 0x02, 0x14, 0x40, 0x8f, 0x0e,
+        0x84, 0x22, 0x62, 0x69, 0xa2, 0x65, 0x91, 0xf5, 0x91, 0xaa, 0x39, 0x21, 0xa0, 0x10, 0x08, 0xd5,
+        0xa3, 0x30, 0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02, 0x30, 0x32, 0x31,
+        0x13, 0x30, 0x11, 0x06, 0x03, 0x55, 0x04, 0x0a, 0x0c, 0x0a, 0x53, 0x74, 0x61, 0x6c, 0x6b, 0x65,
+        0x72, 0x6d, 0x61, 0x70, 0x31, 0x1b, 0x30, 0x19, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x12, 0x53,
+        0x74, 0x61, 0x6c, 0x6b, 0x65, 0x72, 0x6d, 0x61, 0x70, 0x20, 0x54, 0x65, 0x73, 0x74, 0x20, 0x43,
+        0x41, 0x30, 0x1e, 0x17, 0x0d, 0x32, 0x36, 0x31, 0x30, 0x31, 0x36, 0x30, 0x39, 0x31, 0x30, 0x33,
+        0x34, 0x5a, 0x17, 0x0d, 0x33, 0x36, 0x31, 0x30, 0x31, 0x33, 0x30, 0x39, 0x31, 0x30, 0x33, 0x34,
+        0x5a, 0x30, 0x43, 0x31, 0x0b, 0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x06, 0x13, 0x02, 0x50, 0x54,
+        0x31, 0x13, 0x30, 0x11, 0x06, 0x03, 0x55, 0x04, 0x0a, 0x0c, 0x0a, 0x53, 0x74, 0x61, 0x6c, 0x6b,
+        0x65, 0x72, 0x6d, 0x61, 0x70, 0x31, 0x1f, 0x30, 0x1d, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x16,
+        0x73, 0x63, 0x61, 0x6e, 0x6d, 0x65, 0x2e, 0x73, 0x74, 0x61, 0x6c, 0x6b, 0x65, 0x72, 0x6d, 0x61,
+        0x70, 0x2e, 0x74, 0x65, 0x73, 0x74, 0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce,
+        0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+        0x04, 0xe0, 0x3d, 0x6d, 0xab, 0x4f, 0x90, 0xf4, 0x9c, 0x99, 0xdc, 0x68, 0x0b, 0xcb, 0x6e, 0x47,
+        0xc6, 0x40, 0x5c, 0xd9, 0xdf, 0x8b, 0x26, 0xcb, 0xab, 0xa8, 0x10, 0xf2, 0xa3, 0x27, 0x16, 0x48,
+        0x06, 0xbf, 0x81, 0x30, 0xc2, 0x47, 0xeb, 0xfb, 0x1d, 0x75, 0x2c, 0x72, 0xbe, 0x5d, 0xc1, 0xf8,
+        0x97, 0xf0, 0x13, 0xfe, 0x91, 0x4b, 0xb6, 0x5c, 0x14, 0x64, 0xc2, 0x43, 0x89, 0x83, 0x2b, 0x74,
+        0xe9, 0xa3, 0x42, 0x30, 0x40, 0x30, 0x1d, 0x06, 0x03, 0x55, 0x1d, 0x0e, 0x04, 0x16, 0x04, 0x14,
+        0x1e, 0xbe, 0xdb, 0xf7, 0xdc, 0xd4, 0x58, 0x26, 0x00, 0x44, 0x55, 0x89, 0xe1, 0xb9, 0xc1, 0xc7,
+        0xf7, 0x6f, 0x45, 0x58, 0x30, 0x1f, 0x06, 0x03, 0x55, 0x1d, 0x23, 0x04, 0x18, 0x30, 0x16, 0x80,
+        0x14, 0x3d, 0xdc, 0x96, 0x67, 0x12, 0xc8, 0xfe, 0x6f, 0x3f, 0xd5, 0xf2, 0x28, 0x71, 0x53, 0x28,
+        0xcf, 0x5a, 0x5f, 0x3a, 0xbe, 0x30, 0x0a, 0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03,
+        0x02, 0x03, 0x48, 0x00, 0x30, 0x45, 0x02, 0x21, 0x00, 0xac, 0xef, 0xbb, 0x74, 0x7d, 0x0f, 0xaa,
+        0xc8, 0x69, 0xda, 0x05, 0xfd, 0x58, 0xf7, 0xfa, 0xa0, 0x5d, 0x35, 0x36, 0x56, 0xb4, 0xb3, 0xca,
+        0x04, 0xf9, 0x8a, 0x35, 0xc9, 0x47, 0x7a, 0x3a, 0x58, 0x02, 0x20, 0x17, 0x99, 0xec, 0x03, 0xe7,
+        0x40, 0xf0, 0xfd, 0xdc, 0x58, 0x28, 0xeb, 0x34, 0x22, 0x87, 0x9e, 0xbe, 0xf3, 0x9b, 0x4b, 0x40,
+        0xb5, 0xee, 0x4d, 0x40, 0xa6, 0xfd, 0xd8, 0x1a, 0xc2, 0x2c, 0x15, 0x0e, 0x00, 0x00, 0x00,
+    ];
+
+    #[tokio::test]
+    async fn test_scanner_action_tls_cert_cn() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    // The task's own connection sends nothing, only the follow-up says hello.
+                    let mut hello = [0u8; 5];
+                    if socket.read_exact(&mut hello).await.is_err() {
+                        return;
+                    }
+                    assert_eq!(hello[0], 0x16);
+
+                    // Split the flight so the certificate spans several reads.
+                    let (first, rest) = TLS_SERVER_FLIGHT.split_at(100);
+                    socket.write_all(first).await.unwrap();
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    socket.write_all(rest).await.unwrap();
+                });
+            }
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionTlsCertCn {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            log.header_response.actions_results.get("TlsCn"),
+            Some(&ActionValue::from("scanme.stalkermap.test"))
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[derive(Clone)]
+    struct ActionSendPing {}
+
+    impl Action for ActionSendPing {
+        fn name(&self) -> &'static str {
+            "SendPing"
+        }
+
+        fn set_read_from_successfull_connection(&self) -> bool {
+            false
+        }
+
+        fn wants_write(&self) -> Option<Vec<u8>> {
+            Some(b"PING\r\n".to_vec())
+        }
+
+        fn execute_after_successfull_connection(
+            &self,
+            _ctx: &ScanContext,
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+
+        fn execute_after_successfull_connection_and_read(
+            &self,
+            _ctx: &ScanContext,
+            _raw_data: &[u8],
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+    }
+
+    #[derive(Clone)]
+    struct ActionLocalAddr {}
+
+    impl Action for ActionLocalAddr {
+        fn name(&self) -> &'static str {
+            "LocalAddr"
+        }
+
+        fn set_read_from_successfull_connection(&self) -> bool {
+            false
+        }
+
+        fn execute_after_successfull_connection(
+            &self,
+            ctx: &ScanContext,
+            actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+            if let Some(addr) = ctx.local_addr {
+                actions_results.insert(self.name().to_string(), addr.to_string().into());
+            }
+        }
+
+        fn execute_after_successfull_connection_and_read(
+            &self,
+            _ctx: &ScanContext,
+            _raw_data: &[u8],
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scanner_context_local_addr() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let peer = tokio::spawn(async move {
+            let (_socket, peer) = listener.accept().await.unwrap();
+            peer
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionLocalAddr {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let local_addr: std::net::SocketAddr = log.header_response.actions_results["LocalAddr"]
+            .to_string()
+            .parse()
+            .unwrap();
+        assert_eq!(local_addr.ip().to_string(), "127.0.0.1");
+        assert_eq!(local_addr, peer.await.unwrap());
+
+        scanner.shutdown_graceful().await;
+    }
+
+    /// "Connects" port 22 to an in-memory server sending a banner and refuses
+    /// every other port, recording each address asked for.
+    struct MockConnector {
+        addrs: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Connector for MockConnector {
+        async fn connect(
+            &self,
+            addr: &str,
+            _timeout: Duration,
+        ) -> std::io::Result<Box<dyn AsyncConn>> {
+            use tokio::io::AsyncWriteExt;
+
+            self.addrs.lock().push(addr.to_string());
+            if !addr.ends_with(":22") {
+                return Err(std::io::ErrorKind::ConnectionRefused.into());
+            }
+
+            let (client, mut server) = tokio::io::duplex(64);
+            server.write_all(b"SSH-2.0-MockSSH\r\n").await?;
+            Ok(Box::new(client))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scanner_mock_connector() {
+        let connector = Arc::new(MockConnector {
+            addrs: Mutex::new(Vec::new()),
+        });
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_connector(connector.clone())
+            .with_options(ScannerOptions {
+                emit_idle_events: false,
+                ..Default::default()
+            })
+            .build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        // TEST-NET-1 addresses: nothing would answer if a real connection was made.
+        for port in [22, 23] {
+            scanner.add_task(
+                actions!(
+                    ActionIsPortOpen {},
+                    ActionLocalAddr {},
+                    ActionMatchBanner {
+                        needles: vec!["ssh".to_string()],
+                        result_key: "Service".to_string(),
+                    }
+                ),
+                UrlParser::from_str(&format!("http://192.0.2.1:{}", port)).unwrap(),
+            );
+        }
+        scanner.execute_tasks();
+
+        let mut records = HashMap::new();
+        while records.len() < 2 {
+            let log = timeout(Duration::from_secs(5), logs.next())
+                .await
+                .unwrap()
+                .unwrap();
+            records.insert(log.task_seq, log);
+        }
+
+        let open = &records[&1];
+        let results = &open.header_response.actions_results;
+        assert_eq!(open.data, "SSH-2.0-MockSSH\r\n");
+        assert_eq!(results.get("IsPortOpen"), Some(&ActionValue::from("open")));
+        assert_eq!(results.get("Service"), Some(&ActionValue::from("ssh")));
+        assert!(!results.contains_key("LocalAddr"));
+
+        let refused = &records[&2];
+        assert_eq!(
+            refused.header_response.actions_results.get("IsPortOpen"),
+            Some(&ActionValue::from("refused"))
+        );
+
+        let mut addrs = connector.addrs.lock().clone();
+        addrs.sort();
+        assert_eq!(addrs, ["192.0.2.1:22", "192.0.2.1:23"]);
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_follow_up_uses_connector() {
+        let connector = Arc::new(MockConnector {
+            addrs: Mutex::new(Vec::new()),
+        });
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_connector(connector.clone())
+            .build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionDirEnum {
+                paths: vec!["/admin".to_string()],
+            }),
+            UrlParser::from_str("http://192.0.2.1:22").unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(
+            log.header_response
+                .actions_results
+                .contains_key("FoundPaths")
+        );
+
+        // The task's connection and the follow-up's both went through the mock.
+        assert_eq!(
+            connector.addrs.lock().clone(),
+            ["192.0.2.1:22", "192.0.2.1:22"]
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[derive(Clone)]
+    struct ActionCapture {}
+
+    impl Action for ActionCapture {
+        fn name(&self) -> &'static str {
+            "Capture"
+        }
+
+        fn set_read_from_successfull_connection(&self) -> bool {
+            true
+        }
+
+        fn wait_for_data(&self) -> bool {
+            true
+        }
+
+        fn execute_after_successfull_connection(
+            &self,
+            _ctx: &ScanContext,
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+
+        fn execute_after_successfull_connection_and_read(
+            &self,
+            _ctx: &ScanContext,
+            raw_data: &[u8],
+            actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+            actions_results.insert(
+                self.name().to_string(),
+                String::from_utf8_lossy(raw_data).into_owned().into(),
+            );
+        }
+    }
+
+    /// [`ActionCapture`] with an [`Action::timeout`].
+    #[derive(Clone)]
+    struct ActionCaptureWithin {
+        limit: Duration,
+    }
+
+    impl Action for ActionCaptureWithin {
+        fn name(&self) -> &'static str {
+            "CaptureWithin"
+        }
+
+        fn set_read_from_successfull_connection(&self) -> bool {
+            true
+        }
+
+        fn wait_for_data(&self) -> bool {
+            true
+        }
+
+        fn timeout(&self) -> Option<Duration> {
+            Some(self.limit)
+        }
+
+        fn execute_after_successfull_connection(
+            &self,
+            _ctx: &ScanContext,
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+
+        fn execute_after_successfull_connection_and_read(
+            &self,
+            _ctx: &ScanContext,
+            raw_data: &[u8],
+            actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+            actions_results.insert(
+                self.name().to_string(),
+                String::from_utf8_lossy(raw_data).into_owned().into(),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_timeout_bounds_read() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let _conn = listener.accept().await;
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                timeout_ms: 10_000,
+                ..Default::default()
+            })
+            .build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(
+                ActionCapture {},
+                ActionCaptureWithin {
+                    limit: Duration::from_millis(50),
+                }
+            ),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        // Well before the scanner's 10s timeout.
+        let log = timeout(Duration::from_secs(2), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let results = log.header_response.actions_results;
+        assert_eq!(
+            results.get("CaptureWithin_timeout"),
+            Some(&ActionValue::Bool(true))
+        );
+        assert!(!results.contains_key("Capture_timeout"));
+        assert_eq!(results.get("CaptureWithin"), Some(&ActionValue::from("")));
+
+        scanner.shutdown_graceful().await;
+    }
+
+    /// Reads like [`ActionCapture`], without waiting for data.
+    #[derive(Clone)]
+    struct ActionPeek {}
+
+    impl Action for ActionPeek {
+        fn name(&self) -> &'static str {
+            "Peek"
+        }
+
+        fn set_read_from_successfull_connection(&self) -> bool {
+            true
+        }
+
+        fn execute_after_successfull_connection(
+            &self,
+            _ctx: &ScanContext,
+            _actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+        }
+
+        fn execute_after_successfull_connection_and_read(
+            &self,
+            _ctx: &ScanContext,
+            raw_data: &[u8],
+            actions_results: &mut HashMap<String, ActionValue>,
+        ) {
+            actions_results.insert(
+                self.name().to_string(),
+                ActionValue::Int(raw_data.len() as i64),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scanner_read_waits_only_when_asked() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        // A silent peer, like a service waiting for the client to speak first.
+        tokio::spawn(async move {
+            let mut conns = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                conns.push(socket);
+            }
+        });
+
+        let timeout_ms = 300;
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                timeout_ms,
+                ..Default::default()
+            })
+            .build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+        let target = UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap();
+
+        for todo in [
+            actions!(ActionPeek {}),
+            actions!(ActionMatchBanner {
+                needles: vec!["ssh".to_string()],
+                result_key: "Ssh".to_string(),
+            }),
+        ] {
+            let started = std::time::Instant::now();
+            scanner.add_task(todo, target.clone());
+            scanner.execute_tasks();
+
+            let log = timeout(Duration::from_secs(5), logs.next())
+                .await
+                .unwrap()
+                .unwrap();
+            let elapsed = started.elapsed();
+            let results = log.header_response.actions_results;
+
+            match results.get("Peek") {
+                Some(read) => {
+                    assert_eq!(read, &ActionValue::Int(0));
+                    assert!(elapsed < Duration::from_millis(timeout_ms));
+                }
+                None => {
+                    assert_eq!(results.get("Ssh"), Some(&ActionValue::from("none")));
+                    assert!(elapsed >= Duration::from_millis(timeout_ms));
+                }
+            }
+        }
+
+        scanner.shutdown_graceful().await;
+    }
+
+    /// Serves `GET` requests, answering each path with the matching canned response.
+    ///
+    /// Connections are served concurrently, so one that never sends a request
+    /// (e.g. the task's own connection) doesn't block the others.
+    async fn spawn_http_responder(routes: Vec<(String, String)>) -> u16 {
+        spawn_http_responder_on("127.0.0.1:0", routes).await
+    }
+
+    /// Same as [`spawn_http_responder`], listening on `addr`.
+    async fn spawn_http_responder_on(addr: &str, routes: Vec<(String, String)>) -> u16 {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let routes = Arc::new(routes);
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let routes = routes.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let Ok(n) = socket.read(&mut buf).await else {
+                        return;
+                    };
+                    let request = String::from_utf8_lossy(&buf[..n]).into_owned();
+                    let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+                    if let Some((_, response)) = routes.iter().find(|(p, _)| p == path) {
+                        socket.write_all(response.as_bytes()).await.ok();
+                    }
+                });
+            }
+        });
+
+        port
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_follow_redirects() {
+        let redirect = |location: &str| {
+            format!(
+                "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\n\r\n",
+                location
+            )
+        };
+
+        let second = spawn_http_responder(vec![
+            ("/next".to_string(), redirect("/done")),
+            (
+                "/done".to_string(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok".to_string(),
+            ),
+        ])
+        .await;
+        let first = spawn_http_responder(vec![(
+            "/".to_string(),
+            redirect(&format!("http://127.0.0.1:{}/next", second)),
+        )])
+        .await;
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        for max_hops in [5, 1] {
+            scanner.add_task(
+                actions!(ActionFollowRedirects { max_hops }),
+                UrlParser::from_str(&format!("http://127.0.0.1:{}", first)).unwrap(),
+            );
+        }
+        scanner.execute_tasks();
+
+        let mut results = HashMap::new();
+        while results.len() < 2 {
+            let log = timeout(Duration::from_secs(5), logs.next())
+                .await
+                .unwrap()
+                .unwrap();
+            results.insert(log.task_seq, log.header_response.actions_results);
+        }
+
+        let followed = &results[&1];
+        assert_eq!(
+            followed.get("FinalLocation"),
+            Some(&ActionValue::from(format!(
+                "http://127.0.0.1:{}/done",
+                second
+            )))
+        );
+        assert_eq!(followed.get("RedirectHops"), Some(&ActionValue::Int(2)));
+
+        let bounded = &results[&2];
+        assert_eq!(
+            bounded.get("FinalLocation"),
+            Some(&ActionValue::from(format!(
+                "http://127.0.0.1:{}/next",
+                second
+            )))
+        );
+        assert_eq!(bounded.get("RedirectHops"), Some(&ActionValue::Int(1)));
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_follow_redirects_sends_host() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Virtual-host style server: only redirects requests naming it in `Host`.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let Ok(n) = socket.read(&mut buf).await else {
+                        return;
+                    };
+                    let request = String::from_utf8_lossy(&buf[..n]).into_owned();
+                    let response = if !request.contains("\r\nHost: 127.0.0.1\r\n") {
+                        "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n"
+                    } else if request.starts_with("GET / ") {
+                        "HTTP/1.1 302 Found\r\nLocation: /done\r\nContent-Length: 0\r\n\r\n"
+                    } else {
+                        "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok"
+                    };
+                    socket.write_all(response.as_bytes()).await.ok();
+                });
+            }
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionFollowRedirects { max_hops: 5 }),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let results = log.header_response.actions_results;
+
+        assert_eq!(
+            results.get("FinalLocation"),
+            Some(&ActionValue::from(format!(
+                "http://127.0.0.1:{}/done",
+                port
+            )))
+        );
+        assert_eq!(results.get("RedirectHops"), Some(&ActionValue::Int(1)));
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_follow_redirects_relative_locations() {
+        let redirect = |location: &str| {
+            format!(
+                "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\n\r\n",
+                location
+            )
+        };
+
+        let second = spawn_http_responder(vec![
+            ("/dir/page".to_string(), redirect("next")),
+            ("/dir/next".to_string(), redirect("../done")),
+            (
+                "/done".to_string(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok".to_string(),
+            ),
+        ])
+        .await;
+        let first = spawn_http_responder(vec![(
+            "/".to_string(),
+            redirect(&format!("//127.0.0.1:{}/dir/page", second)),
+        )])
+        .await;
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionFollowRedirects { max_hops: 5 }),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", first)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let results = log.header_response.actions_results;
+
+        assert_eq!(
+            results.get("FinalLocation"),
+            Some(&ActionValue::from(format!(
+                "http://127.0.0.1:{}/done",
+                second
+            )))
+        );
+        assert_eq!(results.get("RedirectHops"), Some(&ActionValue::Int(3)));
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_follow_redirects_ipv6() {
+        let port = spawn_http_responder_on(
+            "[::1]:0",
+            vec![
+                (
+                    "/".to_string(),
+                    "HTTP/1.1 301 Moved Permanently\r\nLocation: /done\r\n\r\n".to_string(),
+                ),
+                (
+                    "/done".to_string(),
+                    "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok".to_string(),
+                ),
+            ],
+        )
+        .await;
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionFollowRedirects { max_hops: 5 }),
+            UrlParser::from_str(&format!("http://[::1]:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let results = log.header_response.actions_results;
+
+        assert_eq!(
+            results.get("FinalLocation"),
+            Some(&ActionValue::from(format!("http://[::1]:{}/done", port)))
+        );
+        assert_eq!(results.get("RedirectHops"), Some(&ActionValue::Int(1)));
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_dir_enum() {
+        let port = spawn_http_responder(vec![
+            (
+                "/admin".to_string(),
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok".to_string(),
+            ),
+            (
+                "/backup".to_string(),
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string(),
+            ),
+            (
+                "/private".to_string(),
+                "HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n".to_string(),
+            ),
+        ])
+        .await;
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionDirEnum {
+                paths: ["/admin", "/backup", "private", "/missing"]
+                    .map(String::from)
+                    .to_vec(),
+            }),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            log.header_response.actions_results.get("FoundPaths"),
+            Some(&ActionValue::from(vec![
+                "/admin".to_string(),
+                "/private".to_string()
+            ]))
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_write_before_read() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        // Echo server
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut ping = [0u8; 6];
+            socket.read_exact(&mut ping).await.unwrap();
+            socket.write_all(&ping).await.unwrap();
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionSendPing {}, ActionCapture {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            log.header_response.actions_results.get("Capture"),
+            Some(&ActionValue::from("PING\r\n"))
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[test]
+    fn test_tasks_from_lines() {
+        let lines: Vec<String> = [
+            "http://127.0.0.1:80",
+            "ftp://example.com",
+            "https://[::1]:443",
+            "",
+            "http://exa$mple.com",
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+
+        let (tasks, errors) = tasks_from_lines(&lines, || actions!(ActionIsPortOpen {}));
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].target.port, 80);
+        assert_eq!(tasks[1].target.target_type, TargetType::IPv6);
+
+        let indices: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![1, 3, 4]);
+        assert!(matches!(errors[1].1, UrlParserErrors::UrlEmpty));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_scanner_idle_counters_stress() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                drop(socket);
+            }
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                batch_size: 32,
+                timeout_ms: 2_000,
+                ..Default::default()
+            })
+            .build();
+        scanner.execute_tasks();
+
+        const ROUNDS: usize = 10;
+        const TASKS_PER_ROUND: usize = 200;
+
+        for round in 1..=ROUNDS {
+            let tasks = (0..TASKS_PER_ROUND)
+                .map(|i| {
+                    // Mix connecting tasks with DNS targets, which finish immediately.
+                    let url = if i % 2 == 0 {
+                        format!("http://127.0.0.1:{}", port)
+                    } else {
+                        "http://example.com".to_string()
+                    };
+                    Task::new(actions!(ActionIsPortOpen {}), UrlParser::new(&url).unwrap())
+                })
+                .collect();
+            scanner.add_multiple_tasks(tasks);
+
+            timeout(Duration::from_secs(10), scanner.await_idle())
+                .await
+                .expect("scanner did not reach idle");
+
+            let status = scanner.describe();
+            assert_eq!(scanner.total_tasks_on_queue(), 0);
+            assert_eq!(status.pending_tasks, 0);
+            assert_eq!(status.active_tasks, 0);
+            assert_eq!(status.completed_tasks, round * TASKS_PER_ROUND);
+        }
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_scanner_task_seq_distinct() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let url = format!("http://127.0.0.1:{}", port);
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        let task = Task::new(actions!(ActionIsPortOpen {}), UrlParser::new(&url).unwrap());
+        scanner.add_task(actions!(ActionIsPortOpen {}), UrlParser::new(&url).unwrap());
+        scanner.add_multiple_tasks(vec![task.clone(), task]);
+        scanner.execute_tasks();
+
+        let mut seqs = Vec::new();
+        for _ in 0..3 {
+            let log = timeout(Duration::from_secs(5), logs.next())
+                .await
+                .unwrap()
+                .unwrap();
+            seqs.push(log.task_seq);
+        }
+        seqs.sort();
+
+        assert_eq!(seqs, vec![1, 2, 3]);
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_raw_data_shared_by_read_actions() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        // The banner is sent once, there is nothing left for a second read.
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket.write_all(b"SSH-2.0-OpenSSH_9.6\r\n").await.unwrap();
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(
+                ActionCapture {},
+                ActionMatchBanner {
+                    needles: vec!["openssh".to_string()],
+                    result_key: "Ssh".to_string(),
+                }
+            ),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(log.data, "SSH-2.0-OpenSSH_9.6\r\n");
+        assert_eq!(
+            log.header_response.actions_results.get("Capture"),
+            Some(&ActionValue::from("SSH-2.0-OpenSSH_9.6\r\n"))
+        );
+        assert_eq!(
+            log.header_response.actions_results.get("Ssh"),
+            Some(&ActionValue::from("openssh"))
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_max_read_bytes_truncates() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket.write_all(&[b'A'; 100]).await.unwrap();
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new()
+            .with_options(ScannerOptions {
+                max_read_bytes: 16,
+                ..Default::default()
+            })
+            .build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionCapture {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(log.data.len(), 16);
+        assert_eq!(
+            log.header_response.actions_results.get("Truncated"),
+            Some(&ActionValue::from(true))
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_default_max_read_bytes_truncates() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket.write_all(&[b'A'; 600]).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionCapture {}),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(log.data.len(), ScannerOptions::default().max_read_bytes);
+        assert_eq!(
+            log.header_response.actions_results.get("Truncated"),
+            Some(&ActionValue::from(true))
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_match_banner() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket.write_all(b"Server: nginx/1.25\r\n").await.unwrap();
+        });
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        scanner.add_task(
+            actions!(ActionMatchBanner {
+                needles: vec!["nginx".to_string(), "apache".to_string()],
+                result_key: "WebServer".to_string(),
+            }),
+            UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        );
+        scanner.execute_tasks();
+
+        let log = timeout(Duration::from_secs(5), logs.next())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            log.header_response.actions_results.get("WebServer"),
+            Some(&ActionValue::from("nginx"))
+        );
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[tokio::test]
+    async fn test_scanner_action_if_runs_only_when_matching() {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                socket.write_all(b"SSH-2.0-OpenSSH_9.6\r\n").await.ok();
+            }
+        });
+
+        let banner_if = |state: &str| ActionIf {
+            condition_key: "IsPortOpen".to_string(),
+            condition_value: state.to_string(),
+            then: actions!(ActionMatchBanner {
+                needles: vec!["openssh".to_string()],
+                result_key: "Ssh".to_string(),
+            }),
+        };
+
+        let scanner = Scanner::<StructuredFormatter>::new().build();
+        let mut logs = scanner.get_logs_stream().await.unwrap();
+
+        let url = UrlParser::from_str(&format!("http://127.0.0.1:{}", port)).unwrap();
+        scanner.add_task(
+            actions!(ActionIsPortOpen {}, banner_if("open")),
+            url.clone(),
+        );
+        scanner.add_task(actions!(ActionIsPortOpen {}, banner_if("closed")), url);
+        scanner.execute_tasks();
+
+        for _ in 0..2 {
+            let log = timeout(Duration::from_secs(5), logs.next())
+                .await
+                .unwrap()
+                .unwrap();
+            let results = &log.header_response.actions_results;
+
+            assert_eq!(results.get("IsPortOpen"), Some(&ActionValue::from("open")));
+            match log.task_seq {
+                1 => assert_eq!(results.get("Ssh"), Some(&ActionValue::from("openssh"))),
+                _ => assert_eq!(results.get("Ssh"), None),
+            }
+        }
+
+        scanner.shutdown_graceful().await;
+    }
+
+    #[test]
+    fn test_json_formatter_typed_action_values() {
+        let mut actions_results = HashMap::new();
+        actions_results.insert(
+            "OpenPorts".to_string(),
+            ActionValue::from(vec!["22".to_string(), "80".to_string()]),
+        );
+        actions_results.insert("port".to_string(), 443u16.into());
+        actions_results.insert("TLS".to_string(), true.into());
+        actions_results.insert("IsPortOpen".to_string(), "open".into());
+
+        let json = JsonFormatter.format(7, actions_results.clone(), b"");
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let results = &value["header_response"]["actions_results"];
+
+        assert_eq!(results["OpenPorts"], serde_json::json!(["22", "80"]));
+        assert_eq!(results["port"], serde_json::json!(443));
+        assert_eq!(results["TLS"], serde_json::json!(true));
+        assert_eq!(results["IsPortOpen"], serde_json::json!("open"));
+
+        // Untagged values deserialize back to the same variants.
+        let record: LogRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(record.header_response.actions_results, actions_results);
+    }
+
+    #[test]
+    fn test_nmap_greppable_formatter() {
+        let formatter = NmapGreppableFormatter;
+
+        let mut actions_results = HashMap::new();
+        actions_results.insert("IsPortOpen".to_string(), ActionValue::from("open"));
+        actions_results.insert("target".to_string(), "127.0.0.1".into());
+        actions_results.insert("port".to_string(), 80u16.into());
+
+        assert_eq!(
+            formatter.format(1, actions_results.clone(), &[]),
+            "Host: 127.0.0.1 ()\tPorts: 80/open/tcp//http///"
+        );
+
+        actions_results.insert("IsPortOpen".to_string(), "timeout".into());
+        actions_results.insert("port".to_string(), 9999u16.into());
+        assert_eq!(
+            formatter.format(2, actions_results.clone(), &[]),
+            "Host: 127.0.0.1 ()\tPorts: 9999/filtered/tcp/////"
+        );
+
+        actions_results.insert("IsPortOpen".to_string(), "refused".into());
+        assert_eq!(
+            formatter.format(3, actions_results, &[]),
+            "Host: 127.0.0.1 ()\tPorts: 9999/closed/tcp/////"
+        );
+
+        assert!(formatter.is_idle_signal(&formatter.idle_output()));
+        assert!(formatter.idle_output().starts_with('#'));
+    }
 }
@@ -0,0 +1,259 @@
+//! # CIDR
+//!
+//! Expands CIDR ranges (`192.168.1.0/24`, `2001:db8::/120`) into the addresses
+//! they contain, e.g. to build one scan target per host.
+//!
+//! The addresses are produced lazily by [`Ipv4CidrIter`] / [`Ipv6CidrIter`], so
+//! even huge ranges never allocate. Every address of the range is yielded,
+//! including the network and broadcast addresses. Host bits set in the input
+//! (`192.168.1.7/24`) are ignored.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use stalkermap::utils::cidr::expand_ipv4;
+//! use std::net::Ipv4Addr;
+//!
+//! let addrs: Vec<Ipv4Addr> = expand_ipv4("10.0.0.0/30").unwrap().collect();
+//! assert_eq!(addrs.len(), 4);
+//! assert_eq!(addrs[3], Ipv4Addr::new(10, 0, 0, 3));
+//! ```
+use std::{
+    error::Error,
+    fmt::Display,
+    net::{Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+/// Represents possible errors when parsing a CIDR range.
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum CidrError {
+    /// The input is not in the `<address>/<prefix>` form.
+    InvalidSyntax,
+    /// The address part is not a valid IP address of the expected family.
+    InvalidAddress(String),
+    /// The prefix is not a number, or is longer than the address (32 or 128 bits).
+    InvalidPrefix(String),
+}
+
+impl Display for CidrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSyntax => {
+                write!(f, "Invalid CIDR => <address>/<prefix> expected")
+            }
+            Self::InvalidAddress(addr) => {
+                write!(f, "Invalid CIDR address => {}", addr)
+            }
+            Self::InvalidPrefix(prefix) => {
+                write!(f, "Invalid CIDR prefix => {}", prefix)
+            }
+        }
+    }
+}
+
+impl Error for CidrError {}
+
+/// Iterator over the IPv4 addresses of a range, returned by [`expand_ipv4`].
+#[derive(Debug, Clone)]
+pub struct Ipv4CidrIter {
+    next: u32,
+    last: u32,
+    done: bool,
+}
+
+impl Iterator for Ipv4CidrIter {
+    type Item = Ipv4Addr;
+
+    fn next(&mut self) -> Option<Ipv4Addr> {
+        if self.done {
+            return None;
+        }
+        let addr = self.next;
+        match addr == self.last {
+            true => self.done = true,
+            false => self.next += 1,
+        }
+        Some(Ipv4Addr::from(addr))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.done {
+            true => (0, Some(0)),
+            false => {
+                let remaining = usize::try_from(self.last - self.next).ok();
+                let remaining = remaining.and_then(|n| n.checked_add(1));
+                (remaining.unwrap_or(usize::MAX), remaining)
+            }
+        }
+    }
+}
+
+/// Iterator over the IPv6 addresses of a range, returned by [`expand_ipv6`].
+#[derive(Debug, Clone)]
+pub struct Ipv6CidrIter {
+    next: u128,
+    last: u128,
+    done: bool,
+}
+
+impl Iterator for Ipv6CidrIter {
+    type Item = Ipv6Addr;
+
+    fn next(&mut self) -> Option<Ipv6Addr> {
+        if self.done {
+            return None;
+        }
+        let addr = self.next;
+        match addr == self.last {
+            true => self.done = true,
+            false => self.next += 1,
+        }
+        Some(Ipv6Addr::from(addr))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.done {
+            true => (0, Some(0)),
+            false => {
+                let remaining = usize::try_from(self.last - self.next).ok();
+                let remaining = remaining.and_then(|n| n.checked_add(1));
+                (remaining.unwrap_or(usize::MAX), remaining)
+            }
+        }
+    }
+}
+
+/// Splits `cidr` into its address and prefix length (at most `max_prefix`).
+fn split_cidr(cidr: &str, max_prefix: u32) -> Result<(&str, u32), CidrError> {
+    let (addr, prefix) = cidr
+        .trim()
+        .split_once('/')
+        .ok_or(CidrError::InvalidSyntax)?;
+
+    match prefix.parse::<u32>() {
+        Ok(len) if len <= max_prefix && prefix.bytes().all(|b| b.is_ascii_digit()) => {
+            Ok((addr, len))
+        }
+        _ => Err(CidrError::InvalidPrefix(prefix.to_string())),
+    }
+}
+
+/// Returns every IPv4 address of `cidr` (e.g. `"192.168.1.0/24"`).
+///
+/// # Errors
+/// Returns [`CidrError`] if `cidr` is not an `<IPv4>/<0-32>` range.
+///
+/// # Example
+/// ```rust,no_run
+/// use stalkermap::utils::cidr::expand_ipv4;
+///
+/// for addr in expand_ipv4("192.168.1.0/24").unwrap() {
+///     println!("{}", addr);
+/// }
+/// ```
+pub fn expand_ipv4(cidr: &str) -> Result<Ipv4CidrIter, CidrError> {
+    let (addr, prefix) = split_cidr(cidr, 32)?;
+    let addr = Ipv4Addr::from_str(addr).map_err(|_| CidrError::InvalidAddress(addr.to_string()))?;
+
+    let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+    let network = u32::from(addr) & mask;
+
+    Ok(Ipv4CidrIter {
+        next: network,
+        last: network | !mask,
+        done: false,
+    })
+}
+
+/// Returns every IPv6 address of `cidr` (e.g. `"2001:db8::/120"`).
+///
+/// Large IPv6 ranges hold far more addresses than can be scanned, so callers
+/// usually want a long prefix (`/112` or more).
+///
+/// # Errors
+/// Returns [`CidrError`] if `cidr` is not an `<IPv6>/<0-128>` range.
+pub fn expand_ipv6(cidr: &str) -> Result<Ipv6CidrIter, CidrError> {
+    let (addr, prefix) = split_cidr(cidr, 128)?;
+    let addr = Ipv6Addr::from_str(addr).map_err(|_| CidrError::InvalidAddress(addr.to_string()))?;
+
+    let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+    let network = u128::from(addr) & mask;
+
+    Ok(Ipv6CidrIter {
+        next: network,
+        last: network | !mask,
+        done: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cidr_expand_ipv4() {
+        let addrs: Vec<Ipv4Addr> = expand_ipv4("192.168.1.5/30").unwrap().collect();
+        assert_eq!(
+            addrs,
+            vec![
+                Ipv4Addr::new(192, 168, 1, 4),
+                Ipv4Addr::new(192, 168, 1, 5),
+                Ipv4Addr::new(192, 168, 1, 6),
+                Ipv4Addr::new(192, 168, 1, 7),
+            ]
+        );
+
+        let single: Vec<Ipv4Addr> = expand_ipv4("10.0.0.1/32").unwrap().collect();
+        assert_eq!(single, vec![Ipv4Addr::new(10, 0, 0, 1)]);
+
+        let mut all = expand_ipv4("0.0.0.0/0").unwrap();
+        assert_eq!(all.size_hint().1, Some(1 << 32));
+        assert_eq!(all.next(), Some(Ipv4Addr::UNSPECIFIED));
+
+        let mut top = expand_ipv4("255.255.255.254/31").unwrap();
+        assert_eq!(top.nth(1), Some(Ipv4Addr::BROADCAST));
+        assert_eq!(top.next(), None);
+    }
+
+    #[test]
+    fn test_cidr_expand_ipv6() {
+        let addrs: Vec<Ipv6Addr> = expand_ipv6("2001:db8::/126").unwrap().collect();
+        assert_eq!(addrs.len(), 4);
+        assert_eq!(addrs[0], Ipv6Addr::from_str("2001:db8::").unwrap());
+        assert_eq!(addrs[3], Ipv6Addr::from_str("2001:db8::3").unwrap());
+
+        let mut all = expand_ipv6("::/0").unwrap();
+        assert_eq!(all.size_hint(), (usize::MAX, None));
+        assert_eq!(all.next(), Some(Ipv6Addr::UNSPECIFIED));
+    }
+
+    #[test]
+    fn test_cidr_invalid_ranges() {
+        assert_eq!(
+            expand_ipv4("192.168.1.0/33").unwrap_err(),
+            CidrError::InvalidPrefix("33".to_string())
+        );
+        assert_eq!(
+            expand_ipv4("192.168.1.0/+8").unwrap_err(),
+            CidrError::InvalidPrefix("+8".to_string())
+        );
+        assert_eq!(
+            expand_ipv4("192.168.1.0").unwrap_err(),
+            CidrError::InvalidSyntax
+        );
+        assert_eq!(
+            expand_ipv4("192.168.1/24").unwrap_err(),
+            CidrError::InvalidAddress("192.168.1".to_string())
+        );
+        assert_eq!(
+            expand_ipv4("::1/32").unwrap_err(),
+            CidrError::InvalidAddress("::1".to_string())
+        );
+        assert_eq!(
+            expand_ipv6("::1/129").unwrap_err(),
+            CidrError::InvalidPrefix("129".to_string())
+        );
+    }
+}
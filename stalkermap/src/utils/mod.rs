@@ -5,11 +5,12 @@
 //!
 //! ## Overview
 //!
-//! This crate provides three main utilities for CLI application development:
+//! This crate provides four main utilities for CLI application development:
 //!
 //! 1. **Input Sanitization & Validation** - Validate user input with type checking and custom rules
 //! 2. **Interactive Terminal Interface** - Prompt users with validation loops until valid input is received
 //! 3. **URL Parsing** - Parse and validate HTTP/HTTPS URLs with comprehensive error handling
+//! 4. **CIDR Expansion** - Turn ranges such as `192.168.1.0/24` into the addresses they contain
 //!
 //! ## Quick Start
 //!
@@ -37,6 +38,11 @@
 //! - **Exact String Matching**: Require input to match a specific string
 //! - **Multiple Option Matching**: Allow input to match one of several valid options
 //! - **Range Validation**: Ensure numeric input falls within an inclusive range using `Sanitize::IsBetween(min, max)`
+//! - **Open Bounds**: Check a single bound with `Sanitize::IsAtLeast(min)` or `Sanitize::IsAtMost(max)`
+//! - **Non-Empty Validation**: Reject blank input using `Sanitize::NonEmpty`
+//! - **Host Validation**: Accept a DNS name or IP address without a scheme using `Sanitize::IsHost`
+//! - **List Validation**: Validate every element of a separated list (e.g. `80,443`) using
+//!   `Sanitize::ListOf { separator, inner }`
 //!
 //! #### Type & Range Validation Examples
 //!
@@ -81,6 +87,8 @@
 //! - **Booleans**: `DesiredType::Bool`
 //! - **Unsigned Integers**: `U8`, `U16`, `U32`, `U64`, `U128`
 //! - **Signed Integers**: `I8`, `I16`, `I32`, `I64`, `I128`
+//! - **Ports**: `Port` (`1`-`65535`)
+//! - **Encoded data**: `Hex`, `Base64`
 //!
 //! ### 2. Interactive Terminal Interface (`Terminal`)
 //!
@@ -119,6 +127,47 @@
 //! );
 //! ```
 //!
+//! #### Multiline Input
+//!
+//! `Terminal::ask_multiline` collects lines until a sentinel (or EOF), e.g. for pasting target lists:
+//!
+//! ```rust,no_run
+//! use stalkermap::utils::Terminal;
+//!
+//! let targets = Terminal::ask_multiline("Paste the targets, finish with END:", "END");
+//! ```
+//!
+//! #### Confirmation
+//!
+//! `Terminal::confirm` asks a yes/no question, returning the default on an empty answer:
+//!
+//! ```rust,no_run
+//! use stalkermap::utils::Terminal;
+//!
+//! let proceed = Terminal::confirm("Start the scan? (y/N)", false);
+//! ```
+//!
+//! #### Path Prompts
+//!
+//! `Terminal::ask_path` asks for a filesystem path that exists, or whose directory exists
+//! when `must_exist` is `false` (e.g. an output file):
+//!
+//! ```rust,no_run
+//! use stalkermap::utils::Terminal;
+//!
+//! let output = Terminal::ask_path("Save the report to:", false);
+//! ```
+//!
+//! #### Cancellable Prompts
+//!
+//! `Terminal::ask_cancellable` works like `ask`, but returns `None` when the user types the cancel word:
+//!
+//! ```rust,no_run
+//! use stalkermap::utils::{DesiredType, Sanitize, Terminal};
+//!
+//! let answer = Terminal::ask_cancellable("Verbose? (true/false)", &[Sanitize::IsType(DesiredType::Bool)], "cancel");
+//! ```
+//!
 //! #### Error Handling
 //!
 //! The terminal automatically handles validation errors and provides user-friendly messages:
@@ -177,6 +226,10 @@
 //! let url3 = UrlParser::try_from("https://example.com").unwrap();
 //! let ty2 = DesiredType::try_from("u8").unwrap();
 //!
+//! // Method 4: From an owned String (e.g. user input)
+//! let input = String::from("https://example.com");
+//! let url4 = UrlParser::from_string(input).unwrap();
+//!
 //! ```
 //!
 //! #### URL Components
@@ -198,8 +251,31 @@
 //! - `InvalidScheme`: Unsupported scheme (only HTTP/HTTPS allowed)
 //! - `InvalidSchemeSyntax`: Malformed scheme syntax
 //! - `InvalidTargetType`: Invalid hostname or IP address
-//! - `InvalidPort`: Port out of range (1-65535)
+//! - `PortNotNumeric`: Port is not a number
+//! - `PortOutOfRange`: Port out of range (1-65535), with the value that was given
 //! - `InvalidSize`: URL too short for parsing
+//! - `TooLong`: URL longer than `MAX_URL_LEN` (2048 bytes, see `UrlParser::with_max_len`)
+//!
+//! `UrlParser::decoded_path` returns the percent-decoded path, or a `PercentError`
+//! for a malformed `%XX` escape.
+//!
+//! ### 4. CIDR Ranges (`cidr`)
+//!
+//! `expand_ipv4` and `expand_ipv6` lazily yield every address of a range, so
+//! `10.0.0.0/8` costs no more memory than `10.0.0.0/30`:
+//!
+//! ```rust,no_run
+//! use stalkermap::utils::cidr::expand_ipv4;
+//!
+//! let hosts: Vec<String> = expand_ipv4("192.168.1.0/30")
+//!     .unwrap()
+//!     .map(|addr| format!("http://{}", addr))
+//!     .collect();
+//! assert_eq!(hosts.len(), 4);
+//! ```
+//!
+//! Malformed ranges return a `CidrError` (`InvalidSyntax`, `InvalidAddress` or
+//! `InvalidPrefix`).
 //!
 //! ## Complete Example: Interactive URL Input
 //!
@@ -276,6 +352,9 @@
 //! - Standard library only for most functionality
 //! - No external HTTP clients or networking libraries
 //! - Pure Rust implementation for maximum compatibility
+pub mod cidr;
+pub use cidr::{CidrError, expand_ipv4, expand_ipv6};
+
 pub mod sanitize;
 pub use sanitize::{DesiredType, Sanitize};
 
@@ -283,4 +362,4 @@ pub mod terminal;
 pub use terminal::Terminal;
 
 pub mod url;
-pub use url::{TargetType, UrlParser, UrlParserErrors};
+pub use url::{MAX_URL_LEN, PercentError, Scheme, TargetType, UrlParser, UrlParserErrors};
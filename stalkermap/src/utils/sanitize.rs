@@ -2,15 +2,20 @@
 //!
 //! This module provides a small yet flexible input validation framework for
 //! interactive CLI applications. It defines a set of composable validation
-//! filters (`Sanitize`) that can be applied to user-provided strings. Filters
-//! run in order and short-circuit on the first failure, returning a friendly
-//! error message describing what went wrong.
+//! filters (`Sanitize`) that can be applied to user-provided strings. Every
+//! filter runs in order and all failures are reported together, each with a
+//! friendly error message describing what went wrong.
 //!
 //! ## Features
 //! - Type validation for common Rust primitives via [`DesiredType`]
-//! - Exact string matching with [`Sanitize::MatchString`]
+//! - Exact string matching with [`Sanitize::MatchString`], or case/whitespace-insensitive
+//!   matching with [`Sanitize::MatchStringWith`]
 //! - Multiple-option matching with [`Sanitize::MatchStrings`]
 //! - Inclusive range validation with [`Sanitize::IsBetween`]
+//! - Open-ended bounds with [`Sanitize::IsAtLeast`] and [`Sanitize::IsAtMost`]
+//! - Non-empty input validation with [`Sanitize::NonEmpty`]
+//! - Host (DNS name or IP address, no scheme) validation with [`Sanitize::IsHost`]
+//! - Per-element validation of separated lists (e.g. `80,443,8080`) with [`Sanitize::ListOf`]
 //! - Human-readable error messages for invalid input
 //!
 //! ## When to use
@@ -68,17 +73,53 @@
 //! ```
 use std::{error::Error, fmt::Display};
 
+use crate::utils::TargetType;
+
 /// Represents a validation filter that can be applied to user input.
 ///
 /// - `MatchString`: ensures that the input matches a specific string.
+/// - `MatchStringWith`: like `MatchString`, optionally ignoring case (`ignore_case`) and/or
+///   surrounding and repeated whitespace (`trim`, e.g. `" port  scan "` matches `"port scan"`).
 /// - `MatchStrings`: ensures that the input matches one of the given options.
 /// - `IsType`: ensures that the input can be parsed into a certain [`DesiredType`].
 /// - `IsBetween`: ensures that a numeric input is within an inclusive range `[min, max]`.
+/// - `IsAtLeast`: ensures that a numeric input is greater than or equal to `min`.
+/// - `IsAtMost`: ensures that a numeric input is less than or equal to `max`.
+/// - `NonEmpty`: ensures that the input is not empty (after trimming).
+/// - `IsHost`: ensures that the input is a DNS name, an IPv4 or an IPv6 address (see [`TargetType`]).
+/// - `ListOf`: splits the input on `separator` and applies `inner` to every (trimmed) element.
+///
+/// # Example
+/// ```rust,no_run
+/// use stalkermap::utils::{DesiredType, Sanitize, Terminal};
+///
+/// let ports = Terminal::ask(
+///     "Ports to scan (e.g. 80,443):",
+///     &[Sanitize::ListOf {
+///         separator: ',',
+///         inner: Box::new(Sanitize::IsType(DesiredType::Port)),
+///     }],
+/// );
+/// println!("{}", ports.answer);
+/// ```
 pub enum Sanitize {
     MatchString(String),
+    MatchStringWith {
+        target: String,
+        ignore_case: bool,
+        trim: bool,
+    },
     MatchStrings(Vec<String>),
     IsBetween(isize, isize),
+    IsAtLeast(isize),
+    IsAtMost(isize),
     IsType(DesiredType),
+    NonEmpty,
+    IsHost,
+    ListOf {
+        separator: char,
+        inner: Box<Sanitize>,
+    },
 }
 
 /// Trait for input validation.  
@@ -97,6 +138,12 @@ trait Validate {
 /// - [`MatchString`]: did not match the required string.
 /// - [`MatchStrings`]: did not match any of the given options.
 /// - [`Between`]: did not match between the values given.
+/// - [`AtLeast`]: was lower than the minimum given.
+/// - [`AtMost`]: was greater than the maximum given.
+/// - [`Port`]: was not a valid port number (1-65535).
+/// - [`Host`]: was not a valid DNS name or IP address.
+/// - [`Empty`]: the value was empty.
+/// - [`ListElement`]: the element at `index` (zero-based) of a list failed `inner`.
 #[derive(Debug)]
 pub(crate) enum FilterErrorNot {
     Number(DesiredType),
@@ -105,6 +152,15 @@ pub(crate) enum FilterErrorNot {
     MatchString(String),
     MatchStrings(Vec<String>),
     Between(isize, isize),
+    AtLeast(isize),
+    AtMost(isize),
+    Port,
+    Host,
+    Empty,
+    ListElement {
+        index: usize,
+        inner: Box<FilterErrorNot>,
+    },
 }
 
 impl Display for FilterErrorNot {
@@ -122,6 +178,17 @@ impl Display for FilterErrorNot {
             Self::Between(n1, n2) => {
                 write!(f, "The value is not between {} and {}, try again!", n1, n2)
             }
+            Self::AtLeast(n) => write!(f, "The value must be at least {}, try again!", n),
+            Self::AtMost(n) => write!(f, "The value must be at most {}, try again!", n),
+            Self::Port => write!(f, "The value is not a valid port (1-65535), try again!"),
+            Self::Host => write!(
+                f,
+                "The value is not a valid host name or IP address, try again!"
+            ),
+            Self::Empty => write!(f, "The value cannot be empty, try again!"),
+            Self::ListElement { index, inner } => {
+                write!(f, "Element {} of the list: {}", index + 1, inner)
+            }
         }
     }
 }
@@ -158,6 +225,9 @@ impl Sanitize {
     /// - Trims whitespace before validation.
     /// - Stops and returns the first error encountered.
     /// - Returns the cleaned string if all filters pass.
+    ///
+    /// The prompts use [`Sanitize::execute_all`], which reports every failure instead.
+    #[allow(dead_code)]
     pub(crate) fn execute(answer: &str, filters: &[Sanitize]) -> Result<String, FilterErrorNot> {
         let clean_answer = answer.trim();
 
@@ -169,6 +239,29 @@ impl Sanitize {
         }
         Ok(clean_answer.to_string())
     }
+
+    /// Executes every filter against the given answer, collecting all failures.
+    ///
+    /// - Trims whitespace before validation.
+    /// - Runs every filter, even after one fails.
+    /// - Returns the cleaned string if all filters pass, otherwise the errors in filter order.
+    pub(crate) fn execute_all(
+        answer: &str,
+        filters: &[Sanitize],
+    ) -> Result<String, Vec<FilterErrorNot>> {
+        let clean_answer = answer.trim();
+
+        let errors: Vec<FilterErrorNot> = filters
+            .iter()
+            .filter_map(|filter| filter.validate(clean_answer).err())
+            .collect();
+
+        if errors.is_empty() {
+            Ok(clean_answer.to_string())
+        } else {
+            Err(errors)
+        }
+    }
 }
 
 impl Validate for Sanitize {
@@ -182,6 +275,26 @@ impl Validate for Sanitize {
                     Err(FilterErrorNot::MatchString(s.to_string()))
                 }
             }
+            Sanitize::MatchStringWith {
+                target,
+                ignore_case,
+                trim,
+            } => {
+                let normalize = |s: &str| {
+                    let s = if *trim {
+                        s.split_whitespace().collect::<Vec<_>>().join(" ")
+                    } else {
+                        s.to_string()
+                    };
+                    if *ignore_case { s.to_lowercase() } else { s }
+                };
+
+                if normalize(input) == normalize(target) {
+                    Ok(())
+                } else {
+                    Err(FilterErrorNot::MatchString(target.to_string()))
+                }
+            }
             Sanitize::MatchStrings(options) => {
                 if options.contains(&input.to_string()) {
                     Ok(())
@@ -200,6 +313,54 @@ impl Validate for Sanitize {
                 }
                 Err(e) => Err(e),
             },
+            Sanitize::IsAtLeast(min) => match DesiredType::Isize.parse(input) {
+                Ok(_) => {
+                    let input_parsed: isize = input.parse().unwrap_or_default();
+                    if input_parsed >= *min {
+                        Ok(())
+                    } else {
+                        Err(FilterErrorNot::AtLeast(*min))
+                    }
+                }
+                Err(e) => Err(e),
+            },
+            Sanitize::IsAtMost(max) => match DesiredType::Isize.parse(input) {
+                Ok(_) => {
+                    let input_parsed: isize = input.parse().unwrap_or_default();
+                    if input_parsed <= *max {
+                        Ok(())
+                    } else {
+                        Err(FilterErrorNot::AtMost(*max))
+                    }
+                }
+                Err(e) => Err(e),
+            },
+            Sanitize::NonEmpty => {
+                if input.trim().is_empty() {
+                    Err(FilterErrorNot::Empty)
+                } else {
+                    Ok(())
+                }
+            }
+            Sanitize::IsHost => {
+                let host = input.trim();
+                TargetType::is_ipv4(host)
+                    .or_else(|_| TargetType::is_ipv6(host))
+                    .or_else(|_| TargetType::is_dns(host))
+                    .map(|_| ())
+                    .map_err(|_| FilterErrorNot::Host)
+            }
+            Sanitize::ListOf { separator, inner } => {
+                for (index, element) in input.trim().split(*separator).enumerate() {
+                    if let Err(e) = inner.validate(element.trim()) {
+                        return Err(FilterErrorNot::ListElement {
+                            index,
+                            inner: Box::new(e),
+                        });
+                    }
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -214,7 +375,11 @@ impl Validate for Sanitize {
 /// - Unsigned integers: `U8`, `U16`, `U32`, `U64`, `U128`
 /// - Signed integers: `I8`, `I16`, `I32`, `I64`, `I128`
 /// - Platform-sized integer: `Isize`
-#[derive(Debug)]
+/// - Network port: `Port` (a `u16` other than `0`)
+/// - Encoded data: `Hex` (even-length, `[0-9a-fA-F]`) and `Base64` (standard alphabet, `=` padding)
+///
+/// Parsing (`FromStr`) accepts the names produced by `Display`, so every variant round-trips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DesiredType {
     String,
     Bool,
@@ -229,13 +394,17 @@ pub enum DesiredType {
     I64,
     I128,
     Isize,
+    Port,
+    Hex,
+    Base64,
 }
 
 impl std::str::FromStr for DesiredType {
     type Err = DesiredTypeFromStrErr;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "String" => Ok(DesiredType::String),
+            // "String" is kept for backwards compatibility.
+            "string" | "String" => Ok(DesiredType::String),
             "bool" => Ok(DesiredType::Bool),
             "u8" => Ok(DesiredType::U8),
             "u16" => Ok(DesiredType::U16),
@@ -248,6 +417,9 @@ impl std::str::FromStr for DesiredType {
             "i64" => Ok(DesiredType::I64),
             "i128" => Ok(DesiredType::I128),
             "isize" => Ok(DesiredType::Isize),
+            "port" => Ok(DesiredType::Port),
+            "hex" => Ok(DesiredType::Hex),
+            "base64" => Ok(DesiredType::Base64),
             s => Err(DesiredTypeFromStrErr::UnknownType(s.to_string())),
         }
     }
@@ -339,6 +511,30 @@ impl Display for DesiredTypeFromStrErr {
 impl Error for DesiredTypeFromStrErr {}
 
 impl DesiredType {
+    /// Returns every variant, in declaration order.
+    ///
+    /// Handy to list the accepted type names, e.g. in a help message.
+    pub fn all_variants() -> Vec<DesiredType> {
+        vec![
+            DesiredType::String,
+            DesiredType::Bool,
+            DesiredType::U8,
+            DesiredType::U16,
+            DesiredType::U32,
+            DesiredType::U64,
+            DesiredType::U128,
+            DesiredType::I8,
+            DesiredType::I16,
+            DesiredType::I32,
+            DesiredType::I64,
+            DesiredType::I128,
+            DesiredType::Isize,
+            DesiredType::Port,
+            DesiredType::Hex,
+            DesiredType::Base64,
+        ]
+    }
+
     /// Matches a [`DesiredType`] variant and applies the corresponding [`check_type!`] validation.
     ///
     /// Expands into a `match` that checks the input string against
@@ -396,10 +592,53 @@ impl DesiredType {
                 isize,
                 Err(FilterErrorNot::Number(DesiredType::Isize))
             ),
+            DesiredType::Port => match input.parse::<u16>() {
+                Ok(port) if port != 0 => Ok(()),
+                _ => Err(FilterErrorNot::Port),
+            },
+            DesiredType::Hex => {
+                if is_hex(input) {
+                    Ok(())
+                } else {
+                    Err(FilterErrorNot::String(DesiredType::Hex))
+                }
+            }
+            DesiredType::Base64 => {
+                if is_base64(input) {
+                    Ok(())
+                } else {
+                    Err(FilterErrorNot::String(DesiredType::Base64))
+                }
+            }
         }
     }
 }
 
+/// Returns `true` if `input` is a non-empty, even-length string of hex digits.
+fn is_hex(input: &str) -> bool {
+    !input.is_empty()
+        && input.len().is_multiple_of(2)
+        && input.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Returns `true` if `input` is padded standard base64 (RFC 4648 §4).
+///
+/// The length must be a multiple of 4 and `=` may only appear as one or two
+/// trailing padding characters.
+fn is_base64(input: &str) -> bool {
+    if input.is_empty() || !input.len().is_multiple_of(4) {
+        return false;
+    }
+
+    let data = input.trim_end_matches('=');
+    if input.len() - data.len() > 2 {
+        return false;
+    }
+
+    data.bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
 impl Display for DesiredType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -416,6 +655,9 @@ impl Display for DesiredType {
             Self::I64 => write!(f, "i64"),
             Self::I128 => write!(f, "i128"),
             Self::Isize => write!(f, "isize"),
+            Self::Port => write!(f, "port"),
+            Self::Hex => write!(f, "hex"),
+            Self::Base64 => write!(f, "base64"),
         }
     }
 }
@@ -430,6 +672,34 @@ mod tests {
         assert!(filter.validate("hello").is_ok());
     }
 
+    #[test]
+    fn test_sanitize_match_string_with() {
+        let filter = |ignore_case, trim| Sanitize::MatchStringWith {
+            target: "yes".to_string(),
+            ignore_case,
+            trim,
+        };
+
+        assert!(filter(true, false).validate("YES").is_ok());
+        assert!(filter(false, false).validate("YES").is_err());
+        assert!(filter(false, true).validate(" yes ").is_ok());
+        assert!(filter(false, false).validate(" yes ").is_err());
+        assert!(filter(true, true).validate("  Yes\t").is_ok());
+
+        let spaced = Sanitize::MatchStringWith {
+            target: " Port scan".to_string(),
+            ignore_case: true,
+            trim: true,
+        };
+        assert!(spaced.validate("port   SCAN").is_ok());
+
+        let res = filter(true, true).validate("no");
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "The value doesn't match with yes, try again!"
+        );
+    }
+
     #[test]
     fn test_sanitize_match_string_fail() {
         let filter = Sanitize::MatchString("hello".to_string());
@@ -463,6 +733,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sanitize_is_type_port() {
+        let filter = Sanitize::IsType(DesiredType::Port);
+        assert!(filter.validate("65535").is_ok());
+        assert!(filter.validate("70000").is_err());
+
+        let res = filter.validate("0");
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "The value is not a valid port (1-65535), try again!"
+        );
+        assert!(matches!(
+            DesiredType::try_from("port"),
+            Ok(DesiredType::Port)
+        ));
+        assert_eq!(DesiredType::Port.to_string(), "port");
+    }
+
+    #[test]
+    fn test_sanitize_is_type_hex() {
+        let filter = Sanitize::IsType(DesiredType::Hex);
+        assert!(filter.validate("deadbeef").is_ok());
+        assert!(filter.validate("DEADBEEF").is_ok());
+        assert!(filter.validate("xyz").is_err());
+        assert!(filter.validate("abc").is_err());
+        assert_eq!(
+            filter.validate("xyz").unwrap_err().to_string(),
+            "The value is not hex, try again!"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_is_type_base64() {
+        let filter = Sanitize::IsType(DesiredType::Base64);
+        assert!(filter.validate("c3RhbGtlcm1hcA==").is_ok());
+        assert!(filter.validate("cnVzdA==").is_ok());
+        assert!(filter.validate("YWJj").is_ok());
+        assert!(filter.validate("c3Rh=GtlcA==").is_err());
+        assert!(filter.validate("YWJ").is_err());
+        assert!(filter.validate("YQ===").is_err());
+        assert!(matches!(
+            DesiredType::try_from("base64"),
+            Ok(DesiredType::Base64)
+        ));
+    }
+
     #[test]
     fn test_sanitize_is_type_bool() {
         let filter = Sanitize::IsType(DesiredType::Bool);
@@ -496,6 +812,33 @@ mod tests {
         assert!(filter.validate("-20").is_err()); // overflow
     }
 
+    #[test]
+    fn test_sanitize_is_at_least() {
+        let filter = Sanitize::IsAtLeast(1);
+        assert!(filter.validate("0").is_err());
+        assert!(filter.validate("1").is_ok());
+        assert!(filter.validate("abc").is_err());
+
+        let res = Sanitize::execute("0", &[filter]);
+        assert_eq!(
+            format!("{}", res.unwrap_err()),
+            "The value must be at least 1, try again!"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_is_at_most() {
+        let filter = Sanitize::IsAtMost(65535);
+        assert!(filter.validate("70000").is_err());
+        assert!(filter.validate("65535").is_ok());
+
+        let res = Sanitize::execute("70000", &[filter]);
+        assert_eq!(
+            format!("{}", res.unwrap_err()),
+            "The value must be at most 65535, try again!"
+        );
+    }
+
     #[test]
     fn test_sanitize_is_type_from_str() {
         let filter = Sanitize::IsType("u8".parse::<DesiredType>().unwrap());
@@ -505,6 +848,22 @@ mod tests {
         assert!(filter.validate("abc").is_err());
     }
 
+    #[test]
+    fn test_sanitize_desired_type_display_round_trip() {
+        let variants = DesiredType::all_variants();
+        assert_eq!(variants.len(), 16);
+
+        for variant in variants {
+            let name = variant.to_string();
+            assert_eq!(name.parse::<DesiredType>().unwrap(), variant, "{}", name);
+        }
+
+        assert_eq!(
+            "String".parse::<DesiredType>().unwrap(),
+            DesiredType::String
+        );
+    }
+
     #[test]
     fn test_sanitize_is_type_try_from_str() {
         let filter = Sanitize::IsType(DesiredType::try_from("u8").unwrap());
@@ -579,4 +938,88 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_sanitize_execute_all_collects_errors() {
+        let filters = vec![
+            Sanitize::IsType(DesiredType::U8),
+            Sanitize::NonEmpty,
+            Sanitize::MatchStrings(vec![String::from("1"), String::from("2")]),
+        ];
+
+        let errors = Sanitize::execute_all("true", &filters).unwrap_err();
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "The value is not a u8, try again!",
+                "The value doesn't match with the options: 1, 2, try again!",
+            ]
+        );
+
+        assert_eq!(Sanitize::execute_all(" 2 ", &filters).unwrap(), "2");
+    }
+
+    #[test]
+    fn test_sanitize_non_empty() {
+        let filter = Sanitize::NonEmpty;
+        assert!(filter.validate("x").is_ok());
+        assert!(filter.validate("").is_err());
+        assert!(filter.validate("   ").is_err());
+
+        let res = Sanitize::execute("   ", &[Sanitize::NonEmpty]);
+        if let Err(e) = res {
+            assert_eq!(format!("{}", e), "The value cannot be empty, try again!");
+        } else {
+            panic!("expected an empty value error");
+        }
+    }
+
+    #[test]
+    fn test_sanitize_is_host() {
+        let filter = Sanitize::IsHost;
+        assert!(filter.validate("example.com").is_ok());
+        assert!(filter.validate("127.0.0.1").is_ok());
+        assert!(filter.validate("[::1]").is_ok());
+        assert!(filter.validate(" scanme.example.org ").is_ok());
+
+        assert!(matches!(
+            filter.validate("exa mple"),
+            Err(FilterErrorNot::Host)
+        ));
+        assert!(filter.validate("http://example.com").is_err());
+
+        let res = Sanitize::execute("exa mple", &[Sanitize::IsHost]);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "The value is not a valid host name or IP address, try again!"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_list_of() {
+        let filter = Sanitize::ListOf {
+            separator: ',',
+            inner: Box::new(Sanitize::IsType(DesiredType::U16)),
+        };
+        assert!(filter.validate("80,443").is_ok());
+        assert!(filter.validate(" 80 , 443 ,8080 ").is_ok());
+
+        let res = filter.validate("80,abc");
+        assert!(matches!(
+            res,
+            Err(FilterErrorNot::ListElement { index: 1, ref inner })
+                if matches!(**inner, FilterErrorNot::Number(DesiredType::U16))
+        ));
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Element 2 of the list: The value is not a u16, try again!"
+        );
+
+        // An empty element is reported like any other bad element.
+        assert!(matches!(
+            filter.validate("80,,443"),
+            Err(FilterErrorNot::ListElement { index: 1, .. })
+        ));
+    }
 }
@@ -2,7 +2,8 @@
 //!
 //! This module provides utilities for interacting with the terminal to
 //! request user input. It repeatedly prompts the user until the input
-//! satisfies the provided validation filters.
+//! satisfies the provided validation filters, printing every failed filter
+//! at once.
 //!
 //! The core functionality relies on [`Sanitize`] for input validation.
 //!
@@ -10,6 +11,9 @@
 //! - Continuously prompts the user until valid input is received.
 //! - Supports multiple validation rules simultaneously.
 //! - Returns the sanitized input wrapped in a [`Terminal`] struct.
+//! - Never spins on a closed stdin: prompts panic at EOF, while the `*_from`
+//!   variants (reading from any [`BufRead`]) return `None` instead.
+//! - [`Terminal::ask_limited`] gives up after a number of invalid answers.
 //!
 //! ## Usage
 //!
@@ -60,8 +64,50 @@
 //! );
 //! println!("The input: {}", input.answer);
 //! ```
+//!
+//! ### Example 4: Multiline input
+//! ```rust,no_run
+//! use stalkermap::utils::Terminal;
+//!
+//! let targets = Terminal::ask_multiline("Paste the targets, finish with END:", "END");
+//! println!("Got {} targets", targets.len());
+//! ```
+//!
+//! ### Example 5: Yes/no confirmation
+//! ```rust,no_run
+//! use stalkermap::utils::Terminal;
+//!
+//! if Terminal::confirm("Start the scan? (y/N)", false) {
+//!     println!("Scanning...");
+//! }
+//! ```
+//!
+//! ### Example 6: Cancellable prompt
+//! ```rust,no_run
+//! use stalkermap::utils::{Sanitize, Terminal};
+//!
+//! match Terminal::ask_cancellable("Port to scan (or cancel):", &[Sanitize::IsBetween(1, 65535)], "cancel") {
+//!     Some(input) => println!("Scanning port {}", input.answer),
+//!     None => println!("Aborted"),
+//! }
+//! ```
+//!
+//! ### Example 7: Output file path
+//! ```rust,no_run
+//! use stalkermap::utils::Terminal;
+//!
+//! // The file may not exist yet, but its directory must.
+//! let output = Terminal::ask_path("Save the report to:", false);
+//! println!("Writing to {}", output.display());
+//! ```
 use crate::utils::sanitize::Sanitize;
-use std::io;
+use std::{
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+};
+
+/// Panic message of the prompts that can't return without a valid answer.
+const STDIN_CLOSED: &str = "stdin was closed or unreadable before a valid answer was entered";
 /// A helper for repeatedly asking the user for input until it passes all [`Sanitize`] filters.  
 ///
 /// # Examples
@@ -105,30 +151,366 @@ pub struct Terminal {
 impl Terminal {
     /// Prints a question to the terminal and loops until a valid answer is received.  
     /// Returns a [`Terminal`] struct containing the accepted answer.
+    ///
+    /// # Panics
+    /// Panics if stdin reaches EOF (e.g. a closed pipe) or can't be read before a
+    /// valid answer, instead of prompting forever. Use [`Terminal::ask_from`] to
+    /// handle EOF.
     pub fn ask(question: &str, filters: &[Sanitize]) -> Terminal {
-        let answer: String = loop {
+        Self::ask_from(io::stdin().lock(), question, filters).expect(STDIN_CLOSED)
+    }
+
+    /// Same as [`Terminal::ask`], but reads from the given `reader`.
+    ///
+    /// EOF or a read error returns `None`, since no valid answer can follow.
+    /// A line that isn't valid UTF-8 is rejected like any invalid answer.
+    pub fn ask_from<R: BufRead>(
+        reader: R,
+        question: &str,
+        filters: &[Sanitize],
+    ) -> Option<Terminal> {
+        Self::prompt(reader, question, filters, None, None)
+    }
+
+    /// Same as [`Terminal::ask`], but gives up after `max_attempts` invalid answers.
+    ///
+    /// Returns `None` once every attempt failed the filters, or if stdin reaches
+    /// EOF or can't be read.
+    pub fn ask_limited(
+        question: &str,
+        filters: &[Sanitize],
+        max_attempts: usize,
+    ) -> Option<Terminal> {
+        Self::ask_limited_from(io::stdin().lock(), question, filters, max_attempts)
+    }
+
+    /// Same as [`Terminal::ask_limited`], but reads from the given `reader`.
+    pub fn ask_limited_from<R: BufRead>(
+        reader: R,
+        question: &str,
+        filters: &[Sanitize],
+        max_attempts: usize,
+    ) -> Option<Terminal> {
+        Self::prompt(reader, question, filters, Some(max_attempts), None)
+    }
+
+    /// Same as [`Terminal::ask`], but returns `None` when the user types `cancel_word`.
+    ///
+    /// The trimmed input is compared to `cancel_word` before the filters run, so
+    /// the cancel word doesn't have to pass them.
+    pub fn ask_cancellable(
+        question: &str,
+        filters: &[Sanitize],
+        cancel_word: &str,
+    ) -> Option<Terminal> {
+        Self::ask_cancellable_from(io::stdin().lock(), question, filters, cancel_word)
+    }
+
+    /// Same as [`Terminal::ask_cancellable`], but reads from the given `reader`.
+    ///
+    /// EOF or a read error also returns `None`, since no valid answer can follow.
+    pub fn ask_cancellable_from<R: BufRead>(
+        reader: R,
+        question: &str,
+        filters: &[Sanitize],
+        cancel_word: &str,
+    ) -> Option<Terminal> {
+        Self::prompt(reader, question, filters, None, Some(cancel_word))
+    }
+
+    /// Prompt loop shared by [`Terminal::ask_from`], [`Terminal::ask_limited_from`]
+    /// and [`Terminal::ask_cancellable_from`].
+    ///
+    /// Stops after `max_attempts` invalid answers if given, and returns `None`
+    /// when the trimmed input equals `cancel_word`.
+    fn prompt<R: BufRead>(
+        mut reader: R,
+        question: &str,
+        filters: &[Sanitize],
+        max_attempts: Option<usize>,
+        cancel_word: Option<&str>,
+    ) -> Option<Terminal> {
+        let mut attempts = 0;
+        while max_attempts.is_none_or(|max| attempts < max) {
+            attempts += 1;
             println!("{}", question);
-            let mut answer = String::new();
-
-            match io::stdin().read_line(&mut answer) {
-                Ok(_) => {
-                    let sanatized_answer = Sanitize::execute(answer.as_str(), filters);
-
-                    match sanatized_answer {
-                        Ok(data) => break data,
-                        Err(e) => {
-                            println!("{}", e);
-                            continue;
-                        }
-                    }
-                }
-                Err(_) => {
-                    eprintln!("Couldn't read line..");
-                    continue;
+
+            let answer = match read_answer(&mut reader) {
+                Answer::Line(answer) => answer,
+                Answer::Invalid => continue,
+                Answer::Closed => return None,
+            };
+
+            if cancel_word.is_some_and(|word| answer.trim() == word) {
+                return None;
+            }
+
+            match Sanitize::execute_all(answer.as_str(), filters) {
+                Ok(answer) => return Some(Terminal { answer }),
+                Err(errors) => errors.iter().for_each(|e| println!("{}", e)),
+            }
+        }
+        None
+    }
+
+    /// Asks for a filesystem path and loops until a usable one is entered.
+    ///
+    /// With `must_exist` the trimmed input must be an existing path; otherwise
+    /// only its parent directory has to exist, so the path can be created.
+    /// A relative path without a parent (e.g. `report.txt`) refers to the
+    /// current directory.
+    ///
+    /// # Panics
+    /// Panics if stdin reaches EOF before a valid path, like [`Terminal::ask`].
+    pub fn ask_path(question: &str, must_exist: bool) -> PathBuf {
+        Self::ask_path_from(io::stdin().lock(), question, must_exist).expect(STDIN_CLOSED)
+    }
+
+    /// Same as [`Terminal::ask_path`], but reads from the given `reader`.
+    ///
+    /// EOF or a read error returns `None`, since no valid answer can follow.
+    pub fn ask_path_from<R: BufRead>(
+        mut reader: R,
+        question: &str,
+        must_exist: bool,
+    ) -> Option<PathBuf> {
+        loop {
+            println!("{}", question);
+
+            let answer = match read_answer(&mut reader) {
+                Answer::Line(answer) => answer,
+                Answer::Invalid => continue,
+                Answer::Closed => return None,
+            };
+
+            let answer = answer.trim();
+            if answer.is_empty() {
+                println!("Please enter a path, try again!");
+                continue;
+            }
+
+            let path = PathBuf::from(answer);
+            if must_exist {
+                if path.exists() {
+                    return Some(path);
                 }
+                println!("The path {} doesn't exist, try again!", path.display());
+                continue;
+            }
+
+            let parent = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            if parent.is_dir() {
+                return Some(path);
+            }
+            println!(
+                "The directory {} doesn't exist, try again!",
+                parent.display()
+            );
+        }
+    }
+
+    /// Prints a question and reads lines from stdin until a line equal to `sentinel`.
+    ///
+    /// Reading also stops at EOF. Each line is returned without its line ending;
+    /// the sentinel line itself is not included.
+    pub fn ask_multiline(question: &str, sentinel: &str) -> Vec<String> {
+        Self::ask_multiline_from(io::stdin().lock(), question, sentinel)
+    }
+
+    /// Same as [`Terminal::ask_multiline`], but reads from the given `reader`.
+    ///
+    /// Useful for tests or for reading a block of input from a file.
+    /// A read error is treated like EOF.
+    pub fn ask_multiline_from<R: BufRead>(
+        reader: R,
+        question: &str,
+        sentinel: &str,
+    ) -> Vec<String> {
+        println!("{}", question);
+
+        reader
+            .lines()
+            .map_while(Result::ok)
+            .take_while(|line| line.trim() != sentinel)
+            .collect()
+    }
+
+    /// Asks a yes/no question and returns the answer as a `bool`.
+    ///
+    /// Accepts `y`, `yes`, `n` and `no` (case-insensitive). An empty answer
+    /// returns `default`; anything else prompts again.
+    pub fn confirm(question: &str, default: bool) -> bool {
+        Self::confirm_from(io::stdin().lock(), question, default)
+    }
+
+    /// Same as [`Terminal::confirm`], but reads from the given `reader`.
+    ///
+    /// EOF or a read error returns `default`.
+    pub fn confirm_from<R: BufRead>(mut reader: R, question: &str, default: bool) -> bool {
+        loop {
+            println!("{}", question);
+
+            let answer = match read_answer(&mut reader) {
+                Answer::Line(answer) => answer,
+                Answer::Invalid => continue,
+                Answer::Closed => return default,
             };
-        };
 
-        Terminal { answer }
+            match answer.trim().to_lowercase().as_str() {
+                "" => return default,
+                "y" | "yes" => return true,
+                "n" | "no" => return false,
+                _ => println!("Please answer y or n, try again!"),
+            }
+        }
+    }
+}
+
+/// Outcome of reading one answer from the prompt's reader.
+enum Answer {
+    /// A line was read (with its line ending).
+    Line(String),
+    /// The line couldn't be used (invalid UTF-8, interrupted read): prompt again.
+    Invalid,
+    /// EOF or an I/O error: no answer can follow.
+    Closed,
+}
+
+/// Reads one line of `reader` for a prompt.
+fn read_answer<R: BufRead>(reader: &mut R) -> Answer {
+    let mut answer = String::new();
+
+    match reader.read_line(&mut answer) {
+        Ok(0) => Answer::Closed,
+        Ok(_) => Answer::Line(answer),
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            println!("The input is not valid UTF-8, try again!");
+            Answer::Invalid
+        }
+        Err(e) if e.kind() == io::ErrorKind::Interrupted => Answer::Invalid,
+        Err(_) => Answer::Closed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::DesiredType;
+
+    #[test]
+    fn test_terminal_ask_multiline_until_sentinel() {
+        let input = "a\nb\nEND\nc\n".as_bytes();
+        let lines = Terminal::ask_multiline_from(input, "Targets:", "END");
+        assert_eq!(lines, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_terminal_ask_multiline_until_eof() {
+        let input = "a\r\nb".as_bytes();
+        let lines = Terminal::ask_multiline_from(input, "Targets:", "END");
+        assert_eq!(lines, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_terminal_ask_from_eof_returns_none() {
+        let filters = [Sanitize::IsType(DesiredType::Bool)];
+
+        assert!(Terminal::ask_from("".as_bytes(), "Bool?", &filters).is_none());
+        assert!(Terminal::ask_from("maybe\n".as_bytes(), "Bool?", &filters).is_none());
+
+        let input = Terminal::ask_from("maybe\ntrue\n".as_bytes(), "Bool?", &filters).unwrap();
+        assert_eq!(input.answer, "true");
+
+        // A line that isn't UTF-8 is rejected and the question asked again.
+        let input = Terminal::ask_from(&b"\xff\ntrue\n"[..], "Bool?", &filters).unwrap();
+        assert_eq!(input.answer, "true");
+        assert!(Terminal::ask_from(&[0xff, b'\n'][..], "Bool?", &filters).is_none());
+    }
+
+    #[test]
+    fn test_terminal_ask_limited() {
+        let filters = [Sanitize::IsType(DesiredType::Bool)];
+
+        let input =
+            Terminal::ask_limited_from("maybe\ntrue\n".as_bytes(), "Bool?", &filters, 2).unwrap();
+        assert_eq!(input.answer, "true");
+
+        assert!(
+            Terminal::ask_limited_from("maybe\nno\ntrue\n".as_bytes(), "Bool?", &filters, 2)
+                .is_none()
+        );
+        assert!(Terminal::ask_limited_from("".as_bytes(), "Bool?", &filters, 3).is_none());
+        assert!(Terminal::ask_limited_from("true\n".as_bytes(), "Bool?", &filters, 0).is_none());
+    }
+
+    #[test]
+    fn test_terminal_ask_cancellable() {
+        let filters = [Sanitize::IsType(DesiredType::Bool)];
+
+        assert!(
+            Terminal::ask_cancellable_from("cancel\n".as_bytes(), "Bool?", &filters, "cancel")
+                .is_none()
+        );
+
+        let input =
+            Terminal::ask_cancellable_from("maybe\ntrue\n".as_bytes(), "Bool?", &filters, "cancel")
+                .unwrap();
+        assert_eq!(input.answer, "true");
+
+        assert!(
+            Terminal::ask_cancellable_from("".as_bytes(), "Bool?", &filters, "cancel").is_none()
+        );
+    }
+
+    #[test]
+    fn test_terminal_ask_path() {
+        let dir = std::env::temp_dir();
+        let missing = dir.join("stalkermap-missing-dir-for-ask-path");
+
+        let input = format!("\n{}\n  {}  \n", missing.display(), dir.display());
+        let path = Terminal::ask_path_from(input.as_bytes(), "Path?", true).unwrap();
+        assert_eq!(path, dir);
+
+        let new_file = dir.join("stalkermap-report.txt");
+        let input = format!(
+            "{}\n{}\n",
+            missing.join("report.txt").display(),
+            new_file.display()
+        );
+        let path = Terminal::ask_path_from(input.as_bytes(), "Path?", false).unwrap();
+        assert_eq!(path, new_file);
+
+        let path = Terminal::ask_path_from("report.txt\n".as_bytes(), "Path?", false).unwrap();
+        assert_eq!(path, PathBuf::from("report.txt"));
+
+        assert!(Terminal::ask_path_from("".as_bytes(), "Path?", true).is_none());
+    }
+
+    #[test]
+    fn test_terminal_confirm_yes() {
+        assert!(Terminal::confirm_from("y\n".as_bytes(), "Continue?", false));
+        assert!(!Terminal::confirm_from(
+            "NO\n".as_bytes(),
+            "Continue?",
+            true
+        ));
+    }
+
+    #[test]
+    fn test_terminal_confirm_empty_uses_default() {
+        assert!(!Terminal::confirm_from("\n".as_bytes(), "Continue?", false));
+        assert!(Terminal::confirm_from("".as_bytes(), "Continue?", true));
+    }
+
+    #[test]
+    fn test_terminal_confirm_reprompts_on_invalid() {
+        assert!(Terminal::confirm_from(
+            "maybe\ny\n".as_bytes(),
+            "Continue?",
+            false
+        ));
     }
 }
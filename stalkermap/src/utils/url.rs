@@ -6,6 +6,7 @@
 //! - Supports `http` and `https` schemes
 //! - Host validation (`DNS`, `IPv4`, `IPv6`)
 //! - Custom error enum for precise error handling
+//! - Inputs longer than [`MAX_URL_LEN`] are rejected up front (see [`UrlParser::with_max_len`])
 //! - No external dependencies
 //!
 //! ## Example
@@ -31,9 +32,15 @@ use std::str::FromStr;
 use std::{
     error::Error,
     fmt::Display,
-    net::{Ipv4Addr, Ipv6Addr},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    num::{IntErrorKind, ParseIntError},
 };
 
+/// Default maximum length (in bytes) of a URL accepted by [`UrlParser::new`].
+///
+/// Longer inputs are rejected with [`UrlParserErrors::TooLong`] before any parsing happens.
+pub const MAX_URL_LEN: usize = 2048;
+
 /// Represents a parsed URL.
 ///
 /// Contains information about:
@@ -41,7 +48,7 @@ use std::{
 /// - [`TargetType`] (DNS, IPv4, IPv6)
 /// - Associated port (0 is the default and its not included on the url)
 /// - Full normalized URL
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct UrlParser {
     pub scheme: Scheme,
     pub target: String,
@@ -62,12 +69,22 @@ impl Display for UrlParser {
 }
 
 /// Represents the scheme of a URL (`http` or `https`).
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Scheme {
     Http,
     Https,
 }
 
+impl Scheme {
+    /// Returns the default port of the scheme (`80` for `http`, `443` for `https`).
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Self::Http => 80,
+            Self::Https => 443,
+        }
+    }
+}
+
 impl Display for Scheme {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -83,7 +100,7 @@ impl Display for Scheme {
 /// - `Dns`
 /// - `IPv4`
 /// - `IPv6`
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TargetType {
     Dns,
     IPv4,
@@ -108,12 +125,15 @@ impl TargetType {
     /// - Each label ≤ 63 characters
     /// - Cannot start or end with `-`
     /// - Only ASCII alphanumeric characters and `-` allowed
+    /// - A single trailing `.` (fully qualified name) is allowed, empty labels are not
     pub fn is_dns(target: &str) -> Result<TargetType, UrlParserErrors> {
-        if target.len() > 253 {
+        let name = target.strip_suffix('.').unwrap_or(target);
+
+        if name.len() > 253 {
             return Err(UrlParserErrors::InvalidTargetType);
         }
 
-        let valid: bool = target.split('.').all(|label| {
+        let valid: bool = name.split('.').all(|label| {
             if label.is_empty() || label.len() > 63 {
                 return false;
             }
@@ -145,24 +165,69 @@ impl TargetType {
     }
 
     /// Checks if the provided string is a valid IPv6 address.
+    ///
+    /// Accepts both the bracketed (`[::1]`) and unbracketed (`::1`) forms.
+    /// Unbalanced brackets (`[::1`, `::1]`) are rejected.
     pub fn is_ipv6(target: &str) -> Result<TargetType, UrlParserErrors> {
-        let clean_ip = target.trim_matches(['[', ']'].as_ref());
-        match Ipv6Addr::from_str(clean_ip) {
+        match Ipv6Addr::from_str(strip_ipv6_brackets(target)) {
             Ok(_) => Ok(TargetType::IPv6),
             Err(_) => Err(UrlParserErrors::InvalidTargetType),
         }
     }
 }
 
+/// Builds the normalized url, leaving the port out when it is `0`.
+fn format_url(scheme: &Scheme, target: &str, port: u16, subdirectory: &str) -> String {
+    format!(
+        "{}://{}{}{}",
+        scheme,
+        target,
+        match port {
+            0 => String::new(),
+            n => format!(":{}", n),
+        },
+        subdirectory
+    )
+}
+
+/// Removes one pair of enclosing brackets (`[::1]` -> `::1`), if present.
+fn strip_ipv6_brackets(target: &str) -> &str {
+    target
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .unwrap_or(target)
+}
+
 /// Represents possible errors when parsing a URL.
+///
+/// Like every public error enum of this crate, it is `#[non_exhaustive]`:
+/// new variants may be added, so matches outside the crate need a wildcard arm.
+///
+/// ```rust
+/// use stalkermap::utils::{UrlParser, UrlParserErrors};
+///
+/// let message = match UrlParser::new("ftp://example.com") {
+///     Ok(_) => "ok",
+///     Err(UrlParserErrors::InvalidSchemeSyntax) => "bad scheme",
+///     Err(_) => "other error",
+/// };
+/// assert_eq!(message, "bad scheme");
+/// ```
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum UrlParserErrors {
     UrlEmpty,
     InvalidSize,
     InvalidScheme,
     InvalidTargetType,
     InvalidSchemeSyntax,
-    InvalidPort,
+    /// The port contains no digits (e.g. `http://example.com:abc`).
+    PortNotNumeric,
+    /// The port doesn't fit in a `u16`; holds the parsed value (`u64::MAX` if even larger).
+    PortOutOfRange(u64),
+    NotAnIpTarget,
+    /// The input exceeds the maximum length (in bytes) held by the variant.
+    TooLong(usize),
 }
 
 impl Display for UrlParserErrors {
@@ -183,8 +248,20 @@ impl Display for UrlParserErrors {
             Self::InvalidSchemeSyntax => {
                 write!(f, "Invalid scheme sintax => http:// or https://")
             }
-            Self::InvalidPort => {
-                write!(f, "Invalid port => (1 -> 65,535)")
+            Self::PortNotNumeric => {
+                write!(f, "Invalid port => must be a number (1 -> 65,535)")
+            }
+            Self::PortOutOfRange(port) => {
+                write!(f, "Invalid port => {} is out of range (1 -> 65,535)", port)
+            }
+            Self::NotAnIpTarget => {
+                write!(
+                    f,
+                    "The target is not an IP address => Must be a IPV4 or IPV6"
+                )
+            }
+            Self::TooLong(max) => {
+                write!(f, "The url is too long => max {} bytes", max)
             }
         }
     }
@@ -192,6 +269,73 @@ impl Display for UrlParserErrors {
 
 impl Error for UrlParserErrors {}
 
+/// Maps a failed port parse: no digits give [`UrlParserErrors::PortNotNumeric`],
+/// an overflow gives [`UrlParserErrors::PortOutOfRange`] with `u64::MAX`.
+impl From<ParseIntError> for UrlParserErrors {
+    fn from(value: ParseIntError) -> Self {
+        match value.kind() {
+            IntErrorKind::PosOverflow => Self::PortOutOfRange(u64::MAX),
+            _ => Self::PortNotNumeric,
+        }
+    }
+}
+
+/// Represents possible errors when percent-decoding a path (see [`UrlParser::decoded_path`]).
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PercentError {
+    /// A `%` at the given byte offset is not followed by two hex digits.
+    InvalidEscape(usize),
+    /// The decoded bytes are not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl Display for PercentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidEscape(offset) => {
+                write!(
+                    f,
+                    "Invalid percent escape at byte {} => %XX expected",
+                    offset
+                )
+            }
+            Self::InvalidUtf8 => {
+                write!(f, "The decoded path is not valid UTF-8")
+            }
+        }
+    }
+}
+
+impl Error for PercentError {}
+
+/// Replaces every `%XX` escape of `input` with the byte it encodes.
+///
+/// `+` is left as is: it only means a space in query strings.
+fn percent_decode(input: &str) -> Result<String, PercentError> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or(PercentError::InvalidEscape(i))?;
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| PercentError::InvalidUtf8)
+}
+
 /// Helper macro to safely slice a string with error handling.
 ///
 /// subslice!({input}, {range}, {ErrorMessage})
@@ -223,7 +367,184 @@ impl TryFrom<&str> for UrlParser {
     }
 }
 
+impl TryFrom<String> for UrlParser {
+    type Error = UrlParserErrors;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        UrlParser::from_string(value)
+    }
+}
+
+impl TryFrom<&UrlParser> for SocketAddr {
+    type Error = UrlParserErrors;
+
+    /// Converts an IP-literal target into a [`SocketAddr`], using [`UrlParser::effective_port`].
+    ///
+    /// # Errors
+    /// Returns [`UrlParserErrors::NotAnIpTarget`] for DNS targets.
+    fn try_from(url: &UrlParser) -> Result<Self, Self::Error> {
+        let ip: IpAddr = match url.target_type {
+            TargetType::IPv4 => Ipv4Addr::from_str(&url.target)
+                .map_err(|_| UrlParserErrors::InvalidTargetType)?
+                .into(),
+            TargetType::IPv6 => Ipv6Addr::from_str(strip_ipv6_brackets(&url.target))
+                .map_err(|_| UrlParserErrors::InvalidTargetType)?
+                .into(),
+            TargetType::Dns => return Err(UrlParserErrors::NotAnIpTarget),
+        };
+
+        Ok(SocketAddr::new(ip, url.effective_port()))
+    }
+}
+
 impl UrlParser {
+    /// Returns the port to connect to.
+    ///
+    /// This is the explicit port of the URL, or the scheme's default
+    /// (`80` for `http`, `443` for `https`) when none was given.
+    pub fn effective_port(&self) -> u16 {
+        match self.port {
+            0 => self.scheme.default_port(),
+            port => port,
+        }
+    }
+
+    /// Returns the target in its canonical form.
+    ///
+    /// IPv6 targets are always bracketed (`[::1]`), so the result can be joined
+    /// with a port (`[::1]:80`). DNS and IPv4 targets are returned unchanged.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::utils::UrlParser;
+    ///
+    /// let url = UrlParser::new("http://[::1]:8080").unwrap();
+    /// assert_eq!(url.normalized_target(), "[::1]");
+    /// ```
+    pub fn normalized_target(&self) -> String {
+        match self.target_type {
+            TargetType::IPv6 => format!("[{}]", strip_ipv6_brackets(&self.target)),
+            TargetType::Dns | TargetType::IPv4 => self.target.clone(),
+        }
+    }
+
+    /// Returns the base domain (the last two labels) of a DNS target, lowercased.
+    ///
+    /// Useful to group results by site (`www.example.com` -> `example.com`).
+    /// This is a simple heuristic, **not** aware of the Public Suffix List:
+    /// `www.example.co.uk` gives `co.uk`. A trailing `.` is ignored and single-label
+    /// names (`localhost`) are returned as is.
+    ///
+    /// Returns `None` for IPv4/IPv6 targets.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::utils::UrlParser;
+    ///
+    /// let url = UrlParser::new("https://www.example.com").unwrap();
+    /// assert_eq!(url.base_domain().as_deref(), Some("example.com"));
+    /// ```
+    pub fn base_domain(&self) -> Option<String> {
+        if self.target_type != TargetType::Dns {
+            return None;
+        }
+
+        let name = self.target.strip_suffix('.').unwrap_or(&self.target);
+        let labels: Vec<&str> = name.rsplitn(3, '.').take(2).collect();
+        let base: Vec<&str> = labels.into_iter().rev().collect();
+
+        Some(base.join(".").to_ascii_lowercase())
+    }
+
+    /// Returns the path of the URL (the `subdirectory` without its query string
+    /// or fragment), percent-decoded.
+    ///
+    /// Dot-segments are **not** re-normalized: `/a/%2E%2E/b` gives `/a/../b`,
+    /// so the result must not be joined to a local directory without checking it.
+    ///
+    /// # Errors
+    /// Returns [`PercentError::InvalidEscape`] if a `%` is not followed by two hex
+    /// digits, or [`PercentError::InvalidUtf8`] if the decoded bytes aren't UTF-8.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::utils::UrlParser;
+    ///
+    /// let url = UrlParser::new("http://example.com/my%20files/?page=2").unwrap();
+    /// assert_eq!(url.decoded_path().unwrap(), "/my files/");
+    /// ```
+    pub fn decoded_path(&self) -> Result<String, PercentError> {
+        let end = self
+            .subdirectory
+            .find(['?', '#'])
+            .unwrap_or(self.subdirectory.len());
+
+        percent_decode(&self.subdirectory[..end])
+    }
+
+    /// Creates a new [`UrlParser`] from its parts, without going through a string.
+    ///
+    /// The target is validated like in [`UrlParser::new`] and IPv6 targets are
+    /// stored bracketed (`::1` -> `[::1]`). A `port` of `0` means "no port", and a
+    /// non-empty `path` missing its leading `/` gets one.
+    ///
+    /// # Errors
+    /// Returns [`UrlParserErrors::InvalidTargetType`] if the target is not a valid
+    /// DNS name, IPv4 or IPv6 address, or [`UrlParserErrors::TooLong`] if the
+    /// resulting URL is longer than [`MAX_URL_LEN`] bytes.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::utils::{Scheme, UrlParser};
+    ///
+    /// let url = UrlParser::from_parts(Scheme::Https, "example.com", 8443, "/api").unwrap();
+    /// assert_eq!(url.full_url, "https://example.com:8443/api");
+    /// ```
+    pub fn from_parts(
+        scheme: Scheme,
+        target: &str,
+        port: u16,
+        path: &str,
+    ) -> Result<UrlParser, UrlParserErrors> {
+        let target_type: TargetType = TargetType::is_ipv4(target)
+            .or(TargetType::is_ipv6(target))
+            .or(TargetType::is_dns(target))?;
+
+        let target = match target_type {
+            TargetType::IPv6 => format!("[{}]", strip_ipv6_brackets(target)),
+            TargetType::Dns | TargetType::IPv4 => target.to_string(),
+        };
+
+        let subdirectory = if path.is_empty() || path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{}", path)
+        };
+
+        let full_url = format_url(&scheme, &target, port, &subdirectory);
+        if full_url.len() > MAX_URL_LEN {
+            return Err(UrlParserErrors::TooLong(MAX_URL_LEN));
+        }
+
+        Ok(UrlParser {
+            scheme,
+            target,
+            target_type,
+            port,
+            subdirectory,
+            full_url,
+        })
+    }
+
+    /// Creates a new [`UrlParser`] from an owned `String`, e.g. a line read from the terminal.
+    ///
+    /// Same as [`UrlParser::new`], so the input doesn't have to be kept alive by the caller.
+    ///
+    /// # Errors
+    /// Returns [`UrlParserErrors`] under the same conditions as [`UrlParser::new`].
+    pub fn from_string(input_url: String) -> Result<UrlParser, UrlParserErrors> {
+        UrlParser::new(&input_url)
+    }
+
     /// Creates a new [`UrlParser`] from a `Terminal` input.
     ///
     /// # Errors
@@ -231,6 +552,7 @@ impl UrlParser {
     /// - The scheme is invalid
     /// - The host is invalid
     /// - The URL is empty
+    /// - The URL is longer than [`MAX_URL_LEN`] bytes
     ///
     /// # Example
     ///  ```rust,no_run
@@ -243,10 +565,35 @@ impl UrlParser {
     /// // Also usable via `parse` (FromStr) or `TryFrom`
     /// let url2: UrlParser = "<http://example.com>".parse().unwrap();
     /// let url3 = UrlParser::try_from("<http://example.com>").unwrap();
+    /// let url4 = UrlParser::try_from(String::from("<http://example.com>")).unwrap();
     /// ```
     pub fn new(input_url: &str) -> Result<UrlParser, UrlParserErrors> {
+        UrlParser::with_max_len(input_url, MAX_URL_LEN)
+    }
+
+    /// Creates a new [`UrlParser`], accepting inputs of at most `max_len` bytes.
+    ///
+    /// Same as [`UrlParser::new`] (which uses [`MAX_URL_LEN`]), for callers that need a
+    /// stricter or looser bound, e.g. when ingesting untrusted target lists.
+    ///
+    /// # Errors
+    /// Returns [`UrlParserErrors::TooLong`] if `input_url` is longer than `max_len`,
+    /// otherwise the same errors as [`UrlParser::new`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use stalkermap::utils::{UrlParser, UrlParserErrors};
+    ///
+    /// let err = UrlParser::with_max_len("http://example.com", 10).unwrap_err();
+    /// assert!(matches!(err, UrlParserErrors::TooLong(10)));
+    /// ```
+    pub fn with_max_len(input_url: &str, max_len: usize) -> Result<UrlParser, UrlParserErrors> {
         let url = input_url;
 
+        if url.len() > max_len {
+            return Err(UrlParserErrors::TooLong(max_len));
+        }
+
         if url.is_empty() {
             return Err(UrlParserErrors::UrlEmpty);
         }
@@ -265,99 +612,52 @@ impl UrlParser {
             return Err(UrlParserErrors::InvalidScheme);
         };
 
-        let target: String = {
-            match scheme {
-                Scheme::Http => {
-                    if url[7..].starts_with('[') {
-                        url[7..]
-                            .chars()
-                            .take_while(|c| *c != ']')
-                            .chain(std::iter::once(']'))
-                            .collect()
-                    } else {
-                        url.chars()
-                            .skip(7)
-                            .take_while(|c| *c != ':' && *c != '/')
-                            .collect()
-                    }
-                }
-                Scheme::Https => {
-                    if url[8..].starts_with('[') {
-                        url[8..]
-                            .chars()
-                            .take_while(|c| *c != ']')
-                            .chain(std::iter::once(']'))
-                            .collect()
-                    } else {
-                        url.chars()
-                            .skip(8)
-                            .take_while(|c| *c != ':' && *c != '/')
-                            .collect()
-                    }
-                }
+        // Single forward pass over byte indices: the scheme and (validated) target are
+        // ASCII, so every index computed below falls on a char boundary.
+        let scheme_len = match scheme {
+            Scheme::Http => "http://".len(),
+            Scheme::Https => "https://".len(),
+        };
+        let rest = &url[scheme_len..];
+
+        let target: String = if rest.starts_with('[') {
+            match rest.find(']') {
+                Some(end) => rest[..=end].to_string(),
+                None => format!("{}]", rest),
             }
+        } else {
+            let end = rest.find([':', '/']).unwrap_or(rest.len());
+            rest[..end].to_string()
         };
 
         let target_type: TargetType = TargetType::is_ipv4(&target)
             .or(TargetType::is_ipv6(&target))
             .or(TargetType::is_dns(&target))?;
 
-        let quant_to_skip = match scheme {
-            Scheme::Http => "http://".len() + target.len(),
-            Scheme::Https => "https://".len() + target.len(),
-        };
+        let target_end = scheme_len + target.len();
+
+        // `port_len` is the length of `:{port}` as written in the url, which can
+        // differ from the parsed value (e.g. leading zeros in `:0080`).
+        let (port, port_len): (u16, usize) = match url.as_bytes().get(target_end) {
+            Some(b':') => {
+                let after_colon = &url[target_end + 1..];
+                let digits_len = after_colon
+                    .bytes()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
 
-        let port: u16 = {
-            if quant_to_skip >= url.len() {
-                0
-            } else if url
-                .chars()
-                .nth(quant_to_skip)
-                .ok_or(UrlParserErrors::InvalidSize)?
-                == ':'
-            {
-                let string_port_temp: String = url
-                    .chars()
-                    .skip(quant_to_skip + 1)
-                    .take_while(|v| v.is_ascii_digit())
-                    .collect();
-
-                match string_port_temp.parse::<u16>() {
-                    Ok(port) => port,
-                    Err(_) => return Err(UrlParserErrors::InvalidPort),
-                }
-            } else {
-                0
+                // Parsed as `u64` first so an out of range error can report the value.
+                let port: u64 = after_colon[..digits_len].parse()?;
+                let port =
+                    u16::try_from(port).map_err(|_| UrlParserErrors::PortOutOfRange(port))?;
+                (port, 1 + digits_len)
             }
+            _ => (0, 0),
         };
 
-        let subdirectory = {
-            let chars_to_skip = {
-                match scheme {
-                    Scheme::Http => "http://".len(),
-                    Scheme::Https => "https://".len(),
-                }
-            } + target.len()
-                + {
-                    match port {
-                        0 => 0,
-                        n => format!(":{}", n).len(),
-                    }
-                };
-            let subdirectory: String = url.chars().skip(chars_to_skip).collect();
-            subdirectory
-        };
+        let subdirectory = url.get(target_end + port_len..).unwrap_or("").to_string();
 
-        let full_url = format!(
-            "{}://{}{}{}",
-            scheme,
-            target,
-            match port {
-                0 => String::new(),
-                n => format!(":{}", n),
-            },
-            subdirectory
-        );
+        let full_url = format_url(&scheme, &target, port, &subdirectory);
 
         Ok(UrlParser {
             scheme,
@@ -434,6 +734,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_url_urlparser_port_with_leading_zeros() {
+        let url = UrlParser::new("http://h:0080/x").unwrap();
+        assert_eq!(url.target, "h");
+        assert_eq!(url.port, 80);
+        assert_eq!(url.subdirectory, "/x");
+        assert_eq!(url.full_url, "http://h:80/x");
+    }
+
     #[test]
     fn test_url_urlparser_valid_ipv4() {
         let url = UrlParser::new("http://127.0.0.1").unwrap();
@@ -494,6 +803,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_url_urlparser_from_parts() {
+        let url = UrlParser::from_parts(Scheme::Https, "example.com", 8443, "/api").unwrap();
+        assert_eq!(url.full_url, "https://example.com:8443/api");
+        assert_eq!(url.target_type, TargetType::Dns);
+        assert_eq!(url, UrlParser::new("https://example.com:8443/api").unwrap());
+
+        let url = UrlParser::from_parts(Scheme::Http, "::1", 0, "status").unwrap();
+        assert_eq!(url.target, "[::1]");
+        assert_eq!(url.full_url, "http://[::1]/status");
+
+        let res = UrlParser::from_parts(Scheme::Http, "exa$mple.com", 80, "");
+        assert!(matches!(res, Err(UrlParserErrors::InvalidTargetType)));
+    }
+
+    #[test]
+    fn test_url_decoded_path() {
+        let url = UrlParser::new("http://example.com/a%20b/c?q=%20#top").unwrap();
+        assert_eq!(url.decoded_path().unwrap(), "/a b/c");
+
+        let url = UrlParser::new("http://example.com/%2e%2E/caf%C3%A9").unwrap();
+        assert_eq!(url.decoded_path().unwrap(), "/../café");
+
+        let url = UrlParser::new("http://example.com").unwrap();
+        assert_eq!(url.decoded_path().unwrap(), "");
+
+        let url = UrlParser::new("http://example.com/a%2").unwrap();
+        assert_eq!(url.decoded_path(), Err(PercentError::InvalidEscape(2)));
+
+        let url = UrlParser::new("http://example.com/%zz").unwrap();
+        assert_eq!(url.decoded_path(), Err(PercentError::InvalidEscape(1)));
+
+        let url = UrlParser::new("http://example.com/%FF").unwrap();
+        assert_eq!(url.decoded_path(), Err(PercentError::InvalidUtf8));
+    }
+
     #[test]
     fn test_url_urlparser_invalid_empty_url() {
         let res = UrlParser::new("");
@@ -506,6 +851,21 @@ mod tests {
         assert!(matches!(res, Err(UrlParserErrors::InvalidSchemeSyntax)));
     }
 
+    #[test]
+    fn test_url_is_dns_trailing_dot() {
+        assert!(matches!(
+            TargetType::is_dns("example.com"),
+            Ok(TargetType::Dns)
+        ));
+        assert!(matches!(
+            TargetType::is_dns("example.com."),
+            Ok(TargetType::Dns)
+        ));
+        assert!(TargetType::is_dns("example..com").is_err());
+        assert!(TargetType::is_dns("example.com..").is_err());
+        assert!(TargetType::is_dns(".").is_err());
+    }
+
     #[test]
     fn test_url_urlparser_invalid_dns() {
         let res = UrlParser::new("http://exa$mple.com");
@@ -515,12 +875,176 @@ mod tests {
     #[test]
     fn test_url_urlparser_invalid_port_not_number() {
         let res = UrlParser::new("http://example.com:abcd");
-        assert!(matches!(res, Err(UrlParserErrors::InvalidPort)));
+        assert!(matches!(res, Err(UrlParserErrors::PortNotNumeric)));
+        let res = UrlParser::new("http://h:abc");
+        assert!(matches!(res, Err(UrlParserErrors::PortNotNumeric)));
     }
 
     #[test]
     fn test_url_urlparser_invalid_port_out_of_range() {
         let res = UrlParser::new("http://example.com:70000");
-        assert!(matches!(res, Err(UrlParserErrors::InvalidPort)));
+        assert!(matches!(res, Err(UrlParserErrors::PortOutOfRange(70000))));
+        let res = UrlParser::new("http://h:99999");
+        assert!(matches!(res, Err(UrlParserErrors::PortOutOfRange(99999))));
+        let res = UrlParser::new("http://h:99999999999999999999999");
+        assert!(matches!(
+            res,
+            Err(UrlParserErrors::PortOutOfRange(u64::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_url_scheme_default_port_http() {
+        assert_eq!(Scheme::Http.default_port(), 80);
+    }
+
+    #[test]
+    fn test_url_scheme_default_port_https() {
+        assert_eq!(Scheme::Https.default_port(), 443);
+        let url = UrlParser::new("https://example.com").unwrap();
+        assert_eq!(url.effective_port(), 443);
+    }
+
+    #[test]
+    fn test_url_is_ipv6_bracketed_and_unbracketed() {
+        assert_eq!(TargetType::is_ipv6("::1").unwrap(), TargetType::IPv6);
+        assert_eq!(TargetType::is_ipv6("[::1]").unwrap(), TargetType::IPv6);
+        assert!(TargetType::is_ipv6("[::1").is_err());
+        assert!(TargetType::is_ipv6("::1]").is_err());
+    }
+
+    #[test]
+    fn test_url_base_domain() {
+        let base = |url: &str| UrlParser::new(url).unwrap().base_domain();
+
+        assert_eq!(
+            base("http://www.example.com").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(base("http://example.com").as_deref(), Some("example.com"));
+        assert_eq!(
+            base("https://a.b.Example.COM.:8443/x").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(base("http://localhost").as_deref(), Some("localhost"));
+        assert_eq!(base("http://127.0.0.1"), None);
+        assert_eq!(base("http://[::1]"), None);
+    }
+
+    #[test]
+    fn test_url_normalized_target_ipv6() {
+        let ipv6_url = |target: &str| UrlParser {
+            scheme: Scheme::Http,
+            target: target.to_string(),
+            target_type: TargetType::is_ipv6(target).unwrap(),
+            port: 80,
+            subdirectory: String::new(),
+            full_url: String::new(),
+        };
+
+        assert_eq!(ipv6_url("::1").normalized_target(), "[::1]");
+        assert_eq!(ipv6_url("[::1]").normalized_target(), "[::1]");
+
+        let parsed = UrlParser::new("http://[::1]:80").unwrap();
+        assert_eq!(parsed.normalized_target(), "[::1]");
+
+        let dns = UrlParser::new("http://example.com").unwrap();
+        assert_eq!(dns.normalized_target(), "example.com");
+    }
+
+    #[test]
+    fn test_url_socket_addr_from_ip_target() {
+        let url = UrlParser::new("http://127.0.0.1:8080").unwrap();
+        let addr = SocketAddr::try_from(&url).unwrap();
+        assert_eq!(addr, SocketAddr::from_str("127.0.0.1:8080").unwrap());
+
+        let url = UrlParser::new("https://[::1]").unwrap();
+        let addr = SocketAddr::try_from(&url).unwrap();
+        assert_eq!(addr, SocketAddr::from_str("[::1]:443").unwrap());
+    }
+
+    #[test]
+    fn test_url_socket_addr_from_dns_target_fails() {
+        let url = UrlParser::new("http://example.com").unwrap();
+        assert!(matches!(
+            SocketAddr::try_from(&url),
+            Err(UrlParserErrors::NotAnIpTarget)
+        ));
+    }
+
+    #[test]
+    fn test_url_errors_display_known_variants() {
+        let describe = |e: &UrlParserErrors| match e {
+            UrlParserErrors::UrlEmpty | UrlParserErrors::PortOutOfRange(_) => e.to_string(),
+            _ => format!("other: {}", e),
+        };
+
+        assert_eq!(describe(&UrlParserErrors::UrlEmpty), "The url is empty");
+        assert_eq!(
+            describe(&UrlParserErrors::PortOutOfRange(70000)),
+            "Invalid port => 70000 is out of range (1 -> 65,535)"
+        );
+        assert_eq!(
+            describe(&UrlParserErrors::NotAnIpTarget),
+            "other: The target is not an IP address => Must be a IPV4 or IPV6"
+        );
+    }
+
+    #[test]
+    fn test_url_urlparser_from_owned_string() {
+        let input = "https://127.0.0.1:8443/api?q=1";
+        let borrowed = UrlParser::new(input).unwrap();
+
+        assert_eq!(UrlParser::from_string(input.to_string()).unwrap(), borrowed);
+        assert_eq!(UrlParser::try_from(input.to_string()).unwrap(), borrowed);
+        assert!(matches!(
+            UrlParser::from_string(String::new()),
+            Err(UrlParserErrors::UrlEmpty)
+        ));
+    }
+
+    #[test]
+    fn test_url_urlparser_rejects_too_long() {
+        let long = format!("http://example.com/{}", "a".repeat(3000));
+        assert!(matches!(
+            UrlParser::new(&long),
+            Err(UrlParserErrors::TooLong(MAX_URL_LEN))
+        ));
+
+        let url = UrlParser::new("http://example.com:8080/index").unwrap();
+        assert_eq!(url.target, "example.com");
+        assert_eq!(url.port, 8080);
+
+        assert!(UrlParser::with_max_len(&long, long.len()).is_ok());
+        assert!(matches!(
+            UrlParser::with_max_len("http://example.com", 10),
+            Err(UrlParserErrors::TooLong(10))
+        ));
+    }
+
+    #[test]
+    fn test_url_urlparser_batch_parse_fields() {
+        // Parse a representative batch, as the scanner would, checking every field.
+        for port in 1..=10_000u16 {
+            let input = format!("https://[2001:db8::1]:{}/api/v1?q=stalker", port);
+            let url = UrlParser::new(&input).unwrap();
+
+            assert_eq!(url.scheme, Scheme::Https);
+            assert_eq!(url.target, "[2001:db8::1]");
+            assert_eq!(url.target_type, TargetType::IPv6);
+            assert_eq!(url.port, port);
+            assert_eq!(url.subdirectory, "/api/v1?q=stalker");
+            assert_eq!(url.full_url, input);
+        }
+
+        let url = UrlParser::new("http://10.0.0.1:8080abc").unwrap();
+        assert_eq!(url.target_type, TargetType::IPv4);
+        assert_eq!(url.port, 8080);
+        assert_eq!(url.subdirectory, "abc");
+
+        let url = UrlParser::new("http://example.com/ação").unwrap();
+        assert_eq!(url.port, 0);
+        assert_eq!(url.subdirectory, "/ação");
+        assert_eq!(url.full_url, "http://example.com/ação");
     }
 }